@@ -1,7 +1,57 @@
 use super::BlockAndPatch;
-use crate::core::{Address, Ratio, TokenId};
+use crate::core::{Address, Amount, ChainId, ConfigParam, Ratio, TokenId, TransactionData};
 use crate::mpn::MpnConfig;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+// Consensus rules that are switched on at a given height
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    Jailing,          // Liveness tracking and `Unjail` transactions
+    Governance,       // `Propose`/`Vote` transactions and tallying of proposals
+    ReplayProtection, // Signatures only accepted when bound to the chain-id
+    BaseFee,          // Fees should cover a per-byte base fee, which is burned
+    StateRoot,        // Headers commit to the account state after their block
+    // MPN transactions signed with the chain-id, proven by the chain-bound update circuit
+    MpnReplayProtection,
+}
+
+impl Feature {
+    // Feature that should be active for the transaction to be accepted
+    pub fn required_by(data: &TransactionData) -> Option<Feature> {
+        match data {
+            TransactionData::Unjail => Some(Feature::Jailing),
+            TransactionData::Propose { .. } | TransactionData::Vote { .. } => {
+                Some(Feature::Governance)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ForkSchedule {
+    activations: HashMap<Feature, u64>,
+}
+
+impl ForkSchedule {
+    pub fn new(activations: &[(Feature, u64)]) -> Self {
+        Self {
+            activations: activations.iter().cloned().collect(),
+        }
+    }
+    pub fn activate(&mut self, feature: Feature, height: u64) {
+        self.activations.insert(feature, height);
+    }
+    pub fn activation_height(&self, feature: Feature) -> Option<u64> {
+        self.activations.get(&feature).cloned()
+    }
+    // Features missing from the schedule are never activated
+    pub fn is_active(&self, feature: Feature, height: u64) -> bool {
+        self.activation_height(feature)
+            .map(|h| height >= h)
+            .unwrap_or(false)
+    }
+}
 
 #[derive(Clone)]
 pub struct BlockchainConfig {
@@ -13,10 +63,63 @@ pub struct BlockchainConfig {
     pub ziesha_token_id: TokenId,
     pub mpn_config: MpnConfig,
     pub testnet_height_limit: Option<u64>,
+    pub fork_schedule: ForkSchedule,
     pub max_memo_length: usize,
     pub slot_duration: u32,
     pub slot_per_epoch: u32,
     pub chain_start_timestamp: u32,
     pub check_validator: bool,
     pub max_validator_commission: Ratio,
+    pub equivocation_slash_ratio: Ratio,
+    pub unbonding_period: u64,
+    pub max_missed_slots: u32,
+    pub max_validators: usize,
+    pub proposal_voting_period: u64,
+    pub proposal_approval_ratio: Ratio,
+    pub min_base_fee: Amount, // Per byte, in Ziesha
+}
+
+// Parameters of the config that can be changed through governance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GovernableParams {
+    pub max_block_size: usize,
+    pub max_delta_count: usize,
+    pub reward_ratio: u64,
+    pub max_memo_length: usize,
+    pub max_validator_commission: Ratio,
+}
+
+impl GovernableParams {
+    pub fn apply(&mut self, param: &ConfigParam) {
+        match param {
+            ConfigParam::MaxBlockSize(v) => self.max_block_size = *v,
+            ConfigParam::MaxDeltaCount(v) => self.max_delta_count = *v,
+            ConfigParam::RewardRatio(v) => self.reward_ratio = *v,
+            ConfigParam::MaxMemoLength(v) => self.max_memo_length = *v,
+            ConfigParam::MaxValidatorCommission(v) => self.max_validator_commission = *v,
+        }
+    }
+}
+
+impl BlockchainConfig {
+    // Wallets derive the chain-id locally instead of trusting the node they talk to
+    pub fn chain_id(&self) -> ChainId {
+        self.genesis.block.header.hash()
+    }
+    pub fn governable_params(&self) -> GovernableParams {
+        GovernableParams {
+            max_block_size: self.max_block_size,
+            max_delta_count: self.max_delta_count,
+            reward_ratio: self.reward_ratio,
+            max_memo_length: self.max_memo_length,
+            max_validator_commission: self.max_validator_commission,
+        }
+    }
+
+    pub fn epoch_slot(&self, timestamp: u32) -> (u32, u32) {
+        // TODO: Error instead of saturating_sub!
+        let slot_number = timestamp.saturating_sub(self.chain_start_timestamp) / self.slot_duration;
+        let epoch_number = slot_number / self.slot_per_epoch;
+        (epoch_number, slot_number % self.slot_per_epoch)
+    }
 }
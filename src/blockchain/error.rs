@@ -29,6 +29,8 @@ pub enum BlockchainError {
     InvalidParentHash,
     #[error("merkle root invalid")]
     InvalidMerkleRoot,
+    #[error("state root invalid")]
+    InvalidStateRoot,
     #[error("transaction nonce invalid")]
     InvalidTransactionNonce,
     #[error("block timestamp is in past")]
@@ -63,6 +65,10 @@ pub enum BlockchainError {
     CompressedStateNotFound,
     #[error("no blocks to roll back")]
     NoBlocksToRollback,
+    #[error("cannot roll back pruned blocks")]
+    RollbackBeyondPruned,
+    #[error("block is pruned")]
+    BlockPruned,
     #[error("zk error happened: {0}")]
     ZkError(#[from] ZkError),
     #[error("state-manager error happened: {0}")]
@@ -99,8 +105,6 @@ pub enum BlockchainError {
     TokenSupplyOverflow,
     #[error("token has an invalid name/symbol")]
     TokenBadNameSymbol,
-    #[error("only ziesha fees are accepted!")]
-    OnlyZieshaFeesAccepted,
     #[error("transaction memo is too long")]
     MemoTooLong,
     #[error("Wrong validator has built the block!")]
@@ -127,4 +131,66 @@ pub enum BlockchainError {
     UndelegationNotFound,
     #[error("undelegation still locked")]
     UndelegationLocked,
+    #[error("header signature is invalid")]
+    InvalidHeaderSignature,
+    #[error("equivocation evidence is invalid")]
+    InvalidEquivocationEvidence,
+    #[error("equivocation has already been reported")]
+    EquivocationAlreadyReported,
+    #[error("cannot redelegate to the same staker")]
+    SelfRedelegation,
+    #[error("staker is not jailed")]
+    StakerNotJailed,
+    #[error("proposal is invalid")]
+    InvalidProposal,
+    #[error("proposal not found")]
+    ProposalNotFound,
+    #[error("voter has no stake")]
+    VoterHasNoStake,
+    #[error("feature is not active at this height")]
+    FeatureNotActive,
+    #[error("transaction has expired")]
+    TransactionExpired,
+    #[error("multisig is invalid")]
+    InvalidMultisig,
+    #[error("multisig already exists")]
+    MultisigAlreadyExists,
+    #[error("multisig not found")]
+    MultisigNotFound,
+    #[error("vesting is invalid")]
+    InvalidVesting,
+    #[error("htlc not found")]
+    HtlcNotFound,
+    #[error("htlc preimage is invalid")]
+    InvalidHtlcPreimage,
+    #[error("htlc has timed out")]
+    HtlcTimedOut,
+    #[error("htlc has not timed out yet")]
+    HtlcNotTimedOut,
+    #[error("token metadata is invalid")]
+    TokenBadMetadata,
+    #[error("account is frozen for this token")]
+    TokenAccountFrozen,
+    #[error("fee does not cover the base fee")]
+    FeeBelowBaseFee,
+    #[error("allowance insufficient")]
+    AllowanceInsufficient,
+    #[error("chain is not powerful enough")]
+    WeakChain,
+    #[error("header is not in the chain")]
+    UnknownHeader,
+    #[error("state proof invalid")]
+    InvalidStateProof,
+    #[error("validators of epoch {0} are not proven")]
+    ValidatorsUnknown(u32),
+    #[error("validators proof of the epoch not found")]
+    ValidatorsProofNotFound,
+    #[error("snapshot does not match the trusted header")]
+    UntrustedSnapshot,
+    #[error("snapshot checksum is invalid")]
+    InvalidSnapshotChecksum,
+    #[error("snapshots can only be imported into empty databases")]
+    SnapshotDatabaseNotEmpty,
+    #[error("verifier key of the chain-bound mpn update circuit is not configured")]
+    ChainBoundMpnKeyMissing,
 }
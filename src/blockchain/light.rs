@@ -0,0 +1,295 @@
+use super::*;
+use crate::crypto::merkle::verify_inclusion;
+use crate::db::{Blob, RamKvStore};
+
+/// Validator-set data that light clients need for checking the VRF proofs of the headers,
+/// proven against the state root of a header
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidatorsProof {
+    pub validator_set: Vec<(Address, Amount)>,
+    pub validator_set_proof: SparseMerkleProof<Hasher>,
+    pub randomness: <Hasher as Hash>::Output,
+    pub randomness_proof: SparseMerkleProof<Hasher>,
+    pub stakers: Vec<(Address, Staker)>,
+    pub stakers_proof: SparseMerkleProof<Hasher>,
+}
+
+/// Chains that wallets can work against. Full chains keep the whole state, while light
+/// chains only keep the headers and check the proofs provided by full nodes against them.
+pub trait HeaderChain {
+    fn get_height(&self) -> Result<u64, BlockchainError>;
+    fn get_header(&self, index: u64) -> Result<Header, BlockchainError>;
+
+    fn get_tip(&self) -> Result<Header, BlockchainError> {
+        self.get_header(self.get_height()? - 1)
+    }
+
+    fn is_known(&self, header: &Header) -> Result<bool, BlockchainError> {
+        Ok(header.number < self.get_height()? && self.get_header(header.number)? == *header)
+    }
+
+    fn verify_balance(
+        &self,
+        address: &Address,
+        token_id: TokenId,
+        balance: Amount,
+        header: &Header,
+        proof: &SparseMerkleProof<Hasher>,
+    ) -> Result<bool, BlockchainError> {
+        Ok(self.is_known(header)?
+            && verify_balance_proof(header, address, token_id, balance, proof))
+    }
+
+    fn verify_transaction(
+        &self,
+        tx: &Transaction,
+        header: &Header,
+        proof: &[<Hasher as Hash>::Output],
+    ) -> Result<bool, BlockchainError> {
+        Ok(self.is_known(header)? && verify_inclusion(header, tx, proof))
+    }
+}
+
+impl<K: KvStore> HeaderChain for KvStoreChain<K> {
+    fn get_height(&self) -> Result<u64, BlockchainError> {
+        Blockchain::get_height(self)
+    }
+    fn get_header(&self, index: u64) -> Result<Header, BlockchainError> {
+        Blockchain::get_header(self, index)
+    }
+}
+
+/// Validators of an epoch, proven against the state root of a header in that epoch
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EpochValidators {
+    header: Header,
+    validator_set: Vec<(Address, Amount)>,
+    randomness: <Hasher as Hash>::Output,
+    stakers: HashMap<Address, Staker>,
+}
+
+impl EpochValidators {
+    fn is_elected(&self, config: &BlockchainConfig, header: &Header) -> bool {
+        let validator = &header.proof_of_stake.validator;
+        match self.stakers.get(validator) {
+            Some(staker) => is_elected(
+                &self.validator_set,
+                &self.randomness,
+                config.epoch_slot(header.proof_of_stake.timestamp).1,
+                validator,
+                staker,
+                &header.proof_of_stake.proof,
+            ),
+            None => false,
+        }
+    }
+}
+
+/// A chain of headers, synced without downloading the blocks
+pub struct LightChain<K: KvStore> {
+    config: BlockchainConfig,
+    database: K,
+}
+
+impl<K: KvStore> HeaderChain for LightChain<K> {
+    fn get_height(&self) -> Result<u64, BlockchainError> {
+        Ok(match self.database.get(keys::height())? {
+            Some(b) => b.try_into()?,
+            None => 0,
+        })
+    }
+    fn get_header(&self, index: u64) -> Result<Header, BlockchainError> {
+        if index >= self.get_height()? {
+            return Err(BlockchainError::BlockNotFound);
+        }
+        Ok(match self.database.get(keys::header(index))? {
+            Some(b) => b.try_into()?,
+            None => {
+                return Err(BlockchainError::Inconsistency);
+            }
+        })
+    }
+}
+
+impl<K: KvStore> LightChain<K> {
+    pub fn new(database: K, config: BlockchainConfig) -> Result<Self, BlockchainError> {
+        let genesis = config.genesis.block.header.clone();
+        let mut chain = Self { config, database };
+        match chain.database.get(keys::header(0))? {
+            Some(b) => {
+                let stored: Header = b.try_into()?;
+                if stored != genesis {
+                    return Err(BlockchainError::DifferentGenesis);
+                }
+            }
+            None => {
+                // Validators of the first epoch are known from the genesis block itself
+                let genesis_chain = KvStoreChain::new(RamKvStore::new(), chain.config.clone())?;
+                let validators = EpochValidators {
+                    header: genesis.clone(),
+                    validator_set: genesis_chain.get_validator_set()?,
+                    randomness: genesis_chain.epoch_randomness()?,
+                    stakers: genesis_chain.get_validator_stakers()?.into_iter().collect(),
+                };
+                chain.database.update(&[
+                    WriteOp::Put(keys::height(), 1u64.into()),
+                    WriteOp::Put(keys::header(0), genesis.clone().into()),
+                    WriteOp::Put(
+                        keys::light_validators(chain.epoch(&genesis)),
+                        validators.into(),
+                    ),
+                ])?;
+            }
+        }
+        Ok(chain)
+    }
+
+    pub fn epoch(&self, header: &Header) -> u32 {
+        self.config.epoch_slot(header.proof_of_stake.timestamp).0
+    }
+
+    fn get_validators(&self, epoch: u32) -> Result<Option<EpochValidators>, BlockchainError> {
+        Ok(match self.database.get(keys::light_validators(epoch))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
+
+    fn check_header(&self, parent: &Header, header: &Header) -> Result<(), BlockchainError> {
+        if header.number != parent.number + 1 {
+            return Err(BlockchainError::InvalidBlockNumber);
+        }
+        if header.parent_hash != parent.hash() {
+            return Err(BlockchainError::InvalidParentHash);
+        }
+        if header.proof_of_stake.timestamp < parent.proof_of_stake.timestamp {
+            return Err(BlockchainError::InvalidTimestamp);
+        }
+        if !header.verify_signature() {
+            return Err(BlockchainError::InvalidHeaderSignature);
+        }
+        Ok(())
+    }
+
+    /// Extends the chain with headers starting from the given height, replacing the
+    /// existing headers when the new chain is longer. Parent hashes, timestamps,
+    /// signatures and VRF proofs are checked. Validators are elected based on the state
+    /// after the parent block, so the validators of the epoch of each parent should have
+    /// been proven (Through `update_validators`) by a header of the new chain, otherwise
+    /// none of the headers are accepted.
+    pub fn extend(&mut self, from: u64, headers: &[Header]) -> Result<(), BlockchainError> {
+        let height = self.get_height()?;
+        if from == 0 {
+            return Err(BlockchainError::ExtendFromGenesis);
+        } else if from > height {
+            return Err(BlockchainError::ExtendFromFuture);
+        }
+        let new_height = from + headers.len() as u64;
+        if new_height <= height {
+            return Err(BlockchainError::WeakChain);
+        }
+
+        let mut parent = self.get_header(from - 1)?;
+        for header in headers.iter() {
+            self.check_header(&parent, header)?;
+            if self.config.check_validator {
+                let epoch = self.epoch(&parent);
+                let validators = match self.get_validators(epoch)? {
+                    Some(v) if v.header.number <= parent.number => {
+                        let in_chain = if v.header.number < from {
+                            self.get_header(v.header.number)?
+                        } else {
+                            headers[(v.header.number - from) as usize].clone()
+                        };
+                        (in_chain == v.header).then_some(v)
+                    }
+                    _ => None,
+                }
+                .ok_or(BlockchainError::ValidatorsUnknown(epoch))?;
+                if !validators.is_elected(&self.config, header) {
+                    return Err(BlockchainError::UnelectedValidator);
+                }
+            }
+            parent = header.clone();
+        }
+
+        let mut ops = vec![WriteOp::Put(keys::height(), new_height.into())];
+        for header in headers.iter() {
+            ops.push(WriteOp::Put(
+                keys::header(header.number),
+                header.clone().into(),
+            ));
+        }
+        self.database.update(&ops)?;
+        Ok(())
+    }
+
+    /// Learns the validators of the epoch of a header in the chain, given their proofs
+    /// against its state root. Proofs should be taken at the first header of the epoch,
+    /// since the headers before it in the same epoch cannot be checked with them.
+    pub fn update_validators(
+        &mut self,
+        header: &Header,
+        proof: &ValidatorsProof,
+    ) -> Result<(), BlockchainError> {
+        if !self.is_known(header)? {
+            return Err(BlockchainError::UnknownHeader);
+        }
+        let validator_set: Blob = proof.validator_set.clone().into();
+        let randomness: Blob = proof.randomness.into();
+        // Randomness is not stored before the first epoch change
+        let randomness_proven = ops::verify_state_proof(
+            header,
+            &keys::randomness(),
+            Some(&randomness),
+            &proof.randomness_proof,
+        ) || (proof.randomness == <Hasher as Hash>::Output::default()
+            && ops::verify_state_proof(header, &keys::randomness(), None, &proof.randomness_proof));
+        if !randomness_proven
+            || !ops::verify_state_proof(
+                header,
+                &keys::validator_set(),
+                Some(&validator_set),
+                &proof.validator_set_proof,
+            )
+        {
+            return Err(BlockchainError::InvalidStateProof);
+        }
+        // Stakers are frozen along with the validator-set, so VRF keys changed later in
+        // the epoch are not used before the next one, just like on full nodes
+        let stakers: Blob = proof.stakers.clone().into();
+        if !ops::verify_state_proof(
+            header,
+            &keys::validator_stakers(),
+            Some(&stakers),
+            &proof.stakers_proof,
+        ) {
+            return Err(BlockchainError::InvalidStateProof);
+        }
+
+        let validators = EpochValidators {
+            header: header.clone(),
+            validator_set: proof.validator_set.clone(),
+            randomness: proof.randomness,
+            stakers: proof.stakers.iter().cloned().collect(),
+        };
+        self.database.update(&[WriteOp::Put(
+            keys::light_validators(self.epoch(header)),
+            validators.into(),
+        )])?;
+        Ok(())
+    }
+}
+
+pub fn prove_validators<K: KvStore>(
+    chain: &KvStoreChain<K>,
+) -> Result<ValidatorsProof, BlockchainError> {
+    Ok(ValidatorsProof {
+        validator_set: chain.get_validator_set()?,
+        validator_set_proof: ops::prove_state(chain, &keys::validator_set())?,
+        randomness: chain.epoch_randomness()?,
+        randomness_proof: ops::prove_state(chain, &keys::randomness())?,
+        stakers: chain.get_validator_stakers()?,
+        stakers_proof: ops::prove_state(chain, &keys::validator_stakers())?,
+    })
+}
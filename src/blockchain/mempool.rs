@@ -94,6 +94,12 @@ impl SingleMempool {
         }
         self.nonce = nonce;
     }
+    // Txs after an expired one can't be applied anymore, as their nonces would be skipped
+    fn remove_expired(&mut self, height: u64) {
+        if let Some(pos) = self.txs.iter().position(|(tx, _)| tx.is_expired(height)) {
+            self.txs.truncate(pos);
+        }
+    }
     fn reset(&mut self, nonce: u32) {
         if nonce == 0 {
             self.txs.clear();
@@ -139,6 +145,7 @@ impl Mempool {
         _max_time_remember: Option<u32>,
     ) -> Result<(), BlockchainError> {
         let mpn_contract_id = blockchain.config().mpn_config.mpn_contract_id;
+        let height = blockchain.get_height()?;
         for (ng, mempool) in self.txs.iter_mut() {
             let nonce = match ng.clone() {
                 NonceGroup::TransactionAndDelta(addr) => blockchain.get_nonce(addr)?,
@@ -149,6 +156,7 @@ impl Mempool {
                 NonceGroup::MpnWithdraw(addr) => blockchain.get_mpn_account(addr)?.withdraw_nonce,
             };
             mempool.update_nonce(nonce);
+            mempool.remove_expired(height);
         }
         Ok(())
     }
@@ -166,7 +174,17 @@ impl Mempool {
         if is_local {
             self.rejected.remove(&tx);
         }
-        if self.rejected.contains_key(&tx) || !tx.verify_signature() {
+        let height = blockchain.get_height()?;
+        // MPN transactions are proven by a single update circuit, bound or not
+        let chain_ids = if let GeneralTransaction::MpnTransaction(_) = &tx {
+            vec![blockchain.mpn_chain_id(height)]
+        } else {
+            blockchain.accepted_chain_ids(height)
+        };
+        if self.rejected.contains_key(&tx)
+            || tx.is_expired(height)
+            || !chain_ids.iter().any(|id| tx.verify_signature(id.as_ref()))
+        {
             return Ok(());
         }
         let nonce = match tx.nonce_group() {
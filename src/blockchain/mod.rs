@@ -3,16 +3,22 @@ pub use error::*;
 mod mempool;
 pub use mempool::*;
 mod config;
-pub use config::BlockchainConfig;
+pub use config::{BlockchainConfig, Feature, ForkSchedule, GovernableParams};
 mod ops;
+pub use ops::verify_balance_proof;
+pub mod light;
+pub mod replay;
+pub mod snapshot;
 
 use crate::core::{
-    hash::Hash, Address, Amount, Block, ContractAccount, ContractDeposit, ContractId,
-    ContractUpdate, ContractWithdraw, Delegate, Hasher, Header, Money, MpnAddress, ProofOfStake,
-    Ratio, RegularSendEntry, Signature, Staker, Token, TokenId, TokenUpdate, Transaction,
-    TransactionAndDelta, TransactionData, Undelegation, UndelegationId, ValidatorProof, Vrf,
-    ZkHasher as CoreZkHasher,
+    hash::Hash, Address, Amount, Block, ChainId, ConfigParam, ContractAccount, ContractDeposit,
+    ContractId, ContractUpdate, ContractWithdraw, Delegate, ExchangeRate, Hasher, Header, Htlc,
+    HtlcId, Liveness, Money, MpnAddress, Multisig, ProofOfStake, Proposal, ProposalId, Ratio,
+    RegularSendEntry, RewardPool, Signature, Staker, Token, TokenId, TokenUpdate, Transaction,
+    TransactionAndDelta, TransactionData, TxLocation, UnbondingStake, Undelegation, UndelegationId,
+    ValidatorProof, Vesting, VestingId, Vrf, ZkHasher as CoreZkHasher,
 };
+use crate::crypto::merkle::{MerkleTree, SparseMerkleProof};
 use crate::crypto::VerifiableRandomFunction;
 use crate::db::{keys, KvStore, RamMirrorKvStore, WriteOp};
 
@@ -81,6 +87,10 @@ pub trait Blockchain<K: KvStore> {
     fn epoch_slot(&self, timestamp: u32) -> (u32, u32);
     fn get_stake(&self, addr: Address) -> Result<Amount, BlockchainError>;
     fn get_stakers(&self) -> Result<Vec<(Address, Amount)>, BlockchainError>;
+    fn get_validator_set(&self) -> Result<Vec<(Address, Amount)>, BlockchainError>;
+    // Stakers of the validator-set, frozen along with it so that VRF keys changed in the
+    // middle of an epoch take effect in the next one
+    fn get_validator_stakers(&self) -> Result<Vec<(Address, Staker)>, BlockchainError>;
     fn get_auto_delegate_ratio(
         &self,
         delegator: Address,
@@ -112,12 +122,32 @@ pub trait Blockchain<K: KvStore> {
     fn currency_in_circulation(&self) -> Result<Amount, BlockchainError>;
 
     fn config(&self) -> &BlockchainConfig;
+    // Governable parameters of the config, with the changes accepted through governance
+    fn params(&self) -> Result<GovernableParams, BlockchainError>;
+    fn is_active(&self, feature: Feature, height: u64) -> bool;
+
+    fn chain_id(&self) -> ChainId;
+    // Chain-id that signatures should be bound to at the given height, legacy
+    // (unbound) signatures are still accepted before replay-protection activates
+    fn signing_chain_id(&self, height: u64) -> Option<ChainId>;
+    // Chain-ids that a signature may be checked against at the given height
+    fn accepted_chain_ids(&self, height: u64) -> Vec<Option<ChainId>>;
+    // Chain-id that MPN transactions should be bound to at the given height. There is no
+    // grace period, since the MPN contract only accepts proofs of a single update circuit
+    fn mpn_chain_id(&self, height: u64) -> Option<ChainId>;
 
     fn db_checksum(&self) -> Result<String, BlockchainError>;
 
     fn get_token(&self, token_id: TokenId) -> Result<Option<Token>, BlockchainError>;
 
     fn get_balance(&self, addr: Address, token_id: TokenId) -> Result<Amount, BlockchainError>;
+    fn is_frozen(&self, addr: Address, token_id: TokenId) -> Result<bool, BlockchainError>;
+    fn get_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+        token_id: TokenId,
+    ) -> Result<Amount, BlockchainError>;
     fn get_contract_balance(
         &self,
         contract_id: ContractId,
@@ -128,6 +158,20 @@ pub trait Blockchain<K: KvStore> {
         delegator: Address,
         delegatee: Address,
     ) -> Result<Delegate, BlockchainError>;
+    fn get_reward_pool(&self, staker: Address) -> Result<RewardPool, BlockchainError>;
+    fn get_token_reward_pools(
+        &self,
+        staker: Address,
+    ) -> Result<Vec<(TokenId, RewardPool)>, BlockchainError>;
+    fn get_liveness(&self, staker: Address) -> Result<Liveness, BlockchainError>;
+    fn get_multisig(&self, address: Address) -> Result<Option<Multisig>, BlockchainError>;
+    fn get_htlc(&self, htlc_id: HtlcId) -> Result<Option<Htlc>, BlockchainError>;
+    fn get_proposal(&self, proposal_id: ProposalId) -> Result<Option<Proposal>, BlockchainError>;
+    fn get_pending_reward(
+        &self,
+        delegator: Address,
+        delegatee: Address,
+    ) -> Result<Amount, BlockchainError>;
     fn get_undelegation(
         &self,
         undelegator: Address,
@@ -138,6 +182,15 @@ pub trait Blockchain<K: KvStore> {
         undelegator: Address,
         top: Option<usize>,
     ) -> Result<Vec<(UndelegationId, Undelegation)>, BlockchainError>;
+    fn get_vesting(
+        &self,
+        recipient: Address,
+        vesting_id: VestingId,
+    ) -> Result<Option<Vesting>, BlockchainError>;
+    fn get_vestings(
+        &self,
+        recipient: Address,
+    ) -> Result<Vec<(VestingId, Vesting)>, BlockchainError>;
     fn get_staker(&self, addr: Address) -> Result<Option<Staker>, BlockchainError>;
     fn get_nonce(&self, addr: Address) -> Result<u32, BlockchainError>;
     fn get_mpn_account(&self, addr: MpnAddress) -> Result<zk::MpnAccount, BlockchainError>;
@@ -163,14 +216,22 @@ pub trait Blockchain<K: KvStore> {
         locator: zk::ZkDataLocator,
     ) -> Result<zk::ZkScalar, BlockchainError>;
     fn next_reward(&self) -> Result<Amount, BlockchainError>;
+    // Per byte fee that transactions should burn in the next block
+    fn get_base_fee(&self) -> Result<Amount, BlockchainError>;
+    // Total amount of Ziesha burned through base fees
+    fn get_burned(&self) -> Result<Amount, BlockchainError>;
     fn will_extend(&self, from: u64, headers: &[Header]) -> Result<bool, BlockchainError>;
     fn extend(&mut self, from: u64, blocks: &[Block]) -> Result<(), BlockchainError>;
     fn rollback(&mut self) -> Result<(), BlockchainError>;
+    // Blocks before the pruned height (Except the genesis block) only have their headers
+    fn get_pruned_height(&self) -> Result<u64, BlockchainError>;
+    fn prune(&mut self, retention: u64) -> Result<(), BlockchainError>;
     fn draft_block(
         &self,
         timestamp: u32,
         mempool: &[TransactionAndDelta],
         wallet: &TxBuilder,
+        fee_tokens: &HashMap<TokenId, ExchangeRate>,
         check: bool,
     ) -> Result<Option<BlockAndPatch>, BlockchainError>;
     fn get_height(&self) -> Result<u64, BlockchainError>;
@@ -179,6 +240,31 @@ pub trait Blockchain<K: KvStore> {
     fn get_blocks(&self, since: u64, count: u64) -> Result<Vec<Block>, BlockchainError>;
     fn get_header(&self, index: u64) -> Result<Header, BlockchainError>;
     fn get_block(&self, index: u64) -> Result<Block, BlockchainError>;
+    fn get_merkle_tree(&self, index: u64) -> Result<MerkleTree<Hasher>, BlockchainError>;
+    // Root of the account state after the last block, and proofs against it
+    fn get_state_root(&self) -> Result<<Hasher as Hash>::Output, BlockchainError>;
+    fn prove_balance(
+        &self,
+        address: Address,
+        token_id: TokenId,
+    ) -> Result<SparseMerkleProof<Hasher>, BlockchainError>;
+    fn prove_validators(&self) -> Result<light::ValidatorsProof, BlockchainError>;
+    // Validators of an epoch, proven against the state root of its first header
+    fn get_epoch_validators_proof(
+        &self,
+        epoch: u32,
+    ) -> Result<Option<(Header, light::ValidatorsProof)>, BlockchainError>;
+    fn get_tx_location(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TxLocation>, BlockchainError>;
+    // Locations of the transactions touching an address, newest ones first
+    fn get_history(
+        &self,
+        address: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Vec<TxLocation>, BlockchainError>;
 
     fn get_contract(&self, contract_id: ContractId) -> Result<zk::ZkContract, BlockchainError>;
 
@@ -194,6 +280,41 @@ pub trait Blockchain<K: KvStore> {
     fn check_tx(&self, tx: &Transaction) -> Result<(), BlockchainError>;
 }
 
+// Checks the VRF proof of a validator for a slot, given the validator set and the
+// randomness of the epoch. Validators are elected with a chance proportional to their stake.
+fn is_elected(
+    validator_set: &[(Address, Amount)],
+    randomness: &<Hasher as Hash>::Output,
+    slot: u32,
+    addr: &Address,
+    staker: &Staker,
+    proof: &ValidatorProof,
+) -> bool {
+    let sum_stakes = validator_set
+        .iter()
+        .map(|(_, a)| u64::from(*a))
+        .sum::<u64>();
+    let chance = match validator_set.iter().find(|(k, _)| k == addr) {
+        Some((_, v)) => (u64::from(*v) as f64 / sum_stakes as f64) as f32,
+        None => return false,
+    };
+    if let ValidatorProof::Proof {
+        vrf_output,
+        vrf_proof,
+    } = proof
+    {
+        if Into::<f32>::into(vrf_output.clone()) <= chance {
+            return Vrf::verify(
+                &staker.vrf_pub_key,
+                format!("{}-{}", hex::encode(randomness), slot).as_bytes(),
+                vrf_output,
+                vrf_proof,
+            );
+        }
+    }
+    false
+}
+
 pub struct KvStoreChain<K: KvStore> {
     config: BlockchainConfig,
     database: K,
@@ -293,13 +414,65 @@ impl<K: KvStore> KvStoreChain<K> {
         ops::pay_validator_and_delegators(self, validator, fee_sum)
     }
 
+    fn pay_token_fees(
+        &mut self,
+        validator: Address,
+        fees: &HashMap<TokenId, Amount>,
+    ) -> Result<(), BlockchainError> {
+        ops::pay_token_fees(self, validator, fees)
+    }
+
+    fn tx_base_fee(&self, tx: &Transaction) -> Result<Amount, BlockchainError> {
+        ops::tx_base_fee(self, tx)
+    }
+
+    fn update_base_fee(&mut self, block_size: usize) -> Result<(), BlockchainError> {
+        ops::update_base_fee(self, block_size)
+    }
+
+    fn update_state_root(&mut self, changes: &[WriteOp]) -> Result<(), BlockchainError> {
+        ops::update_state_root(self, changes)
+    }
+
+    fn release_vestings(&mut self) -> Result<(), BlockchainError> {
+        ops::release_vestings(self)
+    }
+
+    fn bind_mpn_to_chain_id(&mut self) -> Result<(), BlockchainError> {
+        ops::bind_mpn_to_chain_id(self)
+    }
+
+    fn update_liveness(&mut self, epoch: u32) -> Result<(), BlockchainError> {
+        ops::update_liveness(self, epoch)
+    }
+
+    fn update_validator_set(&mut self) -> Result<(), BlockchainError> {
+        ops::update_validator_set(self)
+    }
+
+    fn tally_proposals(&mut self) -> Result<(), BlockchainError> {
+        ops::tally_proposals(self)
+    }
+
+    fn activate_config_overrides(&mut self, height: u64) -> Result<(), BlockchainError> {
+        ops::activate_config_overrides(self, height)
+    }
+
+    fn get_active_config_changes(&self) -> Result<Vec<ConfigParam>, BlockchainError> {
+        Ok(match self.database.get(keys::active_config())? {
+            Some(b) => b.try_into()?,
+            None => Vec::new(),
+        })
+    }
+
     fn select_transactions(
         &self,
         validator: Address,
         txs: &[TransactionAndDelta],
+        fee_tokens: &HashMap<TokenId, ExchangeRate>,
         check: bool,
     ) -> Result<Vec<TransactionAndDelta>, BlockchainError> {
-        ops::select_transactions(self, validator, txs, check)
+        ops::select_transactions(self, validator, txs, fee_tokens, check)
     }
 
     fn apply_block(&mut self, block: &Block) -> Result<(), BlockchainError> {
@@ -332,15 +505,108 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         Ok(match self.database.get(keys::block(index))? {
             Some(b) => b.try_into()?,
             None => {
-                return Err(BlockchainError::Inconsistency);
+                return Err(if index < self.get_pruned_height()? {
+                    BlockchainError::BlockPruned
+                } else {
+                    BlockchainError::Inconsistency
+                });
             }
         })
     }
 
+    fn get_state_root(&self) -> Result<<Hasher as Hash>::Output, BlockchainError> {
+        ops::get_state_root(self)
+    }
+
+    fn prove_balance(
+        &self,
+        address: Address,
+        token_id: TokenId,
+    ) -> Result<SparseMerkleProof<Hasher>, BlockchainError> {
+        ops::prove_state(self, &keys::account_balance(&address, token_id))
+    }
+
+    fn prove_validators(&self) -> Result<light::ValidatorsProof, BlockchainError> {
+        light::prove_validators(self)
+    }
+
+    fn get_epoch_validators_proof(
+        &self,
+        epoch: u32,
+    ) -> Result<Option<(Header, light::ValidatorsProof)>, BlockchainError> {
+        Ok(match self.database.get(keys::validators_proof(epoch))? {
+            Some(b) => {
+                let (number, proof): (u64, light::ValidatorsProof) = b.try_into()?;
+                Some((self.get_header(number)?, proof))
+            }
+            None => None,
+        })
+    }
+
+    fn get_merkle_tree(&self, index: u64) -> Result<MerkleTree<Hasher>, BlockchainError> {
+        if index >= self.get_height()? {
+            return Err(BlockchainError::BlockNotFound);
+        }
+        Ok(match self.database.get(keys::merkle(index))? {
+            Some(b) => b.try_into()?,
+            None => {
+                return Err(if index < self.get_pruned_height()? {
+                    BlockchainError::BlockPruned
+                } else {
+                    BlockchainError::Inconsistency
+                });
+            }
+        })
+    }
+
+    fn get_tx_location(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TxLocation>, BlockchainError> {
+        Ok(match self.database.get(keys::tx_location(&tx_hash))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
+
+    fn get_history(
+        &self,
+        address: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Vec<TxLocation>, BlockchainError> {
+        let mut history = Vec::new();
+        for (k, _) in self
+            .database
+            .pairs(keys::AddressHistoryDbKey::prefix(&address).into())?
+            .into_iter()
+            .skip(page.saturating_mul(page_size))
+            .take(page_size)
+        {
+            let key = keys::AddressHistoryDbKey::try_from(k)?;
+            history.push(TxLocation {
+                block: key.block,
+                index: key.index,
+            });
+        }
+        Ok(history)
+    }
+
     fn rollback(&mut self) -> Result<(), BlockchainError> {
         ops::rollback(self)
     }
 
+    fn get_pruned_height(&self) -> Result<u64, BlockchainError> {
+        Ok(match self.database.get(keys::pruned_height())? {
+            Some(b) => b.try_into()?,
+            None => 0,
+        })
+    }
+
+    fn prune(&mut self, retention: u64) -> Result<(), BlockchainError> {
+        ops::prune(self, retention)
+    }
+
     fn get_outdated_heights(&self) -> Result<HashMap<ContractId, u64>, BlockchainError> {
         let outdated = self.get_outdated_contracts()?;
         let mut ret = HashMap::new();
@@ -421,6 +687,27 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         )
     }
 
+    fn is_frozen(&self, addr: Address, token_id: TokenId) -> Result<bool, BlockchainError> {
+        Ok(self.database.get(keys::frozen(&addr, token_id))?.is_some())
+    }
+
+    fn get_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+        token_id: TokenId,
+    ) -> Result<Amount, BlockchainError> {
+        Ok(
+            match self
+                .database
+                .get(keys::allowance(&owner, &spender, token_id))?
+            {
+                Some(b) => b.try_into()?,
+                None => 0.into(),
+            },
+        )
+    }
+
     fn get_nonce(&self, addr: Address) -> Result<u32, BlockchainError> {
         Ok(match self.database.get(keys::nonce(&addr))? {
             Some(b) => b.try_into()?,
@@ -459,13 +746,83 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         Ok(
             match self.database.get(keys::delegate(&delegator, &delegatee))? {
                 Some(b) => b.try_into()?,
-                None => Delegate { amount: Amount(0) },
+                None => Delegate {
+                    amount: Amount(0),
+                    reward_per_stake: 0,
+                },
+            },
+        )
+    }
+
+    fn get_reward_pool(&self, staker: Address) -> Result<RewardPool, BlockchainError> {
+        Ok(match self.database.get(keys::reward_pool(&staker))? {
+            Some(b) => b.try_into()?,
+            None => Default::default(),
+        })
+    }
+
+    fn get_token_reward_pools(
+        &self,
+        staker: Address,
+    ) -> Result<Vec<(TokenId, RewardPool)>, BlockchainError> {
+        let mut pools = Vec::new();
+        for (k, v) in self
+            .database
+            .pairs(keys::TokenRewardPoolDbKey::prefix(&staker).into())?
+            .into_iter()
+        {
+            let token_id = keys::TokenRewardPoolDbKey::try_from(k)?.token_id;
+            let pool: RewardPool = v.try_into()?;
+            pools.push((token_id, pool));
+        }
+        Ok(pools)
+    }
+
+    fn get_proposal(&self, proposal_id: ProposalId) -> Result<Option<Proposal>, BlockchainError> {
+        Ok(
+            match self
+                .database
+                .get(keys::ProposalDbKey { proposal_id }.into())?
+            {
+                Some(b) => Some(b.try_into()?),
+                None => None,
             },
         )
     }
 
+    fn get_liveness(&self, staker: Address) -> Result<Liveness, BlockchainError> {
+        Ok(match self.database.get(keys::liveness(&staker))? {
+            Some(b) => b.try_into()?,
+            None => Default::default(),
+        })
+    }
+
+    fn get_multisig(&self, address: Address) -> Result<Option<Multisig>, BlockchainError> {
+        Ok(match self.database.get(keys::multisig(&address))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
+
+    fn get_htlc(&self, htlc_id: HtlcId) -> Result<Option<Htlc>, BlockchainError> {
+        Ok(match self.database.get(keys::htlc(&htlc_id))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
+
+    fn get_pending_reward(
+        &self,
+        delegator: Address,
+        delegatee: Address,
+    ) -> Result<Amount, BlockchainError> {
+        let delegate = self.get_delegate(delegator, delegatee.clone())?;
+        let pool = self.get_reward_pool(delegatee)?;
+        Ok(delegate.pending_reward(&pool))
+    }
+
     fn get_mpn_account(&self, addr: MpnAddress) -> Result<zk::MpnAccount, BlockchainError> {
-        let index = addr.account_index(self.config().mpn_config.log4_tree_size);
+        let index = addr.account_index(self.config.mpn_config.log4_tree_size);
         let acc = zk::KvStoreStateManager::<CoreZkHasher>::get_mpn_account(
             &self.database,
             self.config.mpn_config.mpn_contract_id,
@@ -569,16 +926,29 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
     }
     fn next_reward(&self) -> Result<Amount, BlockchainError> {
         let supply = self.get_balance(Default::default(), TokenId::Ziesha)?;
-        Ok(supply / self.config.reward_ratio)
+        Ok(supply / self.params()?.reward_ratio)
+    }
+    fn get_base_fee(&self) -> Result<Amount, BlockchainError> {
+        Ok(match self.database.get(keys::base_fee())? {
+            Some(b) => b.try_into()?,
+            None => self.config.min_base_fee,
+        })
+    }
+    fn get_burned(&self) -> Result<Amount, BlockchainError> {
+        Ok(match self.database.get(keys::burned())? {
+            Some(b) => b.try_into()?,
+            None => Amount(0),
+        })
     }
     fn draft_block(
         &self,
         timestamp: u32,
         mempool: &[TransactionAndDelta],
         wallet: &TxBuilder,
+        fee_tokens: &HashMap<TokenId, ExchangeRate>,
         check: bool,
     ) -> Result<Option<BlockAndPatch>, BlockchainError> {
-        ops::draft_block(self, timestamp, mempool, wallet, check)
+        ops::draft_block(self, timestamp, mempool, wallet, fee_tokens, check)
     }
 
     fn update_states(&mut self, patch: &ZkBlockchainPatch) -> Result<(), BlockchainError> {
@@ -605,10 +975,43 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         ops::generate_state_patch(self, heights, to)
     }
 
+    fn is_active(&self, feature: Feature, height: u64) -> bool {
+        self.config.fork_schedule.is_active(feature, height)
+    }
+
+    fn chain_id(&self) -> ChainId {
+        self.config.chain_id()
+    }
+
+    fn signing_chain_id(&self, height: u64) -> Option<ChainId> {
+        self.is_active(Feature::ReplayProtection, height)
+            .then(|| self.chain_id())
+    }
+
+    fn accepted_chain_ids(&self, height: u64) -> Vec<Option<ChainId>> {
+        match self.signing_chain_id(height) {
+            Some(chain_id) => vec![Some(chain_id)],
+            None => vec![Some(self.chain_id()), None],
+        }
+    }
+
+    fn mpn_chain_id(&self, height: u64) -> Option<ChainId> {
+        self.is_active(Feature::MpnReplayProtection, height)
+            .then(|| self.chain_id())
+    }
+
     fn config(&self) -> &BlockchainConfig {
         &self.config
     }
 
+    fn params(&self) -> Result<GovernableParams, BlockchainError> {
+        let mut params = self.config.governable_params();
+        for change in self.get_active_config_changes()?.iter() {
+            params.apply(change);
+        }
+        Ok(params)
+    }
+
     fn currency_in_circulation(&self) -> Result<Amount, BlockchainError> {
         let mut amount_sum = Amount(0);
         for (k, v) in self.database.pairs("ACB-".into())?.into_iter() {
@@ -631,6 +1034,22 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
             let bal: Undelegation = v.try_into().unwrap();
             amount_sum += bal.amount;
         }
+        for (_, v) in self.database.pairs("VST-".into())?.into_iter() {
+            let vesting: Vesting = v.try_into().unwrap();
+            if vesting.amount.token_id == TokenId::Ziesha {
+                amount_sum += vesting.locked();
+            }
+        }
+        for (_, v) in self.database.pairs("HTL-".into())?.into_iter() {
+            let htlc: Htlc = v.try_into().unwrap();
+            if htlc.amount.token_id == TokenId::Ziesha {
+                amount_sum += htlc.amount.amount;
+            }
+        }
+        for (_, v) in self.database.pairs("RWP-".into())?.into_iter() {
+            let pool: RewardPool = v.try_into().unwrap();
+            amount_sum += pool.balance;
+        }
         Ok(amount_sum)
     }
 
@@ -649,32 +1068,19 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
     ) -> Result<bool, BlockchainError> {
         let (_, slot) = self.epoch_slot(timestamp);
         let randomness = self.epoch_randomness()?;
-        let stakers = self.get_stakers()?;
-        let sum_stakes = stakers.iter().map(|(_, a)| u64::from(*a)).sum::<u64>();
-        let stakers: HashMap<Address, f32> = stakers
-            .into_iter()
-            .map(|(k, v)| (k, (u64::from(v) as f64 / sum_stakes as f64) as f32))
-            .collect();
-
-        if let Some(chance) = stakers.get(&addr) {
-            if let Some(staker_info) = self.get_staker(addr.clone())? {
-                if let ValidatorProof::Proof {
-                    vrf_output,
-                    vrf_proof,
-                } = proof
-                {
-                    if Into::<f32>::into(vrf_output.clone()) <= *chance {
-                        return Ok(Vrf::verify(
-                            &staker_info.vrf_pub_key,
-                            format!("{}-{}", hex::encode(randomness), slot).as_bytes(),
-                            &vrf_output,
-                            &vrf_proof,
-                        ));
-                    }
-                }
-            }
-        }
-        Ok(false)
+        let validator_set = self.get_validator_set()?;
+        let stakers = self.get_validator_stakers()?;
+        Ok(match stakers.iter().find(|(a, _)| *a == addr) {
+            Some((_, staker_info)) => is_elected(
+                &validator_set,
+                &randomness,
+                slot,
+                &addr,
+                staker_info,
+                &proof,
+            ),
+            None => false,
+        })
     }
     fn validator_status(
         &self,
@@ -683,7 +1089,7 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
     ) -> Result<ValidatorProof, BlockchainError> {
         let (_, slot) = self.epoch_slot(timestamp);
         let randomness = self.epoch_randomness()?;
-        let stakers = self.get_stakers()?;
+        let stakers = self.get_validator_set()?;
         let sum_stakes = stakers.iter().map(|(_, a)| u64::from(*a)).sum::<u64>();
         let stakers: HashMap<Address, f32> = stakers
             .into_iter()
@@ -719,13 +1125,29 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
             .into_iter()
         {
             let staker_rank = keys::StakerRankDbKey::try_from(k)?;
-            if self.get_staker(staker_rank.address.clone())?.is_some() {
+            if self.get_staker(staker_rank.address.clone())?.is_some()
+                && !self.get_liveness(staker_rank.address.clone())?.jailed
+            {
                 stakers.push((staker_rank.address, staker_rank.amount));
             }
         }
         Ok(stakers)
     }
 
+    fn get_validator_set(&self) -> Result<Vec<(Address, Amount)>, BlockchainError> {
+        Ok(match self.database.get(keys::validator_set())? {
+            Some(b) => b.try_into()?,
+            None => Vec::new(),
+        })
+    }
+
+    fn get_validator_stakers(&self) -> Result<Vec<(Address, Staker)>, BlockchainError> {
+        Ok(match self.database.get(keys::validator_stakers())? {
+            Some(b) => b.try_into()?,
+            None => Vec::new(),
+        })
+    }
+
     fn get_delegators(
         &self,
         delegatee: Address,
@@ -771,11 +1193,7 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
     }
 
     fn epoch_slot(&self, timestamp: u32) -> (u32, u32) {
-        // TODO: Error instead of saturating_sub!
-        let slot_number =
-            timestamp.saturating_sub(self.config.chain_start_timestamp) / self.config.slot_duration;
-        let epoch_number = slot_number / self.config.slot_per_epoch;
-        (epoch_number, slot_number % self.config.slot_per_epoch)
+        self.config.epoch_slot(timestamp)
     }
 
     fn database(&self) -> &K {
@@ -847,6 +1265,40 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         }
         Ok(undelegations)
     }
+    fn get_vesting(
+        &self,
+        recipient: Address,
+        vesting_id: VestingId,
+    ) -> Result<Option<Vesting>, BlockchainError> {
+        Ok(
+            match self.database.get(
+                keys::VestingDbKey {
+                    recipient,
+                    vesting_id,
+                }
+                .into(),
+            )? {
+                Some(b) => Some(b.try_into()?),
+                None => None,
+            },
+        )
+    }
+    fn get_vestings(
+        &self,
+        recipient: Address,
+    ) -> Result<Vec<(VestingId, Vesting)>, BlockchainError> {
+        let mut vestings = Vec::new();
+        for (k, v) in self
+            .database
+            .pairs(keys::VestingDbKey::prefix(&recipient).into())?
+            .into_iter()
+        {
+            let vesting_id = keys::VestingDbKey::try_from(k)?.vesting_id;
+            let vesting: Vesting = v.try_into()?;
+            vestings.push((vesting_id, vesting));
+        }
+        Ok(vestings)
+    }
 }
 
 #[cfg(test)]
@@ -4,7 +4,18 @@ pub fn apply_block<K: KvStore>(
     chain: &mut KvStoreChain<K>,
     block: &Block,
 ) -> Result<(), BlockchainError> {
-    let (ops, _) = chain.isolated(|chain| {
+    apply_block_and_get_state_root(chain, block, true)?;
+    Ok(())
+}
+
+// Applies the block and returns the root of the account state after it. Blocks being
+// drafted do not know their state root yet, so checking it is optional.
+pub fn apply_block_and_get_state_root<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    block: &Block,
+    check_state_root: bool,
+) -> Result<<Hasher as Hash>::Output, BlockchainError> {
+    let (ops, state_root) = chain.isolated(|chain| {
         let curr_height = chain.get_height()?;
 
         if let Some(height_limit) = chain.config.testnet_height_limit {
@@ -24,6 +35,9 @@ pub fn apply_block<K: KvStore>(
         }
 
         if !is_genesis {
+            if !block.header.verify_signature() {
+                return Err(BlockchainError::InvalidHeaderSignature);
+            }
             if chain.config.check_validator
                 && !chain.is_validator(
                     block.header.proof_of_stake.timestamp,
@@ -33,26 +47,66 @@ pub fn apply_block<K: KvStore>(
             {
                 return Err(BlockchainError::UnelectedValidator);
             }
-            // WARN: Sum will be invalid if fees are not in Ziesha
-            let fee_sum = Amount(
-                block
-                    .body
-                    .iter()
-                    .map(|t| -> u64 { t.fee.amount.into() })
-                    .sum(),
-            );
+            let tip_epoch = chain
+                .epoch_slot(chain.get_tip()?.proof_of_stake.timestamp)
+                .0;
+            if chain.epoch_slot(block.header.proof_of_stake.timestamp).0 > tip_epoch {
+                if chain.is_active(Feature::Jailing, block.header.number) {
+                    chain.update_liveness(tip_epoch)?;
+                }
+                chain.update_validator_set()?;
+                if chain.is_active(Feature::Governance, block.header.number) {
+                    chain.tally_proposals()?;
+                }
+            }
+            // Validators only receive the part of the fees exceeding the base fee, the
+            // rest is burned
+            let mut fee_sum = Amount(0);
+            let mut burned = Amount(0);
+            for tx in block.body.iter() {
+                if tx.fee.token_id == TokenId::Ziesha {
+                    let base_fee = std::cmp::min(chain.tx_base_fee(tx)?, tx.fee.amount);
+                    fee_sum += tx.fee.amount - base_fee;
+                    burned += base_fee;
+                }
+            }
+            if burned > Amount(0) {
+                let total_burned = chain.get_burned()? + burned;
+                chain
+                    .database
+                    .update(&[WriteOp::Put(keys::burned(), total_burned.into())])?;
+            }
             chain.pay_validator_and_delegators(
                 block.header.proof_of_stake.validator.clone(),
                 fee_sum,
             )?;
+            chain.release_vestings()?;
+        }
+
+        // MPN updates of the activation block are already proven by the chain-bound circuit.
+        // The genesis block creates the MPN contract itself, so it's bound after its txs.
+        let binds_mpn = chain
+            .config
+            .fork_schedule
+            .activation_height(Feature::MpnReplayProtection)
+            == Some(block.header.number);
+        if binds_mpn && !is_genesis {
+            chain.bind_mpn_to_chain_id()?;
         }
 
         let mut body_size = 0usize;
+        let mut base_fee_size = 0usize;
         let mut state_size_delta = 0isize;
         let mut state_updates: HashMap<ContractId, ZkCompressedStateChange> = HashMap::new();
         let mut outdated_contracts = chain.get_outdated_contracts()?;
 
-        if !is_genesis && !block.body.par_iter().all(|tx| tx.verify_signature()) {
+        let chain_ids = chain.accepted_chain_ids(block.header.number);
+        if !is_genesis
+            && !block
+                .body
+                .par_iter()
+                .all(|tx| chain_ids.iter().any(|id| tx.verify_signature(id.as_ref())))
+        {
             return Err(BlockchainError::SignatureError);
         }
 
@@ -60,7 +114,7 @@ pub fn apply_block<K: KvStore>(
         let mut num_mpn_contract_deposits = 0;
         let mut num_mpn_contract_withdraws = 0;
 
-        for tx in block.body.iter() {
+        for (index, tx) in block.body.iter().enumerate() {
             // Count MPN updates
             if let TransactionData::UpdateContract {
                 contract_id,
@@ -85,6 +139,33 @@ pub fn apply_block<K: KvStore>(
             }
 
             body_size += tx.size();
+            // Txs paying their fees in other tokens burn no base fee, so they don't move it
+            if tx.fee.token_id == TokenId::Ziesha {
+                base_fee_size += tx.size();
+            }
+            let location = TxLocation {
+                block: block.header.number,
+                index: index as u32,
+            };
+            let mut location_ops =
+                vec![WriteOp::Put(keys::tx_location(&tx.hash()), location.into())];
+            let htlc = match &tx.data {
+                TransactionData::ClaimHtlc { htlc_id, .. }
+                | TransactionData::RefundHtlc { htlc_id } => chain.get_htlc(*htlc_id)?,
+                _ => None,
+            };
+            for address in tx.involved_addresses(htlc.as_ref()) {
+                location_ops.push(WriteOp::Put(
+                    keys::AddressHistoryDbKey {
+                        address,
+                        block: location.block,
+                        index: location.index,
+                    }
+                    .into(),
+                    location.into(),
+                ));
+            }
+            chain.database.update(&location_ops)?;
             // All genesis block txs are allowed to get from Treasury
             if let TxSideEffect::StateChange {
                 contract_id,
@@ -104,6 +185,25 @@ pub fn apply_block<K: KvStore>(
             }
         }
 
+        if binds_mpn && is_genesis {
+            chain.bind_mpn_to_chain_id()?;
+        }
+
+        if is_genesis {
+            chain.update_validator_set()?;
+        } else {
+            let mut token_fees = HashMap::<TokenId, Amount>::new();
+            for tx in block.body.iter() {
+                if tx.fee.token_id != TokenId::Ziesha {
+                    *token_fees.entry(tx.fee.token_id).or_default() += tx.fee.amount;
+                }
+            }
+            chain.pay_token_fees(block.header.proof_of_stake.validator.clone(), &token_fees)?;
+            if chain.is_active(Feature::BaseFee, block.header.number) {
+                chain.update_base_fee(base_fee_size)?;
+            }
+        }
+
         if !is_genesis
             && (num_mpn_function_calls < chain.config.mpn_config.mpn_num_update_batches
                 || num_mpn_contract_deposits < chain.config.mpn_config.mpn_num_deposit_batches
@@ -112,11 +212,12 @@ pub fn apply_block<K: KvStore>(
             return Err(BlockchainError::InsufficientMpnUpdates);
         }
 
-        if body_size > chain.config.max_block_size {
+        let params = chain.params()?;
+        if body_size > params.max_block_size {
             return Err(BlockchainError::BlockTooBig);
         }
 
-        if state_size_delta > chain.config.max_delta_count as isize {
+        if state_size_delta > params.max_delta_count as isize {
             return Err(BlockchainError::StateDeltaTooBig);
         }
 
@@ -148,6 +249,34 @@ pub fn apply_block<K: KvStore>(
             }
         }
 
+        let changes = chain.database.to_ops();
+        chain.update_state_root(&changes)?;
+        let state_root = chain.get_state_root()?;
+        if check_state_root
+            && !is_genesis
+            && chain.is_active(Feature::StateRoot, block.header.number)
+            && state_root != block.header.state_root
+        {
+            return Err(BlockchainError::InvalidStateRoot);
+        }
+
+        // Light clients learn the validators of an epoch through the state after its first
+        // block, so its proof is kept
+        if !is_genesis {
+            let block_epoch = chain.epoch_slot(block.header.proof_of_stake.timestamp).0;
+            if block_epoch
+                > chain
+                    .epoch_slot(chain.get_tip()?.proof_of_stake.timestamp)
+                    .0
+            {
+                let proof = chain.prove_validators()?;
+                chain.database.update(&[WriteOp::Put(
+                    keys::validators_proof(block_epoch),
+                    (block.header.number, proof).into(),
+                )])?;
+            }
+        }
+
         chain.database.update(&[
             WriteOp::Put(keys::height(), (curr_height + 1).into()),
             WriteOp::Put(
@@ -161,6 +290,7 @@ pub fn apply_block<K: KvStore>(
             ),
             WriteOp::Put(keys::contract_updates(), state_updates.into()),
         ])?;
+        chain.activate_config_overrides(curr_height + 1)?;
 
         let rollback = chain.database.rollback()?;
 
@@ -173,9 +303,9 @@ pub fn apply_block<K: KvStore>(
             },
         ])?;
 
-        Ok(())
+        Ok(state_root)
     })?;
 
     chain.database.update(&ops)?;
-    Ok(())
+    Ok(state_root)
 }
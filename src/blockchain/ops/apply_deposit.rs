@@ -5,7 +5,11 @@ pub fn apply_deposit<K: KvStore>(
     deposit: &ContractDeposit,
 ) -> Result<(), BlockchainError> {
     let (ops, _) = chain.isolated(|chain| {
-        if !deposit.verify_signature() {
+        if !chain
+            .accepted_chain_ids(chain.get_height()?)
+            .iter()
+            .any(|id| deposit.verify_signature(id.as_ref()))
+        {
             return Err(BlockchainError::InvalidContractPaymentSignature);
         }
 
@@ -20,6 +24,10 @@ pub fn apply_deposit<K: KvStore>(
             deposit_nonce.into(),
         )])?;
 
+        if chain.is_frozen(deposit.src.clone(), deposit.amount.token_id)? {
+            return Err(BlockchainError::TokenAccountFrozen);
+        }
+
         if deposit.amount.token_id == deposit.fee.token_id {
             let mut addr_balance =
                 chain.get_balance(deposit.src.clone(), deposit.amount.token_id)?;
@@ -0,0 +1,18 @@
+use super::*;
+
+pub fn approve<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    spender: Address,
+    token_id: TokenId,
+    amount: Amount,
+) -> Result<(), BlockchainError> {
+    // Approving replaces the previous allowance, instead of adding to it
+    let key = keys::allowance(&tx_src, &spender, token_id);
+    chain.database.update(&[if amount == Amount(0) {
+        WriteOp::Remove(key)
+    } else {
+        WriteOp::Put(key, amount.into())
+    }])?;
+    Ok(())
+}
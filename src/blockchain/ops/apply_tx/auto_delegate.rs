@@ -7,6 +7,9 @@ pub fn auto_delegate<K: KvStore>(
     to: Address,
     ratio: Ratio,
 ) -> Result<(), BlockchainError> {
+    // Rewards earned so far are auto-delegated with the previous ratio
+    claim_reward::claim_reward(chain, tx_src.clone(), to.clone())?;
+
     chain.database.update(&[WriteOp::Put(
         keys::auto_delegate(&tx_src, &to),
         ratio.into(),
@@ -0,0 +1,27 @@
+use super::*;
+
+pub fn claim_htlc<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    htlc_id: HtlcId,
+    preimage: &[u8],
+) -> Result<(), BlockchainError> {
+    let htlc = chain
+        .get_htlc(htlc_id)?
+        .ok_or(BlockchainError::HtlcNotFound)?;
+    if chain.get_height()? >= htlc.timeout_height {
+        return Err(BlockchainError::HtlcTimedOut);
+    }
+    if !htlc.unlocks_with(preimage) {
+        return Err(BlockchainError::InvalidHtlcPreimage);
+    }
+
+    let dst_bal = chain.get_balance(htlc.recipient.clone(), htlc.amount.token_id)?;
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&htlc.recipient, htlc.amount.token_id),
+            (dst_bal + htlc.amount.amount).into(),
+        ),
+        WriteOp::Remove(keys::htlc(&htlc_id)),
+    ])?;
+    Ok(())
+}
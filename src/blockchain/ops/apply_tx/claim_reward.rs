@@ -0,0 +1,94 @@
+use super::*;
+use crate::core::Ratio;
+
+// Pays the pending rewards of a delegation out of the reward-pool of the delegatee.
+// Must be called before the amount of a delegation is changed, so that the rewards
+// earned with the old amount are settled first.
+pub fn claim_reward<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    delegator: Address,
+    delegatee: Address,
+) -> Result<(), BlockchainError> {
+    let mut delegate = chain.get_delegate(delegator.clone(), delegatee.clone())?;
+    claim_token_rewards(chain, &delegator, &delegatee, delegate.amount)?;
+
+    let mut pool = chain.get_reward_pool(delegatee.clone())?;
+    if delegate.amount == Amount(0) || delegate.reward_per_stake == pool.reward_per_stake {
+        return Ok(());
+    }
+
+    let reward = delegate.pending_reward(&pool);
+    if pool.balance < reward {
+        return Err(BlockchainError::Inconsistency);
+    }
+    pool.balance -= reward;
+    delegate.reward_per_stake = pool.reward_per_stake;
+    let new_balance = chain.get_balance(delegator.clone(), TokenId::Ziesha)? + reward;
+    chain.database.update(&[
+        WriteOp::Put(keys::delegate(&delegator, &delegatee), delegate.into()),
+        WriteOp::Put(keys::reward_pool(&delegatee), pool.into()),
+        WriteOp::Put(
+            keys::account_balance(&delegator, TokenId::Ziesha),
+            new_balance.into(),
+        ),
+    ])?;
+
+    let auto_delegate_ratio =
+        chain.get_auto_delegate_ratio(delegator.clone(), delegatee.clone())?;
+    if auto_delegate_ratio > Ratio(0) {
+        let auto_delegate_amount = auto_delegate_ratio.apply(reward);
+        if auto_delegate_amount > Amount(0) {
+            delegate::delegate(chain, delegator, auto_delegate_amount, delegatee)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Same as above, for the fees the delegatee has received in tokens other than Ziesha
+fn claim_token_rewards<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    delegator: &Address,
+    delegatee: &Address,
+    amount: Amount,
+) -> Result<(), BlockchainError> {
+    for (token_id, mut pool) in chain.get_token_reward_pools(delegatee.clone())? {
+        let checkpoint_key = keys::token_reward_checkpoint(delegator, delegatee, token_id);
+        let checkpoint: u128 = match chain.database.get(checkpoint_key.clone())? {
+            Some(b) => b.try_into()?,
+            None => 0,
+        };
+        if checkpoint == pool.reward_per_stake {
+            continue;
+        }
+
+        let reward = Delegate {
+            amount,
+            reward_per_stake: checkpoint,
+        }
+        .pending_reward(&pool);
+        if pool.balance < reward {
+            return Err(BlockchainError::Inconsistency);
+        }
+        pool.balance -= reward;
+        chain.database.update(&[
+            WriteOp::Put(checkpoint_key, pool.reward_per_stake.into()),
+            WriteOp::Put(
+                keys::TokenRewardPoolDbKey {
+                    staker: delegatee.clone(),
+                    token_id,
+                }
+                .into(),
+                pool.into(),
+            ),
+        ])?;
+        if reward > Amount(0) {
+            let new_balance = chain.get_balance(delegator.clone(), token_id)? + reward;
+            chain.database.update(&[WriteOp::Put(
+                keys::account_balance(delegator, token_id),
+                new_balance.into(),
+            )])?;
+        }
+    }
+    Ok(())
+}
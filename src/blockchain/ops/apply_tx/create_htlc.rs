@@ -0,0 +1,40 @@
+use super::*;
+
+pub fn create_htlc<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    htlc_id: HtlcId,
+    tx_src: Address,
+    recipient: Address,
+    amount: Money,
+    hashlock: <Hasher as Hash>::Output,
+    timeout_height: u64,
+) -> Result<(), BlockchainError> {
+    if timeout_height <= chain.get_height()? {
+        return Err(BlockchainError::HtlcTimedOut);
+    }
+
+    if chain.is_frozen(tx_src.clone(), amount.token_id)? {
+        return Err(BlockchainError::TokenAccountFrozen);
+    }
+    let mut src_bal = chain.get_balance(tx_src.clone(), amount.token_id)?;
+    if src_bal < amount.amount {
+        return Err(BlockchainError::BalanceInsufficient);
+    }
+    src_bal -= amount.amount;
+
+    let htlc = Htlc {
+        sender: tx_src.clone(),
+        recipient,
+        amount,
+        hashlock,
+        timeout_height,
+    };
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&tx_src, amount.token_id),
+            src_bal.into(),
+        ),
+        WriteOp::Put(keys::htlc(&htlc_id), htlc.into()),
+    ])?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use super::*;
+
+pub fn create_multisig<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    multisig: Multisig,
+) -> Result<(), BlockchainError> {
+    if !multisig.is_valid() {
+        return Err(BlockchainError::InvalidMultisig);
+    }
+    let address = multisig.address();
+    if chain.get_multisig(address.clone())?.is_some() {
+        return Err(BlockchainError::MultisigAlreadyExists);
+    }
+    chain
+        .database
+        .update(&[WriteOp::Put(keys::multisig(&address), multisig.into())])?;
+    Ok(())
+}
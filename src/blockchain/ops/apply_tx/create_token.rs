@@ -48,6 +48,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            metadata: None,
         };
         let (ops, _) = chain
             .isolated(|chain| Ok(create_token(chain, addr, token_id, &tkn)?))
@@ -90,6 +91,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            metadata: None,
         };
         assert!(matches!(
             chain.isolated(|chain| {
@@ -121,6 +123,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            metadata: None,
         };
         let bad_tokens = vec![
             Token {
@@ -129,6 +132,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -136,6 +140,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
             Token {
                 name: "کیوان".into(),
@@ -143,6 +148,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -150,6 +156,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -157,6 +164,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoinAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".into(),
@@ -164,6 +172,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -171,6 +180,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                metadata: None,
             },
         ];
         assert!(chain
@@ -6,6 +6,8 @@ pub fn delegate<K: KvStore>(
     amount: Amount,
     to: Address,
 ) -> Result<(), BlockchainError> {
+    claim_reward::claim_reward(chain, tx_src.clone(), to.clone())?;
+
     let mut src_bal = chain.get_balance(tx_src.clone(), TokenId::Ziesha)?;
     if src_bal < amount {
         return Err(BlockchainError::BalanceInsufficient);
@@ -19,6 +21,7 @@ pub fn delegate<K: KvStore>(
     let mut delegate = chain.get_delegate(tx_src.clone(), to.clone())?;
     let old_delegate = delegate.amount;
     delegate.amount += amount;
+    delegate.reward_per_stake = chain.get_reward_pool(to.clone())?.reward_per_stake;
     chain.database.update(&[WriteOp::Put(
         keys::delegate(&tx_src, &to),
         delegate.clone().into(),
@@ -123,7 +126,8 @@ mod tests {
                 "DEL-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-ed9e9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad641"
                     .into(),
                 Delegate {
-                    amount: Amount(123)
+                    amount: Amount(123),
+                    reward_per_stake: 0,
                 }.into()
             ),
             WriteOp::Put(
@@ -175,7 +179,8 @@ mod tests {
                 "DEL-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-ed9e9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad641"
                     .into(),
                 Delegate {
-                    amount: Amount(200)
+                    amount: Amount(200),
+                    reward_per_stake: 0,
                 }.into()
             ),
             WriteOp::Put(
@@ -239,7 +244,8 @@ mod tests {
                     "DEL-edae9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad640-ed9e9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad641"
                         .into(),
                     Delegate {
-                        amount: Amount(60)
+                        amount: Amount(60),
+                        reward_per_stake: 0,
                     }.into()
                 ),
                 WriteOp::Put(
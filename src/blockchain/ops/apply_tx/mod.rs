@@ -1,12 +1,25 @@
+mod approve;
 mod auto_delegate;
+mod claim_htlc;
+mod claim_reward;
 mod create_contract;
+mod create_htlc;
+mod create_multisig;
 mod create_token;
 mod delegate;
+mod propose;
+mod redelegate;
+mod refund_htlc;
 mod regular_send;
+mod report_equivocation;
+mod spend_from;
 mod undelegate;
+mod unjail;
 mod update_contract;
 mod update_staker;
 mod update_token;
+mod vested_send;
+mod vote;
 
 use super::*;
 
@@ -22,16 +35,41 @@ pub fn apply_tx<K: KvStore>(
             return Err(BlockchainError::IllegalTreasuryAccess);
         }
 
-        if tx.fee.token_id != TokenId::Ziesha {
-            return Err(BlockchainError::OnlyZieshaFeesAccepted);
+        if tx.fee.token_id != TokenId::Ziesha && chain.get_token(tx.fee.token_id)?.is_none() {
+            return Err(BlockchainError::TokenNotFound);
         }
 
-        if tx.memo.len() > chain.config.max_memo_length {
+        if tx.memo.len() > chain.params()?.max_memo_length {
             return Err(BlockchainError::MemoTooLong);
         }
 
+        if let Some(valid_until) = tx.valid_until {
+            if chain.get_height()? > valid_until {
+                return Err(BlockchainError::TransactionExpired);
+            }
+        }
+
+        if let Some(feature) = Feature::required_by(&tx.data) {
+            if !chain.is_active(feature, chain.get_height()?) {
+                return Err(BlockchainError::FeatureNotActive);
+            }
+        }
+
         let tx_src = tx.src.clone().unwrap_or_default(); // Default is treasury account!
 
+        if let Signature::Multi(sigs) = &tx.sig {
+            let multisig = chain
+                .get_multisig(tx_src.clone())?
+                .ok_or(BlockchainError::MultisigNotFound)?;
+            if !chain
+                .accepted_chain_ids(chain.get_height()?)
+                .iter()
+                .any(|id| multisig.verify(&tx.signing_bytes(id.as_ref()), sigs))
+            {
+                return Err(BlockchainError::SignatureError);
+            }
+        }
+
         let mut acc_nonce = chain.get_nonce(tx_src.clone())?;
         let mut acc_bal = chain.get_balance(tx_src.clone(), tx.fee.token_id)?;
 
@@ -43,6 +81,19 @@ pub fn apply_tx<K: KvStore>(
             return Err(BlockchainError::BalanceInsufficient);
         }
 
+        let base_fee = if internal {
+            Amount(0)
+        } else {
+            chain.tx_base_fee(tx)?
+        };
+        if tx.fee.amount < base_fee {
+            return Err(BlockchainError::FeeBelowBaseFee);
+        }
+
+        if tx.fee.amount > Amount(0) && chain.is_frozen(tx_src.clone(), tx.fee.token_id)? {
+            return Err(BlockchainError::TokenAccountFrozen);
+        }
+
         if !internal {
             acc_nonce += 1;
             chain
@@ -73,6 +124,43 @@ pub fn apply_tx<K: KvStore>(
                 let undelegation_id = UndelegationId::new(tx);
                 undelegate::undelegate(chain, undelegation_id, tx_src, *amount, from.clone())?;
             }
+            TransactionData::ClaimReward { from } => {
+                claim_reward::claim_reward(chain, tx_src, from.clone())?;
+            }
+            TransactionData::Unjail => {
+                unjail::unjail(chain, tx_src)?;
+            }
+            TransactionData::CreateMultisig { signers, threshold } => {
+                create_multisig::create_multisig(
+                    chain,
+                    Multisig {
+                        signers: signers.clone(),
+                        threshold: *threshold,
+                    },
+                )?;
+            }
+            TransactionData::Propose {
+                changes,
+                activation_height,
+            } => {
+                let proposal_id = ProposalId::new(tx);
+                propose::propose(
+                    chain,
+                    proposal_id,
+                    tx_src,
+                    changes.clone(),
+                    *activation_height,
+                )?;
+            }
+            TransactionData::Vote {
+                proposal_id,
+                approve,
+            } => {
+                vote::vote(chain, tx_src, *proposal_id, *approve)?;
+            }
+            TransactionData::Redelegate { amount, from, to } => {
+                redelegate::redelegate(chain, tx_src, *amount, from.clone(), to.clone())?;
+            }
             TransactionData::CreateToken { token } => {
                 let token_id = {
                     let tid = TokenId::new(tx);
@@ -87,9 +175,62 @@ pub fn apply_tx<K: KvStore>(
             TransactionData::UpdateToken { token_id, update } => {
                 update_token::update_token(chain, tx_src, token_id, update)?;
             }
+            TransactionData::VestedSend {
+                dst,
+                amount,
+                start,
+                end,
+            } => {
+                let vesting_id = VestingId::new(tx);
+                vested_send::vested_send(
+                    chain,
+                    vesting_id,
+                    tx_src,
+                    dst.clone(),
+                    *amount,
+                    *start,
+                    *end,
+                )?;
+            }
+            TransactionData::CreateHtlc {
+                recipient,
+                amount,
+                hashlock,
+                timeout_height,
+            } => {
+                let htlc_id = HtlcId::new(tx);
+                create_htlc::create_htlc(
+                    chain,
+                    htlc_id,
+                    tx_src,
+                    recipient.clone(),
+                    *amount,
+                    *hashlock,
+                    *timeout_height,
+                )?;
+            }
+            TransactionData::ClaimHtlc { htlc_id, preimage } => {
+                claim_htlc::claim_htlc(chain, *htlc_id, preimage)?;
+            }
+            TransactionData::RefundHtlc { htlc_id } => {
+                refund_htlc::refund_htlc(chain, *htlc_id)?;
+            }
+            TransactionData::Approve {
+                spender,
+                token_id,
+                amount,
+            } => {
+                approve::approve(chain, tx_src, spender.clone(), *token_id, *amount)?;
+            }
+            TransactionData::SpendFrom { owner, entries } => {
+                spend_from::spend_from(chain, tx_src, owner.clone(), entries)?;
+            }
             TransactionData::RegularSend { entries } => {
                 regular_send::regular_send(chain, tx_src, entries)?;
             }
+            TransactionData::ReportEquivocation { first, second } => {
+                report_equivocation::report_equivocation(chain, first, second)?;
+            }
             TransactionData::CreateContract { contract } => {
                 let contract_id = ContractId::new(tx);
                 side_effect = create_contract::create_contract(chain, contract_id, contract)?;
@@ -103,10 +244,10 @@ pub fn apply_tx<K: KvStore>(
             }
         }
 
-        // Fees go to the Treasury account first
+        // Fees go to the Treasury account first, except the base fee which is burned
         if tx.src != None {
             let mut treasury_balance = chain.get_balance(Default::default(), tx.fee.token_id)?;
-            treasury_balance += tx.fee.amount;
+            treasury_balance += tx.fee.amount - base_fee;
             chain.database.update(&[WriteOp::Put(
                 keys::account_balance(&Default::default(), tx.fee.token_id),
                 treasury_balance.into(),
@@ -0,0 +1,28 @@
+use super::*;
+
+pub fn propose<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    proposal_id: ProposalId,
+    tx_src: Address,
+    changes: Vec<ConfigParam>,
+    activation_height: u64,
+) -> Result<(), BlockchainError> {
+    let voting_ends = chain.get_height()? + chain.config.proposal_voting_period;
+    if changes.is_empty()
+        || changes.iter().any(|c| !c.is_valid())
+        || activation_height <= voting_ends
+    {
+        return Err(BlockchainError::InvalidProposal);
+    }
+    let proposal = Proposal {
+        proposer: tx_src,
+        changes,
+        activation_height,
+        voting_ends,
+    };
+    chain.database.update(&[WriteOp::Put(
+        keys::ProposalDbKey { proposal_id }.into(),
+        proposal.into(),
+    )])?;
+    Ok(())
+}
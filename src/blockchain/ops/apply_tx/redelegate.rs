@@ -0,0 +1,152 @@
+use super::*;
+
+pub fn redelegate<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    amount: Amount,
+    from: Address,
+    to: Address,
+) -> Result<(), BlockchainError> {
+    if from == to {
+        return Err(BlockchainError::SelfRedelegation);
+    }
+
+    claim_reward::claim_reward(chain, tx_src.clone(), from.clone())?;
+    claim_reward::claim_reward(chain, tx_src.clone(), to.clone())?;
+
+    let mut from_delegate = chain.get_delegate(tx_src.clone(), from.clone())?;
+    let old_from_delegate = from_delegate.amount;
+    if from_delegate.amount < amount {
+        return Err(BlockchainError::BalanceInsufficient);
+    }
+    from_delegate.amount -= amount;
+
+    let old_from_stake = chain.get_stake(from.clone())?;
+    if old_from_stake < amount {
+        return Err(BlockchainError::Inconsistency);
+    }
+    let new_from_stake = old_from_stake - amount;
+
+    let mut to_delegate = chain.get_delegate(tx_src.clone(), to.clone())?;
+    let old_to_delegate = to_delegate.amount;
+    to_delegate.amount += amount;
+    to_delegate.reward_per_stake = chain.get_reward_pool(to.clone())?.reward_per_stake;
+
+    let old_to_stake = chain.get_stake(to.clone())?;
+    let new_to_stake = old_to_stake + amount;
+
+    chain.database.update(&[
+        WriteOp::Put(keys::delegate(&tx_src, &from), from_delegate.clone().into()),
+        WriteOp::Put(keys::delegate(&tx_src, &to), to_delegate.clone().into()),
+        WriteOp::Remove(
+            keys::DelegateeRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: from.clone(),
+                amount: old_from_delegate,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::DelegateeRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: from.clone(),
+                amount: from_delegate.amount,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::DelegateeRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: to.clone(),
+                amount: old_to_delegate,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::DelegateeRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: to.clone(),
+                amount: to_delegate.amount,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::DelegatorRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: from.clone(),
+                amount: old_from_delegate,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::DelegatorRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: from.clone(),
+                amount: from_delegate.amount,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::DelegatorRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: to.clone(),
+                amount: old_to_delegate,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::DelegatorRankDbKey {
+                delegator: tx_src.clone(),
+                delegatee: to.clone(),
+                amount: to_delegate.amount,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::StakerRankDbKey {
+                address: from.clone(),
+                amount: old_from_stake,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::StakerRankDbKey {
+                address: from.clone(),
+                amount: new_from_stake,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::StakerRankDbKey {
+                address: to.clone(),
+                amount: old_to_stake,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::StakerRankDbKey {
+                address: to.clone(),
+                amount: new_to_stake,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Put(keys::stake(&from), new_from_stake.into()),
+        WriteOp::Put(keys::stake(&to), new_to_stake.into()),
+    ])?;
+    report_equivocation::record_unbonding_stake(
+        chain,
+        from,
+        UnbondingStake::Redelegation {
+            delegator: tx_src,
+            to,
+            amount,
+        },
+    )?;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+pub fn refund_htlc<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    htlc_id: HtlcId,
+) -> Result<(), BlockchainError> {
+    let htlc = chain
+        .get_htlc(htlc_id)?
+        .ok_or(BlockchainError::HtlcNotFound)?;
+    if chain.get_height()? < htlc.timeout_height {
+        return Err(BlockchainError::HtlcNotTimedOut);
+    }
+
+    let src_bal = chain.get_balance(htlc.sender.clone(), htlc.amount.token_id)?;
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&htlc.sender, htlc.amount.token_id),
+            (src_bal + htlc.amount.amount).into(),
+        ),
+        WriteOp::Remove(keys::htlc(&htlc_id)),
+    ])?;
+    Ok(())
+}
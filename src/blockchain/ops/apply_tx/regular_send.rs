@@ -7,6 +7,9 @@ pub fn regular_send<K: KvStore>(
 ) -> Result<(), BlockchainError> {
     for entry in entries {
         if entry.dst != tx_src {
+            if chain.is_frozen(tx_src.clone(), entry.amount.token_id)? {
+                return Err(BlockchainError::TokenAccountFrozen);
+            }
             let mut src_bal = chain.get_balance(tx_src.clone(), entry.amount.token_id)?;
 
             if src_bal < entry.amount.amount {
@@ -0,0 +1,228 @@
+use super::*;
+
+pub fn report_equivocation<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    first: &Header,
+    second: &Header,
+) -> Result<(), BlockchainError> {
+    let validator = first.proof_of_stake.validator.clone();
+    let (epoch, slot) = chain.epoch_slot(first.proof_of_stake.timestamp);
+    if second.proof_of_stake.validator != validator
+        || chain.epoch_slot(second.proof_of_stake.timestamp) != (epoch, slot)
+        || first.hash() == second.hash()
+        || !first.verify_signature()
+        || !second.verify_signature()
+    {
+        return Err(BlockchainError::InvalidEquivocationEvidence);
+    }
+
+    if chain.get_staker(validator.clone())?.is_none() {
+        return Err(BlockchainError::StakerNotFound);
+    }
+
+    if chain
+        .database
+        .get(keys::equivocation(&validator, epoch, slot))?
+        .is_some()
+    {
+        return Err(BlockchainError::EquivocationAlreadyReported);
+    }
+
+    // Slash the same portion of every delegation (Including validator's own stake)
+    let slash_ratio = chain.config.equivocation_slash_ratio;
+    let mut total_slashed = Amount(0);
+    for (delegator, _) in chain.get_delegators(validator.clone(), None)? {
+        // Rewards earned before the slash are not affected
+        claim_reward::claim_reward(chain, delegator.clone(), validator.clone())?;
+        let amount = chain
+            .get_delegate(delegator.clone(), validator.clone())?
+            .amount;
+        let slashed = slash_ratio.apply(amount);
+        slash_delegation(chain, delegator, validator.clone(), slashed)?;
+        total_slashed += slashed;
+    }
+
+    // Stake that has left the validator after the infraction is slashed too
+    let infraction_height = std::cmp::min(first.number, second.number);
+    for stake in get_unbonding_stakes(chain, validator.clone(), infraction_height)? {
+        match stake {
+            UnbondingStake::Undelegation {
+                undelegator,
+                undelegation_id,
+            } => {
+                if let Some(mut undelegation) =
+                    chain.get_undelegation(undelegator.clone(), undelegation_id)?
+                {
+                    let slashed = slash_ratio.apply(undelegation.amount);
+                    undelegation.amount -= slashed;
+                    chain.database.update(&[WriteOp::Put(
+                        keys::UndelegationDbKey {
+                            undelegator,
+                            undelegation_id,
+                        }
+                        .into(),
+                        undelegation.into(),
+                    )])?;
+                    total_slashed += slashed;
+                }
+            }
+            UnbondingStake::Redelegation {
+                delegator,
+                to,
+                amount,
+            } => {
+                claim_reward::claim_reward(chain, delegator.clone(), to.clone())?;
+                let delegated = chain.get_delegate(delegator.clone(), to.clone())?.amount;
+                let slashed = std::cmp::min(slash_ratio.apply(amount), delegated);
+                slash_delegation(chain, delegator, to, slashed)?;
+                total_slashed += slashed;
+            }
+        }
+    }
+
+    // Slashed funds go back to the Treasury, to be redistributed as future rewards
+    let treasury_balance = chain.get_balance(Default::default(), TokenId::Ziesha)? + total_slashed;
+
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&Default::default(), TokenId::Ziesha),
+            treasury_balance.into(),
+        ),
+        WriteOp::Put(keys::equivocation(&validator, epoch, slot), ().into()),
+    ])?;
+    Ok(())
+}
+
+// Takes the slashed amount out of a delegation and the stake of its delegatee
+fn slash_delegation<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    delegator: Address,
+    delegatee: Address,
+    slashed: Amount,
+) -> Result<(), BlockchainError> {
+    if slashed == Amount(0) {
+        return Ok(());
+    }
+    let mut delegate = chain.get_delegate(delegator.clone(), delegatee.clone())?;
+    let amount = delegate.amount;
+    let new_amount = amount - slashed;
+    delegate.amount = new_amount;
+
+    let old_stake = chain.get_stake(delegatee.clone())?;
+    if old_stake < slashed {
+        return Err(BlockchainError::Inconsistency);
+    }
+    let new_stake = old_stake - slashed;
+
+    chain.database.update(&[
+        WriteOp::Put(keys::delegate(&delegator, &delegatee), delegate.into()),
+        WriteOp::Remove(
+            keys::DelegateeRankDbKey {
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
+                amount,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::DelegateeRankDbKey {
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
+                amount: new_amount,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::DelegatorRankDbKey {
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
+                amount,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::DelegatorRankDbKey {
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
+                amount: new_amount,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Remove(
+            keys::StakerRankDbKey {
+                address: delegatee.clone(),
+                amount: old_stake,
+            }
+            .into(),
+        ),
+        WriteOp::Put(
+            keys::StakerRankDbKey {
+                address: delegatee.clone(),
+                amount: new_stake,
+            }
+            .into(),
+            ().into(),
+        ),
+        WriteOp::Put(keys::stake(&delegatee), new_stake.into()),
+    ])?;
+    Ok(())
+}
+
+// Records of the stake that has left a staker, by the heights they were recorded in
+fn get_unbonding_stake_records<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    staker: Address,
+) -> Result<Vec<(u64, Vec<UnbondingStake>)>, BlockchainError> {
+    let prefix = keys::unbonding_stake_prefix(&staker);
+    let mut records = Vec::new();
+    for (k, v) in chain.database.pairs(prefix.clone().into())?.into_iter() {
+        let block = k.0[prefix.len() + 1..]
+            .parse::<u64>()
+            .map_err(|_| BlockchainError::Inconsistency)?;
+        records.push((block, v.try_into()?));
+    }
+    Ok(records)
+}
+
+// Stake that has left a staker since the given height, and is not unbonded yet
+fn get_unbonding_stakes<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    staker: Address,
+    since: u64,
+) -> Result<Vec<UnbondingStake>, BlockchainError> {
+    let height = chain.get_height()?;
+    Ok(get_unbonding_stake_records(chain, staker)?
+        .into_iter()
+        .filter(|(block, _)| *block >= since && block + chain.config.unbonding_period > height)
+        .flat_map(|(_, stakes)| stakes)
+        .collect())
+}
+
+// Keeps track of the stake leaving a staker, so that it can still be slashed for the
+// infractions committed before it left. Records older than the unbonding period are
+// removed.
+pub fn record_unbonding_stake<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    staker: Address,
+    stake: UnbondingStake,
+) -> Result<(), BlockchainError> {
+    let height = chain.get_height()?;
+    let mut ops = Vec::new();
+    let mut current = Vec::new();
+    for (block, stakes) in get_unbonding_stake_records(chain, staker.clone())? {
+        if block == height {
+            current = stakes;
+        } else if block + chain.config.unbonding_period <= height {
+            ops.push(WriteOp::Remove(keys::unbonding_stake(&staker, block)));
+        }
+    }
+    current.push(stake);
+    ops.push(WriteOp::Put(
+        keys::unbonding_stake(&staker, height),
+        current.into(),
+    ));
+    chain.database.update(&ops)?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use super::*;
+
+pub fn spend_from<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    owner: Address,
+    entries: &[RegularSendEntry],
+) -> Result<(), BlockchainError> {
+    for entry in entries {
+        if entry.dst == owner {
+            continue; // Not moved by `regular_send` either
+        }
+        let token_id = entry.amount.token_id;
+        let mut allowance = chain.get_allowance(owner.clone(), tx_src.clone(), token_id)?;
+        if allowance < entry.amount.amount {
+            return Err(BlockchainError::AllowanceInsufficient);
+        }
+        allowance -= entry.amount.amount;
+        let key = keys::allowance(&owner, &tx_src, token_id);
+        chain.database.update(&[if allowance == Amount(0) {
+            WriteOp::Remove(key)
+        } else {
+            WriteOp::Put(key, allowance.into())
+        }])?;
+    }
+    regular_send::regular_send(chain, owner, entries)
+}
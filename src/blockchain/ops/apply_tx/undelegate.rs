@@ -7,7 +7,9 @@ pub fn undelegate<K: KvStore>(
     amount: Amount,
     from: Address,
 ) -> Result<(), BlockchainError> {
-    let unlocks_on = chain.get_height()? + 10; // TODO: Put undelegation period into config
+    claim_reward::claim_reward(chain, tx_src.clone(), from.clone())?;
+
+    let unlocks_on = chain.get_height()? + chain.config.unbonding_period;
     let undelegation = Undelegation { amount, unlocks_on };
     let mut delegate = chain.get_delegate(tx_src.clone(), from.clone())?;
     let old_delegate = delegate.amount;
@@ -94,5 +96,13 @@ pub fn undelegate<K: KvStore>(
         ),
         WriteOp::Put(keys::stake(&from), new_stake.into()),
     ])?;
+    report_equivocation::record_unbonding_stake(
+        chain,
+        from,
+        UnbondingStake::Undelegation {
+            undelegator: tx_src,
+            undelegation_id,
+        },
+    )?;
     Ok(())
 }
@@ -0,0 +1,17 @@
+use super::*;
+
+pub fn unjail<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+) -> Result<(), BlockchainError> {
+    let mut liveness = chain.get_liveness(tx_src.clone())?;
+    if !liveness.jailed {
+        return Err(BlockchainError::StakerNotJailed);
+    }
+    liveness.jailed = false;
+    liveness.missed = 0;
+    chain
+        .database
+        .update(&[WriteOp::Put(keys::liveness(&tx_src), liveness.into())])?;
+    Ok(())
+}
@@ -169,12 +169,12 @@ mod tests {
                 Amount(9800).into(),
             ),
             WriteOp::Put(
-                "CAB-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1-Ziesha"
+                "CAB-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa-Ziesha"
                     .into(),
                 Amount(123).into(),
             ),
             WriteOp::Put(
-                "DNC-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1".into(),
+                "DNC-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa".into(),
                 1u32.into(),
             ),
         ];
@@ -207,6 +207,7 @@ mod tests {
             Amount(100000),
             3,
             None,
+            None,
             Money::ziesha(0),
             1,
         );
@@ -269,7 +270,7 @@ mod tests {
 
         let expected_ops = vec![
             WriteOp::Put(
-                "ACB-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-0x5bfa932c150df0af24346419d31b61dab0747d78fa1b46e97f838927423c99f4"
+                "ACB-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-0x277c234282a3a34803df00ecfd50644661ecc59dcbc2cc6ed6eda5c3fbd6e675"
                     .into(),
                 Amount(90000).into(),
             ),
@@ -279,12 +280,12 @@ mod tests {
                 Amount(9679).into(),
             ),
             WriteOp::Put(
-                "CAB-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1-0x5bfa932c150df0af24346419d31b61dab0747d78fa1b46e97f838927423c99f4"
+                "CAB-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa-0x277c234282a3a34803df00ecfd50644661ecc59dcbc2cc6ed6eda5c3fbd6e675"
                     .into(),
                 Amount(10000).into(),
             ),
             WriteOp::Put(
-                "DNC-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1".into(),
+                "DNC-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa".into(),
                 1u32.into(),
             ),
         ];
@@ -28,13 +28,14 @@ pub fn withdraw<K: KvStore>(
         log4_size: withdraw_func.log4_payment_capacity,
     };
     let mut state_builder = zk::ZkStateBuilder::<CoreZkHasher>::new(state_model);
+    let chain_id = chain.signing_chain_id(chain.get_height()?);
     for (i, withdraw) in withdraws.iter().enumerate() {
         if withdraw.contract_id != *contract_id
             || withdraw.withdraw_circuit_id != *withdraw_circuit_id
         {
             return Err(BlockchainError::DepositWithdrawPassedToWrongFunction);
         }
-        let fingerprint = withdraw.fingerprint();
+        let fingerprint = withdraw.fingerprint(chain_id.as_ref());
         executor_fees.push(withdraw.fee);
         state_builder.batch_set(&zk::ZkDeltaPairs(
             [
@@ -182,7 +183,7 @@ mod tests {
                 zk::ZkScalar::from(200),
                 zk::ZkScalar::from(1),
                 zk::ZkScalar::from(50),
-                zk::ZkScalar::from(cont_withdraw.fingerprint()),
+                zk::ZkScalar::from(cont_withdraw.fingerprint(None)),
                 zk::ZkScalar::from(777),
             ]),
             empty_leaf,
@@ -204,7 +205,7 @@ mod tests {
                 Amount(9200).into(),
             ),
             WriteOp::Put(
-                "CAB-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1-Ziesha"
+                "CAB-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa-Ziesha"
                     .into(),
                 Amount(750).into(),
             ),
@@ -238,6 +239,7 @@ mod tests {
             Amount(100000),
             3,
             None,
+            None,
             Money::ziesha(0),
             1,
         );
@@ -314,7 +316,7 @@ mod tests {
                 zk::ZkScalar::from(333),
                 zk::ZkScalar::from(1),
                 zk::ZkScalar::from(444),
-                zk::ZkScalar::from(cont_withdraw.fingerprint()),
+                zk::ZkScalar::from(cont_withdraw.fingerprint(None)),
                 zk::ZkScalar::from(777),
             ]),
             empty_leaf,
@@ -331,17 +333,17 @@ mod tests {
 
         let expected_ops = vec![
             WriteOp::Put(
-                "ACB-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-0x5bfa932c150df0af24346419d31b61dab0747d78fa1b46e97f838927423c99f4"
+                "ACB-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-0x277c234282a3a34803df00ecfd50644661ecc59dcbc2cc6ed6eda5c3fbd6e675"
                     .into(),
                 Amount(99333).into(),
             ),
             WriteOp::Put(
-                "CAB-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1-0x5bfa932c150df0af24346419d31b61dab0747d78fa1b46e97f838927423c99f4"
+                "CAB-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa-0x277c234282a3a34803df00ecfd50644661ecc59dcbc2cc6ed6eda5c3fbd6e675"
                     .into(),
                 Amount(667).into(),
             ),
             WriteOp::Put(
-                "CAB-7e945da48a92aeb4da6c05adf8bb65eba45a6e860d5c269e9b5d30cd4ccdbcd1-Ziesha"
+                "CAB-99e52df2270f551a8f6c6be3b5e382f42c7f167ba87e5c13bbd1e8a45a6973fa-Ziesha"
                     .into(),
                 Amount(556).into(),
             )
@@ -8,7 +8,7 @@ pub fn update_staker<K: KvStore>(
     vrf_pub_key: <Vrf as VerifiableRandomFunction>::Pub,
     commission: Ratio,
 ) -> Result<(), BlockchainError> {
-    let commission = std::cmp::min(commission, chain.config.max_validator_commission);
+    let commission = std::cmp::min(commission, chain.params()?.max_validator_commission);
 
     chain.database.update(&[WriteOp::Put(
         keys::staker(&tx_src),
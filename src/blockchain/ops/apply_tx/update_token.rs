@@ -1,45 +1,88 @@
 use super::*;
 
+fn ensure_minter(token: &Token, tx_src: &Address) -> Result<(), BlockchainError> {
+    match &token.minter {
+        Some(minter) if minter == tx_src => Ok(()),
+        Some(_) => Err(BlockchainError::TokenUpdatePermissionDenied),
+        None => Err(BlockchainError::TokenNotUpdatable),
+    }
+}
+
 pub fn update_token<K: KvStore>(
     chain: &mut KvStoreChain<K>,
     tx_src: Address,
     token_id: &TokenId,
     update: &TokenUpdate,
 ) -> Result<(), BlockchainError> {
-    if let Some(mut token) = chain.get_token(*token_id)? {
-        if let Some(minter) = token.minter.clone() {
-            if minter == tx_src {
-                match update {
-                    TokenUpdate::Mint { amount } => {
-                        let mut bal = chain.get_balance(tx_src.clone(), *token_id)?;
-                        if bal + *amount < bal || token.supply + *amount < token.supply {
-                            return Err(BlockchainError::TokenSupplyOverflow);
-                        }
-                        bal += *amount;
-                        token.supply += *amount;
-                        chain
-                            .database
-                            .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
-                        chain.database.update(&[WriteOp::Put(
-                            keys::account_balance(&tx_src, *token_id),
-                            bal.into(),
-                        )])?;
-                    }
-                    TokenUpdate::ChangeMinter { minter } => {
-                        token.minter = Some(minter.clone());
-                        chain
-                            .database
-                            .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
-                    }
-                }
-            } else {
-                return Err(BlockchainError::TokenUpdatePermissionDenied);
+    let mut token = chain
+        .get_token(*token_id)?
+        .ok_or(BlockchainError::TokenNotFound)?;
+    match update {
+        TokenUpdate::Mint { amount } => {
+            ensure_minter(&token, &tx_src)?;
+            let mut bal = chain.get_balance(tx_src.clone(), *token_id)?;
+            if bal + *amount < bal || token.supply + *amount < token.supply {
+                return Err(BlockchainError::TokenSupplyOverflow);
+            }
+            bal += *amount;
+            token.supply += *amount;
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
+            chain.database.update(&[WriteOp::Put(
+                keys::account_balance(&tx_src, *token_id),
+                bal.into(),
+            )])?;
+        }
+        TokenUpdate::ChangeMinter { minter } => {
+            ensure_minter(&token, &tx_src)?;
+            token.minter = Some(minter.clone());
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
+        }
+        // Holders can burn their own balance, no matter who the minter is. Ziesha is only
+        // burned through the base fee.
+        TokenUpdate::Burn { amount } => {
+            if *token_id == TokenId::Ziesha {
+                return Err(BlockchainError::TokenNotUpdatable);
+            }
+            if chain.is_frozen(tx_src.clone(), *token_id)? {
+                return Err(BlockchainError::TokenAccountFrozen);
+            }
+            let mut bal = chain.get_balance(tx_src.clone(), *token_id)?;
+            if bal < *amount || token.supply < *amount {
+                return Err(BlockchainError::BalanceInsufficient);
             }
-        } else {
-            return Err(BlockchainError::TokenNotUpdatable);
+            bal -= *amount;
+            token.supply -= *amount;
+            chain.database.update(&[
+                WriteOp::Put(keys::token(token_id), (&token).into()),
+                WriteOp::Put(keys::account_balance(&tx_src, *token_id), bal.into()),
+            ])?;
+        }
+        TokenUpdate::Freeze { account } => {
+            ensure_minter(&token, &tx_src)?;
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::frozen(account, *token_id), ().into())])?;
+        }
+        TokenUpdate::Unfreeze { account } => {
+            ensure_minter(&token, &tx_src)?;
+            chain
+                .database
+                .update(&[WriteOp::Remove(keys::frozen(account, *token_id))])?;
+        }
+        TokenUpdate::SetMetadata { metadata } => {
+            ensure_minter(&token, &tx_src)?;
+            token.metadata = metadata.clone();
+            if !token.validate() {
+                return Err(BlockchainError::TokenBadMetadata);
+            }
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
         }
-    } else {
-        return Err(BlockchainError::TokenNotFound);
     }
     Ok(())
 }
@@ -103,6 +146,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr_1.clone(), token_id_1, &tkn).unwrap();
         let tkn2 = Token {
@@ -111,6 +155,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: Some(addr_2),
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr_1.clone(), token_id_2, &tkn2).unwrap();
         assert!(matches!(
@@ -157,6 +202,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: Some(addr.clone()),
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr.clone(), token_id, &tkn).unwrap();
 
@@ -187,6 +233,7 @@ mod tests {
                     supply: Amount(12445),
                     decimals: 2,
                     minter: Some(addr),
+                    metadata: None,
                 }).into()
             ),
         ];
@@ -218,6 +265,7 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: Some(addr.clone()),
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr.clone(), token_id, &tkn).unwrap();
 
@@ -242,6 +290,7 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: Some(new_addr),
+                metadata: None,
             })
                 .into(),
         )];
@@ -261,4 +310,148 @@ mod tests {
             Err(BlockchainError::TokenUpdatePermissionDenied)
         ));
     }
+
+    #[test]
+    fn test_burn_token() {
+        let mut chain = KvStoreChain::new(
+            RamKvStore::new(),
+            crate::config::blockchain::get_test_blockchain_config(),
+        )
+        .unwrap();
+        let token_id: TokenId =
+            "0x0001020304050607080900010203040506070809000102030405060708090001"
+                .parse()
+                .unwrap();
+        let addr: Address = "edae9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad640"
+            .parse()
+            .unwrap();
+        let tkn = Token {
+            name: "KeyvanCoin".into(),
+            symbol: "KIWI".into(),
+            supply: Amount(12345),
+            decimals: 2,
+            minter: None,
+            metadata: None,
+        };
+        super::create_token::create_token(&mut chain, addr.clone(), token_id, &tkn).unwrap();
+
+        // Burning is allowed even when the token has no minter
+        let (ops, _) = chain
+            .isolated(|chain| {
+                Ok(update_token(
+                    chain,
+                    addr.clone(),
+                    &token_id,
+                    &TokenUpdate::Burn { amount: Amount(45) },
+                )?)
+            })
+            .unwrap();
+
+        let expected_ops = vec![
+            WriteOp::Put(
+                "ACB-edae9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad640-0x0001020304050607080900010203040506070809000102030405060708090001".into(),
+                Amount(12300).into()
+            ),
+            WriteOp::Put(
+                "TKN-0x0001020304050607080900010203040506070809000102030405060708090001"
+                    .into(),
+                (&Token {
+                    name: "KeyvanCoin".into(),
+                    symbol: "KIWI".into(),
+                    supply: Amount(12300),
+                    decimals: 2,
+                    minter: None,
+                    metadata: None,
+                }).into()
+            ),
+        ];
+        assert_eq!(ops, expected_ops);
+
+        assert!(matches!(
+            chain.isolated(|chain| Ok(update_token(
+                chain,
+                addr,
+                &token_id,
+                &TokenUpdate::Burn {
+                    amount: Amount(12346)
+                }
+            )?)),
+            Err(BlockchainError::BalanceInsufficient)
+        ));
+
+        let ziesha_holder = crate::wallet::TxBuilder::new(&Vec::from("ABC")).get_address();
+        assert!(matches!(
+            chain.isolated(|chain| Ok(update_token(
+                chain,
+                ziesha_holder,
+                &TokenId::Ziesha,
+                &TokenUpdate::Burn { amount: Amount(1) }
+            )?)),
+            Err(BlockchainError::TokenNotUpdatable)
+        ));
+    }
+
+    #[test]
+    fn test_set_metadata() {
+        let mut chain = KvStoreChain::new(
+            RamKvStore::new(),
+            crate::config::blockchain::get_test_blockchain_config(),
+        )
+        .unwrap();
+        let token_id: TokenId =
+            "0x0001020304050607080900010203040506070809000102030405060708090001"
+                .parse()
+                .unwrap();
+        let addr: Address = "edae9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad640"
+            .parse()
+            .unwrap();
+        let tkn = Token {
+            name: "KeyvanCoin".into(),
+            symbol: "KIWI".into(),
+            supply: Amount(12345),
+            decimals: 2,
+            minter: Some(addr.clone()),
+            metadata: None,
+        };
+        super::create_token::create_token(&mut chain, addr.clone(), token_id, &tkn).unwrap();
+
+        let (ops, _) = chain
+            .isolated(|chain| {
+                Ok(update_token(
+                    chain,
+                    addr.clone(),
+                    &token_id,
+                    &TokenUpdate::SetMetadata {
+                        metadata: Some("https://ziesha.network".into()),
+                    },
+                )?)
+            })
+            .unwrap();
+
+        let expected_ops = vec![WriteOp::Put(
+            "TKN-0x0001020304050607080900010203040506070809000102030405060708090001".into(),
+            (&Token {
+                name: "KeyvanCoin".into(),
+                symbol: "KIWI".into(),
+                supply: Amount(12345),
+                decimals: 2,
+                minter: Some(addr.clone()),
+                metadata: Some("https://ziesha.network".into()),
+            })
+                .into(),
+        )];
+        assert_eq!(ops, expected_ops);
+
+        assert!(matches!(
+            chain.isolated(|chain| Ok(update_token(
+                chain,
+                addr,
+                &token_id,
+                &TokenUpdate::SetMetadata {
+                    metadata: Some("A".repeat(257))
+                }
+            )?)),
+            Err(BlockchainError::TokenBadMetadata)
+        ));
+    }
 }
@@ -0,0 +1,59 @@
+use super::*;
+
+pub fn vested_send<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    vesting_id: VestingId,
+    tx_src: Address,
+    dst: Address,
+    amount: Money,
+    start: u64,
+    end: u64,
+) -> Result<(), BlockchainError> {
+    let height = chain.get_height()?;
+    if start > end || end <= height {
+        return Err(BlockchainError::InvalidVesting);
+    }
+
+    if chain.is_frozen(tx_src.clone(), amount.token_id)? {
+        return Err(BlockchainError::TokenAccountFrozen);
+    }
+    let mut src_bal = chain.get_balance(tx_src.clone(), amount.token_id)?;
+    if src_bal < amount.amount {
+        return Err(BlockchainError::BalanceInsufficient);
+    }
+    src_bal -= amount.amount;
+
+    let vesting = Vesting {
+        amount,
+        unlocked: Amount(0),
+        start,
+        end,
+    };
+    let next_release = vesting
+        .next_release(height)
+        .ok_or(BlockchainError::Inconsistency)?;
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&tx_src, amount.token_id),
+            src_bal.into(),
+        ),
+        WriteOp::Put(
+            keys::VestingDbKey {
+                recipient: dst.clone(),
+                vesting_id,
+            }
+            .into(),
+            vesting.into(),
+        ),
+        WriteOp::Put(
+            keys::VestingCallbackDbKey {
+                block: next_release,
+                recipient: dst,
+                vesting_id,
+            }
+            .into(),
+            ().into(),
+        ),
+    ])?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use super::*;
+
+pub fn vote<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    proposal_id: ProposalId,
+    approve: bool,
+) -> Result<(), BlockchainError> {
+    if chain.get_proposal(proposal_id)?.is_none() {
+        return Err(BlockchainError::ProposalNotFound);
+    }
+    if chain.get_stake(tx_src.clone())? == Amount(0) {
+        return Err(BlockchainError::VoterHasNoStake);
+    }
+    // Voters may change their minds until the tally
+    chain.database.update(&[WriteOp::Put(
+        keys::VoteDbKey {
+            proposal_id,
+            voter: tx_src,
+        }
+        .into(),
+        approve.into(),
+    )])?;
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+// Maximum change of the base fee between two consecutive blocks is 1/8
+const BASE_FEE_CHANGE_DENOMINATOR: u128 = 8;
+
+// Part of the fee of a transaction that is burned. MPN updates are required in every
+// block and are exempted. Fees paid in other tokens cannot be compared with the base
+// fee on-chain, it's up to the validators to only accept them when they are worth it,
+// and they don't count towards the fullness of the block either.
+pub fn tx_base_fee<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    tx: &Transaction,
+) -> Result<Amount, BlockchainError> {
+    let is_mpn = if let TransactionData::UpdateContract { contract_id, .. } = &tx.data {
+        *contract_id == chain.config.mpn_config.mpn_contract_id
+    } else {
+        false
+    };
+    if tx.src.is_none()
+        || is_mpn
+        || tx.fee.token_id != TokenId::Ziesha
+        || !chain.is_active(Feature::BaseFee, chain.get_height()?)
+    {
+        return Ok(Amount(0));
+    }
+    Ok(Amount(
+        u64::from(chain.get_base_fee()?).saturating_mul(tx.size() as u64),
+    ))
+}
+
+// Adjusts the base fee for the next block, based on the size of the block just applied.
+// Only the txs paying their fees in Ziesha are counted (See `tx_base_fee`).
+pub fn update_base_fee<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    block_size: usize,
+) -> Result<(), BlockchainError> {
+    let next = next_base_fee(
+        chain.get_base_fee()?,
+        block_size,
+        chain.params()?.max_block_size,
+        chain.config.min_base_fee,
+    );
+    chain
+        .database
+        .update(&[WriteOp::Put(keys::base_fee(), next.into())])?;
+    Ok(())
+}
+
+/// Base fee moves towards keeping the blocks half full. It rises when a block is bigger
+/// than the target (At least by one unit, so that it can leave zero) and falls when it
+/// is smaller, proportional to the distance from the target.
+fn next_base_fee(
+    base_fee: Amount,
+    block_size: usize,
+    max_block_size: usize,
+    min_base_fee: Amount,
+) -> Amount {
+    let target = std::cmp::max(max_block_size / 2, 1) as u128;
+    let base = u64::from(base_fee) as u128;
+    let size = block_size as u128;
+    let next = if size > target {
+        base + std::cmp::max(
+            base * (size - target) / target / BASE_FEE_CHANGE_DENOMINATOR,
+            1,
+        )
+    } else {
+        base - base * (target - size) / target / BASE_FEE_CHANGE_DENOMINATOR
+    };
+    std::cmp::max(
+        Amount(std::cmp::min(next, u64::MAX as u128) as u64),
+        min_base_fee,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_base_fee() {
+        // Half full blocks keep the base fee
+        assert_eq!(
+            next_base_fee(Amount(800), 500, 1000, Amount(0)),
+            Amount(800)
+        );
+        // Full and empty blocks change it by 1/8
+        assert_eq!(
+            next_base_fee(Amount(800), 1000, 1000, Amount(0)),
+            Amount(900)
+        );
+        assert_eq!(next_base_fee(Amount(800), 0, 1000, Amount(0)), Amount(700));
+        assert_eq!(
+            next_base_fee(Amount(800), 750, 1000, Amount(0)),
+            Amount(850)
+        );
+        // Leaves zero when blocks get congested
+        assert_eq!(next_base_fee(Amount(0), 501, 1000, Amount(0)), Amount(1));
+        assert_eq!(next_base_fee(Amount(0), 0, 1000, Amount(0)), Amount(0));
+        // Never goes below the minimum
+        assert_eq!(next_base_fee(Amount(10), 0, 1000, Amount(9)), Amount(9));
+        assert_eq!(next_base_fee(Amount(0), 0, 1000, Amount(9)), Amount(9));
+    }
+}
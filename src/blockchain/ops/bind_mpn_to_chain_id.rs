@@ -0,0 +1,21 @@
+use super::*;
+
+// Replaces the update verifier keys of the MPN contract with the keys of the update circuit
+// bound to the chain-id, so that MPN transactions signed for other chains can't be proven.
+pub fn bind_mpn_to_chain_id<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+) -> Result<(), BlockchainError> {
+    let mpn_config = chain.config.mpn_config.clone();
+    let update_vk = mpn_config
+        .chain_bound_update_vk
+        .ok_or(BlockchainError::ChainBoundMpnKeyMissing)?;
+    let mut contract = chain.get_contract(mpn_config.mpn_contract_id)?;
+    for func in contract.functions.iter_mut() {
+        func.verifier_key = update_vk.clone();
+    }
+    chain.database.update(&[WriteOp::Put(
+        keys::contract(&mpn_config.mpn_contract_id),
+        contract.into(),
+    )])?;
+    Ok(())
+}
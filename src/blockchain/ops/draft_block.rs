@@ -5,6 +5,7 @@ pub fn draft_block<K: KvStore>(
     timestamp: u32,
     mempool: &[TransactionAndDelta],
     wallet: &TxBuilder,
+    fee_tokens: &HashMap<TokenId, ExchangeRate>,
     check: bool,
 ) -> Result<Option<BlockAndPatch>, BlockchainError> {
     let height = chain.get_height()?;
@@ -25,7 +26,18 @@ pub fn draft_block<K: KvStore>(
 
     let last_header = chain.get_header(height - 1)?;
 
-    let tx_and_deltas = chain.select_transactions(wallet.get_address(), mempool, check)?;
+    // Transactions of features that are not activated yet are left in the mempool
+    let mempool = mempool
+        .iter()
+        .filter(|tx| {
+            Feature::required_by(&tx.tx.data)
+                .map(|f| chain.is_active(f, height))
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let tx_and_deltas =
+        chain.select_transactions(wallet.get_address(), &mempool, fee_tokens, check)?;
 
     let mut txs = Vec::new();
 
@@ -58,15 +70,30 @@ pub fn draft_block<K: KvStore>(
             parent_hash: last_header.hash(),
             number: height as u64,
             block_root: Default::default(),
+            state_root: Default::default(),
             proof_of_stake: ProofOfStake {
                 timestamp,
                 validator: wallet.get_address(),
                 proof: validator_status,
             },
+            sig: Signature::Unsigned,
         },
         body: txs,
     };
     blk.header.block_root = blk.merkle_tree().root();
+    wallet.sign_header(&mut blk.header);
+
+    // The state root is only known after applying the block
+    if chain.is_active(Feature::StateRoot, height) {
+        match chain.isolated(|chain| apply_block_and_get_state_root(chain, &blk, false)) {
+            Err(BlockchainError::InsufficientMpnUpdates) => return Ok(None),
+            Err(e) => return Err(e),
+            Ok((_, state_root)) => {
+                blk.header.state_root = state_root;
+                wallet.sign_header(&mut blk.header);
+            }
+        }
+    }
 
     match chain.isolated(|chain| {
         chain.apply_block(&blk)?; // Check if everything is ok
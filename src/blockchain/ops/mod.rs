@@ -17,5 +17,21 @@ mod select_transactions;
 pub use select_transactions::*;
 mod rollback;
 pub use rollback::*;
+mod prune;
+pub use prune::*;
 mod pay_validator_and_delegators;
 pub use pay_validator_and_delegators::*;
+mod update_liveness;
+pub use update_liveness::*;
+mod update_validator_set;
+pub use update_validator_set::*;
+mod tally_proposals;
+pub use tally_proposals::*;
+mod release_vestings;
+pub use release_vestings::*;
+mod base_fee;
+pub use base_fee::*;
+mod state_root;
+pub use state_root::*;
+mod bind_mpn_to_chain_id;
+pub use bind_mpn_to_chain_id::*;
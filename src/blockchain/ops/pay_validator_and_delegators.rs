@@ -12,69 +12,68 @@ pub fn pay_validator_and_delegators<K: KvStore>(
         .ok_or(BlockchainError::ValidatorNotRegistered)?;
 
     let next_reward = chain.next_reward()? + fee_sum;
-    let stakers_reward =
-        u64::from(next_reward) as f64 * (1.0f64 - Into::<f64>::into(staker.commission) as f64);
-
-    let delegators = chain.get_delegators(validator.clone(), None)?;
-    let total_f64 = delegators
-        .iter()
-        .map(|(_, a)| Into::<u64>::into(*a))
-        .sum::<u64>() as f64;
-    let mut payments = delegators
-        .into_iter()
-        .map(|(addr, stake)| {
-            (
-                addr,
-                Amount(((u64::from(stake) as f64 / total_f64) * stakers_reward) as u64),
-            )
-        })
-        .collect::<Vec<_>>();
-
-    let validator_reward = next_reward
-        - payments
-            .iter()
-            .map(|(_, a)| *a)
-            .fold(Amount(0), |a, b| a + b);
-    payments.push((validator.clone(), validator_reward));
-    for (addr, amnt) in payments.into_iter() {
+    let total_stake = chain.get_stake(validator.clone())?;
+    let (stakers_reward, reward_per_stake, validator_reward) =
+        split_reward(next_reward, staker.commission, total_stake);
+
+    chain.apply_tx(
+        &Transaction {
+            memo: String::new(),
+            src: None,
+            data: TransactionData::RegularSend {
+                entries: vec![RegularSendEntry {
+                    dst: validator.clone(),
+                    amount: Money {
+                        amount: validator_reward,
+                        token_id: TokenId::Ziesha,
+                    },
+                }],
+            },
+            nonce: 0,
+            fee: Money::ziesha(0),
+            valid_until: None,
+            sig: Signature::Unsigned,
+        },
+        true,
+    )?;
+    let auto_delegate_ratio =
+        chain.get_auto_delegate_ratio(validator.clone(), validator.clone())?;
+    if auto_delegate_ratio > Ratio(0) {
+        let auto_delegate_amount = auto_delegate_ratio.apply(validator_reward);
         chain.apply_tx(
             &Transaction {
                 memo: String::new(),
-                src: None,
-                data: TransactionData::RegularSend {
-                    entries: vec![RegularSendEntry {
-                        dst: addr.clone(),
-                        amount: Money {
-                            amount: amnt,
-                            token_id: TokenId::Ziesha,
-                        },
-                    }],
+                src: Some(validator.clone()),
+                data: TransactionData::Delegate {
+                    to: validator.clone(),
+                    amount: auto_delegate_amount,
                 },
                 nonce: 0,
                 fee: Money::ziesha(0),
+                valid_until: None,
                 sig: Signature::Unsigned,
             },
             true,
         )?;
-        let auto_delegate_ratio = chain.get_auto_delegate_ratio(addr.clone(), validator.clone())?;
-        if auto_delegate_ratio > Ratio(0) {
-            let auto_delegate_amount =
-                Amount((amnt.0 as f64 * Into::<f64>::into(auto_delegate_ratio)) as u64);
-            chain.apply_tx(
-                &Transaction {
-                    memo: String::new(),
-                    src: Some(addr.clone()),
-                    data: TransactionData::Delegate {
-                        to: validator.clone(),
-                        amount: auto_delegate_amount,
-                    },
-                    nonce: 0,
-                    fee: Money::ziesha(0),
-                    sig: Signature::Unsigned,
-                },
-                true,
-            )?;
+    }
+
+    // Delegators' share is moved to the reward-pool of the validator, to be claimed
+    // lazily by each delegator (See `claim_reward`)
+    if stakers_reward > Amount(0) {
+        let treasury_balance = chain.get_balance(Default::default(), TokenId::Ziesha)?;
+        if treasury_balance < stakers_reward {
+            return Err(BlockchainError::BalanceInsufficient);
         }
+        let mut pool = chain.get_reward_pool(validator.clone())?;
+        pool.reward_per_stake = pool.reward_per_stake.wrapping_add(reward_per_stake);
+        pool.balance += stakers_reward;
+        chain.database.update(&[
+            WriteOp::Put(
+                keys::account_balance(&Default::default(), TokenId::Ziesha),
+                (treasury_balance - stakers_reward).into(),
+            ),
+            WriteOp::Put(keys::reward_pool(&validator), pool.into()),
+        ])?;
     }
 
     for (k, _) in chain
@@ -106,3 +105,172 @@ pub fn pay_validator_and_delegators<K: KvStore>(
 
     Ok(validator_reward)
 }
+
+// Fees paid in tokens other than Ziesha only reach the treasury while the txs of the
+// block are applied, so they are distributed after the block body, in their own tokens.
+// Delegators' share goes to a reward-pool per token (See `claim_reward`)
+pub fn pay_token_fees<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    validator: Address,
+    fees: &HashMap<TokenId, Amount>,
+) -> Result<(), BlockchainError> {
+    let staker = chain
+        .get_staker(validator.clone())?
+        .ok_or(BlockchainError::ValidatorNotRegistered)?;
+    let total_stake = chain.get_stake(validator.clone())?;
+
+    for (token_id, fee_sum) in fees.iter() {
+        if *fee_sum == Amount(0) {
+            continue;
+        }
+        let treasury_balance = chain.get_balance(Default::default(), *token_id)?;
+        if treasury_balance < *fee_sum {
+            return Err(BlockchainError::BalanceInsufficient);
+        }
+        let (stakers_reward, reward_per_stake, validator_reward) =
+            split_reward(*fee_sum, staker.commission, total_stake);
+
+        let validator_balance = chain.get_balance(validator.clone(), *token_id)?;
+        chain.database.update(&[
+            WriteOp::Put(
+                keys::account_balance(&Default::default(), *token_id),
+                (treasury_balance - *fee_sum).into(),
+            ),
+            WriteOp::Put(
+                keys::account_balance(&validator, *token_id),
+                (validator_balance + validator_reward).into(),
+            ),
+        ])?;
+
+        if stakers_reward > Amount(0) {
+            let pool_key: crate::db::StringKey = keys::TokenRewardPoolDbKey {
+                staker: validator.clone(),
+                token_id: *token_id,
+            }
+            .into();
+            let mut pool: RewardPool = match chain.database.get(pool_key.clone())? {
+                Some(b) => b.try_into()?,
+                None => Default::default(),
+            };
+            pool.reward_per_stake = pool.reward_per_stake.wrapping_add(reward_per_stake);
+            pool.balance += stakers_reward;
+            chain
+                .database
+                .update(&[WriteOp::Put(pool_key, pool.into())])?;
+        }
+    }
+    Ok(())
+}
+
+/// Splits `reward` between the delegators of a validator and the validator itself,
+/// using exact integer arithmetic. Returns the delegators' share, the amount added to
+/// the reward-per-stake of the validator, and the validator's reward.
+///
+/// Delegators share `reward * (255 - commission) / 255`, rounded down, and the rest (The
+/// commission and the remainder of that division) goes to the validator, therefore the
+/// two always sum up to exactly `reward`. The whole share of the delegators goes to the
+/// reward-pool, while the reward-per-stake is rounded down, so the pool can always pay
+/// the claims. Remainders of rounding the claims of each delegation down are not known
+/// until claimed, so they stay in the pool rather than going to the validator.
+/// If nobody has delegated to the validator, the validator receives everything.
+fn split_reward(reward: Amount, commission: Ratio, total_stake: Amount) -> (Amount, u128, Amount) {
+    if total_stake == Amount(0) {
+        return (Amount(0), 0, reward);
+    }
+    let stakers_reward = Amount(
+        (u64::from(reward) as u128 * (u8::MAX - commission.0) as u128 / u8::MAX as u128) as u64,
+    );
+    let reward_per_stake =
+        u64::from(stakers_reward) as u128 * RewardPool::PRECISION / u64::from(total_stake) as u128;
+    (stakers_reward, reward_per_stake, reward - stakers_reward)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    #[test]
+    fn test_split_reward_sums_exactly() {
+        let mut rng = ChaChaRng::seed_from_u64(123);
+        for _ in 0..10000 {
+            let reward = Amount(match rng.gen_range(0..3) {
+                0 => rng.gen_range(0..100),
+                1 => rng.gen_range(0..u64::MAX / 2),
+                _ => u64::MAX / 2,
+            });
+            let commission = Ratio(rng.gen());
+            let delegations = (0..rng.gen_range(0..32))
+                .map(|_| {
+                    Amount(match rng.gen_range(0..3) {
+                        0 => rng.gen_range(0..100),
+                        1 => rng.gen_range(0..u64::MAX / 32),
+                        _ => 0,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let total_stake = delegations.iter().fold(Amount(0), |a, b| a + *b);
+
+            let (stakers_reward, reward_per_stake, validator_reward) =
+                split_reward(reward, commission, total_stake);
+            assert_eq!(stakers_reward + validator_reward, reward);
+
+            // Validator never gets less than its commission
+            assert!(validator_reward >= commission.apply(reward));
+
+            // Reward-pool can always pay the claims, and the remainder left in
+            // the pool is less than a unit per delegation
+            let pool = RewardPool {
+                reward_per_stake,
+                balance: stakers_reward,
+            };
+            let claimed = delegations
+                .iter()
+                .map(|amount| {
+                    Delegate {
+                        amount: *amount,
+                        reward_per_stake: 0,
+                    }
+                    .pending_reward(&pool)
+                })
+                .fold(Amount(0), |a, b| a + b);
+            assert!(claimed <= stakers_reward);
+            assert!(u64::from(stakers_reward - claimed) <= delegations.len() as u64);
+        }
+    }
+
+    #[test]
+    fn test_split_reward_remainders() {
+        // 100 * 243 / 255 = 95.29, the remainder of the division goes to the validator
+        let (stakers_reward, reward_per_stake, validator_reward) =
+            split_reward(Amount(100), Ratio(12), Amount(3));
+        assert_eq!(stakers_reward, Amount(95));
+        assert_eq!(validator_reward, Amount(5));
+
+        // Each delegation of 1 claims 31.67 rounded down, the rest stays in the pool
+        let pool = RewardPool {
+            reward_per_stake,
+            balance: stakers_reward,
+        };
+        let claim = Delegate {
+            amount: Amount(1),
+            reward_per_stake: 0,
+        }
+        .pending_reward(&pool);
+        assert_eq!(claim, Amount(31));
+        assert_eq!(stakers_reward - claim - claim - claim, Amount(2));
+    }
+
+    #[test]
+    fn test_split_reward_without_delegators() {
+        assert_eq!(
+            split_reward(Amount(100), Ratio(12), Amount(0)),
+            (Amount(0), 0, Amount(100))
+        );
+        assert_eq!(
+            split_reward(Amount(255), Ratio(12), Amount(3)),
+            (Amount(243), 81 * RewardPool::PRECISION, Amount(12))
+        );
+    }
+}
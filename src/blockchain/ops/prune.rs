@@ -0,0 +1,27 @@
+use super::*;
+
+// Removes the bodies, merkle trees and rollback data of the blocks before the last
+// `retention` blocks. Headers are kept, and so is the genesis block, which is checked
+// when the chain is opened.
+pub fn prune<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    retention: u64,
+) -> Result<(), BlockchainError> {
+    let pruned_height = chain.get_pruned_height()?;
+    let new_pruned_height = chain.get_height()?.saturating_sub(retention);
+    if new_pruned_height <= pruned_height {
+        return Ok(());
+    }
+    let mut ops = Vec::new();
+    for index in std::cmp::max(pruned_height, 1)..new_pruned_height {
+        ops.push(WriteOp::Remove(keys::block(index)));
+        ops.push(WriteOp::Remove(keys::merkle(index)));
+        ops.push(WriteOp::Remove(keys::rollback(index)));
+    }
+    ops.push(WriteOp::Put(
+        keys::pruned_height(),
+        new_pruned_height.into(),
+    ));
+    chain.database.update(&ops)?;
+    Ok(())
+}
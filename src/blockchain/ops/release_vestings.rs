@@ -0,0 +1,53 @@
+use super::*;
+
+// Credits the portions of the vestings that are unlocked in the current block to the
+// balances of their recipients, and schedules the next release of each vesting.
+pub fn release_vestings<K: KvStore>(chain: &mut KvStoreChain<K>) -> Result<(), BlockchainError> {
+    let height = chain.get_height()?;
+    for (k, _) in chain
+        .database
+        .pairs(keys::VestingCallbackDbKey::prefix(height).into())?
+        .into_iter()
+        .collect::<Vec<_>>()
+    {
+        let key = keys::VestingCallbackDbKey::try_from(k.clone())?;
+        let mut vesting = chain
+            .get_vesting(key.recipient.clone(), key.vesting_id)?
+            .ok_or(BlockchainError::Inconsistency)?;
+        let unlocked = vesting.unlocked_at(height);
+        let new_balance = chain.get_balance(key.recipient.clone(), vesting.amount.token_id)?
+            + (unlocked - vesting.unlocked);
+        vesting.unlocked = unlocked;
+
+        let vesting_key = keys::VestingDbKey {
+            recipient: key.recipient.clone(),
+            vesting_id: key.vesting_id,
+        };
+        let mut ops = vec![
+            WriteOp::Remove(k),
+            WriteOp::Put(
+                keys::account_balance(&key.recipient, vesting.amount.token_id),
+                new_balance.into(),
+            ),
+        ];
+        match vesting.next_release(height) {
+            Some(next) => {
+                ops.push(WriteOp::Put(
+                    keys::VestingCallbackDbKey {
+                        block: next,
+                        recipient: key.recipient.clone(),
+                        vesting_id: key.vesting_id,
+                    }
+                    .into(),
+                    ().into(),
+                ));
+                ops.push(WriteOp::Put(vesting_key.into(), vesting.into()));
+            }
+            None => {
+                ops.push(WriteOp::Remove(vesting_key.into()));
+            }
+        }
+        chain.database.update(&ops)?;
+    }
+    Ok(())
+}
@@ -7,6 +7,9 @@ pub fn rollback<K: KvStore>(chain: &mut KvStoreChain<K>) -> Result<(), Blockchai
         if height == 0 {
             return Err(BlockchainError::NoBlocksToRollback);
         }
+        if height - 1 < chain.get_pruned_height()? {
+            return Err(BlockchainError::RollbackBeyondPruned);
+        }
 
         let rollback: Vec<WriteOp> = match chain.database.get(keys::rollback(height - 1))? {
             Some(b) => b.try_into()?,
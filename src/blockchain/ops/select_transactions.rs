@@ -4,27 +4,40 @@ pub fn select_transactions<K: KvStore>(
     chain: &KvStoreChain<K>,
     validator: Address,
     txs: &[TransactionAndDelta],
+    fee_tokens: &HashMap<TokenId, ExchangeRate>,
     check: bool,
 ) -> Result<Vec<TransactionAndDelta>, BlockchainError> {
+    let is_mpn = |tx: &TransactionAndDelta| {
+        if let TransactionData::UpdateContract { contract_id, .. } = &tx.tx.data {
+            *contract_id == chain.config.mpn_config.mpn_contract_id
+        } else {
+            false
+        }
+    };
+    let base_fee = if chain.is_active(Feature::BaseFee, chain.get_height()?) {
+        u64::from(chain.get_base_fee()?)
+    } else {
+        0
+    };
+
+    // Fees are compared in Ziesha, txs paying their fees in tokens that the validator
+    // does not accept, or not covering the base fee, are left in the mempool
     let mut sorted = txs
         .iter()
-        .filter(|t| t.tx.fee.token_id == TokenId::Ziesha)
-        .cloned()
+        .filter_map(|t| normalized_fee(&t.tx.fee, fee_tokens).map(|fee| (fee, t.clone())))
+        .filter(|(fee, t)| {
+            is_mpn(t) || u64::from(*fee) >= base_fee.saturating_mul(t.tx.size() as u64)
+        })
         .collect::<Vec<_>>();
-    // WARN: Sort will be invalid if not all fees are specified in Ziesha
-    sorted.sort_unstable_by_key(|tx| {
+    sorted.sort_unstable_by_key(|(fee, tx)| {
         let cost = tx.tx.size();
-        let is_mpn = if let TransactionData::UpdateContract { contract_id, .. } = &tx.tx.data {
-            *contract_id == chain.config.mpn_config.mpn_contract_id
-        } else {
-            false
-        };
         (
-            is_mpn,
-            Into::<u64>::into(tx.tx.fee.amount) / cost as u64,
+            is_mpn(tx),
+            Into::<u64>::into(*fee) / cost as u64,
             -(tx.tx.nonce as i32),
         )
     });
+    let sorted = sorted.into_iter().map(|(_, tx)| tx).collect::<Vec<_>>();
     if !check {
         return Ok(sorted);
     }
@@ -32,6 +45,8 @@ pub fn select_transactions<K: KvStore>(
         // Safe to consider a 0 fee-sum
         chain.pay_validator_and_delegators(validator, Amount(0))?;
 
+        let params = chain.params()?;
+        let chain_ids = chain.accepted_chain_ids(chain.get_height()?);
         let mut result = Vec::new();
         let mut block_sz = 0usize;
         let mut delta_cnt = 0isize;
@@ -44,9 +59,11 @@ pub fn select_transactions<K: KvStore>(
                         0
                     };
                     let block_diff = tx.tx.size();
-                    if delta_cnt + delta_diff <= chain.config.max_delta_count as isize
-                        && block_sz + block_diff <= chain.config.max_block_size
-                        && tx.tx.verify_signature()
+                    if delta_cnt + delta_diff <= params.max_delta_count as isize
+                        && block_sz + block_diff <= params.max_block_size
+                        && chain_ids
+                            .iter()
+                            .any(|id| tx.tx.verify_signature(id.as_ref()))
                     {
                         delta_cnt += delta_diff;
                         block_sz += block_diff;
@@ -55,13 +72,7 @@ pub fn select_transactions<K: KvStore>(
                     }
                 }
                 Err(e) => {
-                    let is_mpn =
-                        if let TransactionData::UpdateContract { contract_id, .. } = &tx.tx.data {
-                            *contract_id == chain.config.mpn_config.mpn_contract_id
-                        } else {
-                            false
-                        };
-                    if is_mpn {
+                    if is_mpn(&tx) {
                         log::error!("MPN transaction rejected: {}", e);
                     }
                 }
@@ -71,3 +82,13 @@ pub fn select_transactions<K: KvStore>(
     })?;
     Ok(result)
 }
+
+fn normalized_fee(fee: &Money, fee_tokens: &HashMap<TokenId, ExchangeRate>) -> Option<Amount> {
+    if fee.token_id == TokenId::Ziesha {
+        Some(fee.amount)
+    } else {
+        fee_tokens
+            .get(&fee.token_id)
+            .map(|rate| rate.to_ziesha(fee.amount))
+    }
+}
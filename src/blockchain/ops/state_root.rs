@@ -0,0 +1,246 @@
+use super::*;
+use crate::crypto::merkle::{
+    sparse_merkle_bit, sparse_merkle_merge, SparseMerkleNode, SparseMerkleProof,
+    SPARSE_MERKLE_DEPTH,
+};
+use crate::db::{Blob, StringKey};
+
+type StateHash = <Hasher as Hash>::Output;
+type StateNode = SparseMerkleNode<Hasher>;
+
+fn is_account_state(key: &StringKey) -> bool {
+    keys::ACCOUNT_STATE_PREFIXES
+        .iter()
+        .any(|prefix| key.0.starts_with(prefix))
+}
+
+// Position of a key in the state tree, and the leaf committing to its value (Zero when
+// the key does not exist)
+pub fn state_leaf(key: &StringKey, value: Option<&Blob>) -> (StateHash, StateHash) {
+    let path = Hasher::hash(key.0.as_bytes());
+    let leaf = match value {
+        Some(value) => {
+            let mut preimage = key.0.as_bytes().to_vec();
+            preimage.extend(&value.0);
+            Hasher::hash(&preimage)
+        }
+        None => Default::default(),
+    };
+    (path, leaf)
+}
+
+// Node of the state tree at the given depth, on the way to the given path
+fn node_key(depth: usize, path: &StateHash) -> StringKey {
+    let mut masked = *path;
+    for (i, byte) in masked.iter_mut().enumerate() {
+        let start = i * 8;
+        if start >= depth {
+            *byte = 0;
+        } else if start + 8 > depth {
+            *byte &= 0xff << (start + 8 - depth);
+        }
+    }
+    keys::state_tree_node(depth, &masked)
+}
+
+fn sibling_path(path: &StateHash, depth: usize) -> StateHash {
+    let mut sibling = *path;
+    sibling[(depth - 1) / 8] ^= 1 << (7 - (depth - 1) % 8);
+    sibling
+}
+
+fn get_node<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    depth: usize,
+    path: &StateHash,
+) -> Result<Option<StateNode>, BlockchainError> {
+    Ok(match chain.database.get(node_key(depth, path))? {
+        Some(b) => Some(b.try_into()?),
+        None => None,
+    })
+}
+
+fn node_hash(node: &Option<StateNode>) -> StateHash {
+    node.as_ref().map(|n| n.hash()).unwrap_or_default()
+}
+
+pub fn get_state_root<K: KvStore>(chain: &KvStoreChain<K>) -> Result<StateHash, BlockchainError> {
+    Ok(node_hash(&get_node(chain, 0, &Default::default())?))
+}
+
+// Sets the leaf of a path in the state tree (Zero removes it), keeping single leaves as
+// high as possible so that the tree does not depend on the order of the changes
+fn set_state_leaf<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    path: &StateHash,
+    leaf: &StateHash,
+) -> Result<(), BlockchainError> {
+    let removing = *leaf == StateHash::default();
+    let mut depth = 0;
+    loop {
+        match get_node(chain, depth, path)? {
+            Some(StateNode::Branch(_)) => {
+                depth += 1;
+            }
+            Some(StateNode::Leaf {
+                path: other_path,
+                leaf: other_leaf,
+            }) if other_path != *path => {
+                if removing {
+                    return Ok(());
+                }
+                // Both leaves go below the first bit their paths differ in
+                let diverge = (depth..SPARSE_MERKLE_DEPTH)
+                    .find(|d| {
+                        sparse_merkle_bit::<Hasher>(path, *d)
+                            != sparse_merkle_bit::<Hasher>(&other_path, *d)
+                    })
+                    .ok_or(BlockchainError::Inconsistency)?;
+                chain.database.update(&[WriteOp::Put(
+                    node_key(diverge + 1, &other_path),
+                    StateNode::Leaf {
+                        path: other_path,
+                        leaf: other_leaf,
+                    }
+                    .into(),
+                )])?;
+                depth = diverge + 1;
+                break;
+            }
+            Some(StateNode::Leaf { .. }) => {
+                break;
+            }
+            None => {
+                if removing {
+                    return Ok(());
+                }
+                break;
+            }
+        }
+    }
+    chain.database.update(&[if removing {
+        WriteOp::Remove(node_key(depth, path))
+    } else {
+        WriteOp::Put(
+            node_key(depth, path),
+            StateNode::Leaf {
+                path: *path,
+                leaf: *leaf,
+            }
+            .into(),
+        )
+    }])?;
+
+    for depth in (0..depth).rev() {
+        let child = get_node(chain, depth + 1, path)?;
+        let sibling_path = sibling_path(path, depth + 1);
+        let sibling = get_node(chain, depth + 1, &sibling_path)?;
+        let op = match (&child, &sibling) {
+            (None, None) => WriteOp::Remove(node_key(depth, path)),
+            // A leaf left alone in its subtree moves up
+            (Some(leaf @ StateNode::Leaf { .. }), None)
+            | (None, Some(leaf @ StateNode::Leaf { .. })) => {
+                let below = if child.is_some() { path } else { &sibling_path };
+                chain
+                    .database
+                    .update(&[WriteOp::Remove(node_key(depth + 1, below))])?;
+                WriteOp::Put(node_key(depth, path), leaf.clone().into())
+            }
+            _ => {
+                let (child, sibling) = (node_hash(&child), node_hash(&sibling));
+                let hash = if sparse_merkle_bit::<Hasher>(path, depth) {
+                    sparse_merkle_merge::<Hasher>(&sibling, &child)
+                } else {
+                    sparse_merkle_merge::<Hasher>(&child, &sibling)
+                };
+                WriteOp::Put(node_key(depth, path), StateNode::Branch(hash).into())
+            }
+        };
+        chain.database.update(&[op])?;
+    }
+    Ok(())
+}
+
+// Updates the paths of the account state keys written by the given ops
+pub fn update_state_root<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    changes: &[WriteOp],
+) -> Result<(), BlockchainError> {
+    for change in changes.iter() {
+        let (key, value) = match change {
+            WriteOp::Put(k, v) => (k, Some(v)),
+            WriteOp::Remove(k) => (k, None),
+        };
+        if !is_account_state(key) {
+            continue;
+        }
+        let (path, leaf) = state_leaf(key, value);
+        set_state_leaf(chain, &path, &leaf)?;
+    }
+    Ok(())
+}
+
+pub fn prove_state<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    key: &StringKey,
+) -> Result<SparseMerkleProof<Hasher>, BlockchainError> {
+    let path = Hasher::hash(key.0.as_bytes());
+    let mut siblings = Vec::new();
+    let mut other_leaf = None;
+    let mut depth = 0;
+    loop {
+        match get_node(chain, depth, &path)? {
+            Some(StateNode::Branch(_)) => {
+                depth += 1;
+                siblings.push(node_hash(&get_node(
+                    chain,
+                    depth,
+                    &sibling_path(&path, depth),
+                )?));
+            }
+            Some(StateNode::Leaf {
+                path: leaf_path,
+                leaf,
+            }) => {
+                if leaf_path != path {
+                    other_leaf = Some((leaf_path, leaf));
+                }
+                break;
+            }
+            None => {
+                break;
+            }
+        }
+    }
+    siblings.reverse();
+    Ok(SparseMerkleProof {
+        siblings,
+        other_leaf,
+    })
+}
+
+/// Checks the value of an account state key (None if it does not exist) against the
+/// state root of a header
+pub fn verify_state_proof(
+    header: &Header,
+    key: &StringKey,
+    value: Option<&Blob>,
+    proof: &SparseMerkleProof<Hasher>,
+) -> bool {
+    let (path, leaf) = state_leaf(key, value);
+    proof.verify(&header.state_root, &path, &leaf)
+}
+
+/// Checks the balance of an account against the state root of a header. Zero balances
+/// may also be proven by the absence of the balance in the state.
+pub fn verify_balance_proof(
+    header: &Header,
+    address: &Address,
+    token_id: TokenId,
+    balance: Amount,
+    proof: &SparseMerkleProof<Hasher>,
+) -> bool {
+    let key = keys::account_balance(address, token_id);
+    verify_state_proof(header, &key, Some(&balance.into()), proof)
+        || (balance == Amount(0) && verify_state_proof(header, &key, None, proof))
+}
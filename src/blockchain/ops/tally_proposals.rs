@@ -0,0 +1,79 @@
+use super::*;
+
+// Counts the votes of the proposals whose voting period has ended, weighting each
+// vote by the current stake of the voter. Only stakers (Not jailed) are counted, on
+// both sides of the approval ratio. Parameter changes of the accepted proposals are
+// stored, to be merged into the config from their activation heights.
+pub fn tally_proposals<K: KvStore>(chain: &mut KvStoreChain<K>) -> Result<(), BlockchainError> {
+    let height = chain.get_height()?;
+    let stakers: HashMap<Address, Amount> = chain.get_stakers()?.into_iter().collect();
+    let total_stake = Amount(stakers.values().map(|a| u64::from(*a)).sum::<u64>());
+
+    let mut ops = Vec::new();
+    for (k, v) in chain
+        .database
+        .pairs(keys::ProposalDbKey::prefix().into())?
+        .into_iter()
+    {
+        let proposal_id = keys::ProposalDbKey::try_from(k.clone())?.proposal_id;
+        let proposal: Proposal = v.try_into()?;
+        if proposal.voting_ends > height {
+            continue;
+        }
+
+        let mut approvals = Amount(0);
+        for (k, v) in chain
+            .database
+            .pairs(keys::VoteDbKey::prefix(&proposal_id).into())?
+            .into_iter()
+        {
+            let voter = keys::VoteDbKey::try_from(k.clone())?.voter;
+            let approve: bool = v.try_into()?;
+            if approve {
+                approvals += stakers.get(&voter).cloned().unwrap_or_default();
+            }
+            ops.push(WriteOp::Remove(k));
+        }
+
+        if approvals > chain.config.proposal_approval_ratio.apply(total_stake) {
+            ops.push(WriteOp::Put(
+                keys::ConfigOverrideDbKey {
+                    activation_height: proposal.activation_height,
+                    proposal_id,
+                }
+                .into(),
+                proposal.changes.into(),
+            ));
+        }
+        ops.push(WriteOp::Remove(k));
+    }
+    chain.database.update(&ops)?;
+    Ok(())
+}
+
+// Merges the stored parameter changes that activate at the given height into the
+// active config changes, so that reading the config does not need to scan them.
+pub fn activate_config_overrides<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    height: u64,
+) -> Result<(), BlockchainError> {
+    let mut active = chain.get_active_config_changes()?;
+    let mut ops = Vec::new();
+    for (k, v) in chain
+        .database
+        .pairs(keys::ConfigOverrideDbKey::prefix().into())?
+        .into_iter()
+    {
+        if keys::ConfigOverrideDbKey::try_from(k.clone())?.activation_height > height {
+            break;
+        }
+        let changes: Vec<ConfigParam> = v.try_into()?;
+        active.extend(changes);
+        ops.push(WriteOp::Remove(k));
+    }
+    if !ops.is_empty() {
+        ops.push(WriteOp::Put(keys::active_config(), active.into()));
+        chain.database.update(&ops)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+// Number of standard deviations a staker may produce below its expected number of slots
+// without being considered missing them
+const LIVENESS_TOLERANCE_SIGMAS: u64 = 2;
+
+// Slots a staker is expected to win in an epoch, and the tolerance for the variance of
+// the election. Each slot is won with the probability of the staker's share of stake.
+fn expected_slots(num_slots: u64, stake: u64, total_stake: u64) -> (u64, u64) {
+    let (n, s, t) = (num_slots as u128, stake as u128, total_stake as u128);
+    let expected = (n * s / t) as u64;
+    let variance = (n * s * (t - s) / (t * t)) as u64;
+    (expected, LIVENESS_TOLERANCE_SIGMAS * (variance.isqrt() + 1))
+}
+
+// Evaluates the validators of an epoch when it ends. The slots won by a staker are only
+// known to itself (VRF), so each staker is expected to win the slots of the epoch with
+// the probability of its share of stake, including the slots nobody produced a block in.
+// Stakers producing less than that (Minus a tolerance for the variance of the election)
+// accumulate missed slots, and are jailed once they miss too many.
+pub fn update_liveness<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    epoch: u32,
+) -> Result<(), BlockchainError> {
+    let mut produced: HashMap<Address, u64> = HashMap::new();
+    let mut head = chain.get_tip()?;
+    while chain.epoch_slot(head.proof_of_stake.timestamp).0 == epoch && head.number > 0 {
+        *produced
+            .entry(head.proof_of_stake.validator.clone())
+            .or_default() += 1;
+        head = chain.get_header(head.number - 1)?;
+    }
+    let num_slots = chain.config.slot_per_epoch as u64;
+
+    let stakers = chain.get_validator_set()?;
+    let total_stake = stakers.iter().map(|(_, a)| u64::from(*a)).sum::<u64>();
+    if total_stake == 0 {
+        return Ok(());
+    }
+
+    let mut livenesses = Vec::new();
+    for (addr, stake) in stakers {
+        let (expected, tolerance) = expected_slots(num_slots, u64::from(stake), total_stake);
+        let missed =
+            expected.saturating_sub(produced.get(&addr).cloned().unwrap_or_default() + tolerance);
+        let mut liveness = chain.get_liveness(addr.clone())?;
+        liveness.total_expected += expected;
+        liveness.total_missed += missed;
+        liveness.missed = if missed == 0 {
+            0
+        } else {
+            liveness.missed.saturating_add(missed as u32)
+        };
+        if liveness.missed > chain.config.max_missed_slots {
+            liveness.jailed = true;
+        }
+        livenesses.push((addr, liveness));
+    }
+
+    // Jailing every staker would halt the chain, as nobody would be left to produce the
+    // blocks (Including the unjail transactions)
+    let remaining = chain
+        .get_stakers()?
+        .into_iter()
+        .filter(|(addr, stake)| {
+            *stake > Amount(0)
+                && !livenesses
+                    .iter()
+                    .any(|(jailed, liveness)| jailed == addr && liveness.jailed)
+        })
+        .count();
+    if remaining == 0 {
+        for (_, liveness) in livenesses.iter_mut() {
+            liveness.jailed = false;
+        }
+    }
+
+    let ops = livenesses
+        .into_iter()
+        .map(|(addr, liveness)| WriteOp::Put(keys::liveness(&addr), liveness.into()))
+        .collect::<Vec<_>>();
+    chain.database.update(&ops)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_slots() {
+        // A third of the stake, 20 +- 3.65 slots
+        assert_eq!(expected_slots(60, 25, 75), (20, 8));
+        // Tiny stakes are not expected to win any slots for sure
+        assert_eq!(expected_slots(60, 1, 1000), (0, 2));
+        assert_eq!(expected_slots(60, 75, 75), (60, 2));
+    }
+}
@@ -0,0 +1,26 @@
+use super::*;
+
+// Freezes the stakers allowed to validate in the upcoming epoch, so that moving
+// stake or changing VRF keys in the middle of an epoch does not change the elections. The
+// previous set is kept when there are no stakers left, as an empty set halts the chain.
+pub fn update_validator_set<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+) -> Result<(), BlockchainError> {
+    let mut stakers = chain.get_stakers()?; // Sorted by stake, descending
+    stakers.retain(|(_, stake)| *stake > Amount(0));
+    if stakers.is_empty() {
+        return Ok(());
+    }
+    stakers.truncate(chain.config.max_validators);
+    let mut validator_stakers = Vec::new();
+    for (addr, _) in stakers.iter() {
+        if let Some(staker) = chain.get_staker(addr.clone())? {
+            validator_stakers.push((addr.clone(), staker));
+        }
+    }
+    chain.database.update(&[
+        WriteOp::Put(keys::validator_set(), stakers.into()),
+        WriteOp::Put(keys::validator_stakers(), validator_stakers.into()),
+    ])?;
+    Ok(())
+}
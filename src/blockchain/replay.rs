@@ -0,0 +1,202 @@
+use super::*;
+use crate::db::{Blob, RamKvStore, StringKey};
+use std::collections::HashSet;
+
+const CONTRACT_STATE_PREFIX: &str = "S-";
+const STATE_TREE_PREFIX: &str = "STN-";
+
+// Number of blocks replayed at once, bounding the rollback data kept in memory
+const REPLAY_WINDOW: u64 = 1024;
+
+// Contract states are not part of the blocks (Only their compressed states are), so they
+// are not replayed. State tree nodes are already checked through the state roots of the
+// replayed headers.
+fn is_replayed(key: &StringKey) -> bool {
+    !key.0.starts_with(CONTRACT_STATE_PREFIX)
+        && !key.0.starts_with(STATE_TREE_PREFIX)
+        && *key != keys::outdated()
+}
+
+// Keys written by the block at the given height, with their values before the block
+fn get_writes<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    height: u64,
+) -> Result<HashMap<StringKey, Option<Blob>>, BlockchainError> {
+    let rollback: Vec<WriteOp> = match chain.database.get(keys::rollback(height))? {
+        Some(b) => b.try_into()?,
+        None => {
+            return Err(if height < chain.get_pruned_height()? {
+                BlockchainError::BlockPruned
+            } else {
+                BlockchainError::Inconsistency
+            });
+        }
+    };
+    Ok(rollback
+        .into_iter()
+        .map(|op| match op {
+            WriteOp::Put(k, v) => (k, Some(v)),
+            WriteOp::Remove(k) => (k, None),
+        })
+        .filter(|(k, _)| is_replayed(k))
+        .collect())
+}
+
+/// A block executed again on a fresh chain
+#[derive(Debug, Clone)]
+pub struct ReplayedBlock {
+    pub height: u64,
+    pub header_hash: <Hasher as Hash>::Output,
+    // Chained hash of the values written by the blocks up to this one
+    pub checksum: <Hasher as Hash>::Output,
+    // Keys written by the block, having different values in the replayed chain
+    pub diverged_keys: Vec<StringKey>,
+}
+
+// Writes of a range of blocks, giving the values of the chain after each of them, as long
+// as the keys are written again within the range
+struct Window {
+    start: u64,
+    writes: Vec<HashMap<StringKey, Option<Blob>>>,
+}
+
+impl Window {
+    fn load<K: KvStore>(
+        chain: &KvStoreChain<K>,
+        start: u64,
+        end: u64,
+    ) -> Result<Self, BlockchainError> {
+        Ok(Self {
+            start,
+            writes: (start..end)
+                .map(|height| get_writes(chain, height))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn written(&self, height: u64) -> impl Iterator<Item = &StringKey> {
+        self.writes[(height - self.start) as usize].keys()
+    }
+
+    fn value_after(&self, height: u64, key: &StringKey) -> Option<Option<Blob>> {
+        self.writes[(height + 1 - self.start) as usize..]
+            .iter()
+            .find_map(|writes| writes.get(key).cloned())
+    }
+}
+
+// Values of the chain after some height, found by walking the rollback data of the next
+// blocks until all of the keys are written again
+fn values_after<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    height: u64,
+    mut keys: HashSet<StringKey>,
+) -> Result<HashMap<StringKey, Option<Blob>>, BlockchainError> {
+    let mut values = HashMap::new();
+    let mut next = height + 1;
+    let chain_height = chain.get_height()?;
+    while !keys.is_empty() && next < chain_height {
+        let writes = get_writes(chain, next)?;
+        keys.retain(|key| match writes.get(key) {
+            Some(value) => {
+                values.insert(key.clone(), value.clone());
+                false
+            }
+            None => true,
+        });
+        next += 1;
+    }
+    for key in keys {
+        let value = chain.database.get(key.clone())?;
+        values.insert(key, value);
+    }
+    Ok(values)
+}
+
+/// Executes the blocks of the chain again, on a chain kept in memory, comparing the values
+/// written by each block with the ones in the chain. Stops at the first diverging block,
+/// which is returned. Pruned chains can not be replayed, as their old blocks are missing.
+pub fn replay<K: KvStore, F: FnMut(&ReplayedBlock)>(
+    chain: &KvStoreChain<K>,
+    on_block: F,
+) -> Result<Option<ReplayedBlock>, BlockchainError> {
+    replay_in_windows(chain, REPLAY_WINDOW, on_block)
+}
+
+// Blocks are replayed a window at a time, only the rollback data of the window is kept in
+// memory, along with the values of the keys it writes, after the window
+pub(super) fn replay_in_windows<K: KvStore, F: FnMut(&ReplayedBlock)>(
+    chain: &KvStoreChain<K>,
+    window: u64,
+    mut on_block: F,
+) -> Result<Option<ReplayedBlock>, BlockchainError> {
+    if chain.get_pruned_height()? > 0 {
+        return Err(BlockchainError::BlockPruned);
+    }
+    let mut replayed = KvStoreChain::new(RamKvStore::new(), chain.config.clone())?;
+    let mut checksum = <Hasher as Hash>::Output::default();
+    let height = chain.get_height()?;
+    let mut start = 0;
+    while start < height {
+        let end = std::cmp::min(start + window, height);
+        for height in std::cmp::max(start, 1)..end {
+            ops::apply_block_and_get_state_root(&mut replayed, &chain.get_block(height)?, true)?;
+        }
+
+        let replayed_writes = Window::load(&replayed, start, end)?;
+        let writes = Window::load(chain, start, end)?;
+        let mut window_keys = Vec::new();
+        let mut unresolved = HashSet::new();
+        for height in start..end {
+            let mut keys = replayed_writes
+                .written(height)
+                .chain(writes.written(height))
+                .cloned()
+                .collect::<Vec<_>>();
+            keys.sort_by(|a, b| a.0.cmp(&b.0));
+            keys.dedup();
+            for key in keys.iter() {
+                if writes.value_after(height, key).is_none() {
+                    unresolved.insert(key.clone());
+                }
+            }
+            window_keys.push(keys);
+        }
+        let values_after_window = values_after(chain, end - 1, unresolved)?;
+
+        for (height, keys) in (start..end).zip(window_keys) {
+            let mut values = Vec::new();
+            let mut diverged_keys = Vec::new();
+            for key in keys {
+                let value = match replayed_writes.value_after(height, &key) {
+                    Some(value) => value,
+                    None => replayed.database.get(key.clone())?,
+                };
+                let expected = match writes.value_after(height, &key) {
+                    Some(value) => value,
+                    None => values_after_window[&key].clone(),
+                };
+                if expected != value {
+                    diverged_keys.push(key.clone());
+                }
+                values.push((key, value));
+            }
+
+            let mut preimage = checksum.to_vec();
+            preimage.extend(bincode::serialize(&values).unwrap());
+            checksum = Hasher::hash(&preimage);
+            let block = ReplayedBlock {
+                height,
+                header_hash: replayed.get_header(height)?.hash(),
+                checksum,
+                diverged_keys,
+            };
+            on_block(&block);
+            if !block.diverged_keys.is_empty() {
+                return Ok(Some(block));
+            }
+        }
+        start = end;
+    }
+    Ok(None)
+}
@@ -0,0 +1,177 @@
+use super::*;
+use crate::db::{Blob, RamKvStore, StringKey};
+use std::str::FromStr;
+
+const CONTRACT_PREFIX: &str = "CON-";
+const CONTRACT_STATE_PREFIX: &str = "S-";
+const STATE_TREE_PREFIX: &str = "STN-";
+const BLOCK_PREFIXES: [&str; 3] = ["BLK-", "MRK-", "RLK-"];
+const TX_LOCATION_PREFIX: &str = "TXL-";
+const HISTORY_PREFIX: &str = "HIS-";
+// Validator proofs of past epochs are only served to light clients
+const LIGHT_PREFIXES: [&str; 2] = ["VPF-", "LVL-"];
+
+fn is_tree(key: &StringKey) -> bool {
+    key.0.starts_with(CONTRACT_STATE_PREFIX) || key.0.starts_with(STATE_TREE_PREFIX)
+}
+
+// Whether a pair should be exported, when the blocks before `horizon` are pruned. Like
+// pruned nodes, headers and the genesis block are kept.
+fn is_exported(key: &StringKey, value: &Blob, horizon: u64) -> Result<bool, BlockchainError> {
+    if is_tree(key) || *key == keys::pruned_height() {
+        return Ok(false);
+    }
+    if LIGHT_PREFIXES.iter().any(|p| key.0.starts_with(p)) {
+        return Ok(false);
+    }
+    if let Some(prefix) = BLOCK_PREFIXES.iter().find(|p| key.0.starts_with(*p)) {
+        let index =
+            u64::from_str(&key.0[prefix.len()..]).map_err(|_| BlockchainError::Inconsistency)?;
+        return Ok(index >= horizon || *key == keys::block(0));
+    }
+    if key.0.starts_with(TX_LOCATION_PREFIX) {
+        let loc: TxLocation = value.clone().try_into()?;
+        return Ok(loc.block >= horizon);
+    }
+    if key.0.starts_with(HISTORY_PREFIX) {
+        let his = keys::AddressHistoryDbKey::try_from(key.clone())?;
+        return Ok(his.block >= horizon);
+    }
+    Ok(true)
+}
+
+/// State of the chain at some height, for bootstrapping new nodes without replaying the
+/// blocks. Trees are not included and are rebuilt on import: contract states are kept as
+/// their data and the account states as plain key-values. Only the last few blocks are
+/// kept, the node starts as if it was pruned.
+///
+/// The state root of the header only commits to the account state. Contracts, reward
+/// pools, vestings, HTLCs, proposals and the rest of the data are authenticated by the
+/// checksum, which should come from the same trusted source as the header hash.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    pub header: Header,
+    pub pairs: Vec<(StringKey, Blob)>,
+    pub states: HashMap<ContractId, zk::ZkState>,
+}
+
+impl Snapshot {
+    /// Takes a snapshot of the chain, rolled back to the given height, keeping the bodies
+    /// and rollback data of its last `retention` blocks. Contract states are only available
+    /// for the last few blocks, so older heights may not be exported.
+    pub fn export<K: KvStore>(
+        chain: &KvStoreChain<K>,
+        height: u64,
+        retention: u64,
+    ) -> Result<Self, BlockchainError> {
+        if height == 0 || height > chain.get_height()? {
+            return Err(BlockchainError::BlockNotFound);
+        }
+        let mut fork = chain.fork_on_ram();
+        while fork.get_height()? > height {
+            fork.rollback()?;
+        }
+        if !fork.get_outdated_contracts()?.is_empty() {
+            return Err(BlockchainError::StatesUnavailable);
+        }
+
+        let mut states = HashMap::new();
+        for (k, _) in fork.database.pairs(CONTRACT_PREFIX.into())?.into_iter() {
+            let cid = ContractId::from_str(&k.0[CONTRACT_PREFIX.len()..])
+                .map_err(|_| BlockchainError::Inconsistency)?;
+            states.insert(
+                cid,
+                zk::KvStoreStateManager::<CoreZkHasher>::get_full_state(&fork.database, cid)?,
+            );
+        }
+        let horizon = std::cmp::max(height.saturating_sub(retention), fork.get_pruned_height()?);
+        let mut pairs = Vec::new();
+        for (k, v) in fork.database.pairs("".into())?.into_iter() {
+            if is_exported(&k, &v, horizon)? {
+                pairs.push((k, v));
+            }
+        }
+        if horizon > 0 {
+            pairs.push((keys::pruned_height(), horizon.into()));
+        }
+
+        Ok(Self {
+            header: fork.get_tip()?,
+            pairs,
+            states,
+        })
+    }
+
+    /// Checksum of the database the snapshot is imported into, as reported by
+    /// `db_checksum`. Published by the exporter along with the header hash.
+    pub fn checksum(&self, config: BlockchainConfig) -> Result<String, BlockchainError> {
+        let (_, checksum) = self.clone().rebuild(&RamKvStore::new(), config)?;
+        Ok(checksum)
+    }
+
+    // Rebuilds the trees on top of the given database, returning the writes and the
+    // checksum of the resulting database.
+    fn rebuild<K: KvStore>(
+        self,
+        database: &K,
+        config: BlockchainConfig,
+    ) -> Result<(Vec<WriteOp>, String), BlockchainError> {
+        // Trees are always rebuilt, so that they commit to the imported data
+        let mut ops = self
+            .pairs
+            .into_iter()
+            .filter(|(k, _)| !is_tree(k))
+            .map(|(k, v)| WriteOp::Put(k, v))
+            .collect::<Vec<_>>();
+        ops.push(WriteOp::Put(
+            keys::outdated(),
+            self.states.keys().cloned().collect::<Vec<_>>().into(),
+        ));
+        let mut mirror = database.mirror();
+        mirror.update(&ops)?;
+        let mut fork = KvStoreChain::new(mirror, config)?;
+        fork.update_state_root(&ops)?;
+        fork.update_states(&ZkBlockchainPatch {
+            patches: self
+                .states
+                .into_iter()
+                .map(|(cid, state)| (cid, zk::ZkStatePatch::Full(state)))
+                .collect(),
+        })?;
+
+        let tip = fork.get_tip()?;
+        if tip != self.header
+            || (fork.is_active(Feature::StateRoot, tip.number)
+                && fork.get_state_root()? != tip.state_root)
+        {
+            return Err(BlockchainError::UntrustedSnapshot);
+        }
+        let checksum = fork.db_checksum()?;
+        Ok((fork.database.to_ops(), checksum))
+    }
+
+    /// Imports the snapshot into an empty database, given the hash of a header that is
+    /// trusted to be in the chain and the checksum of the snapshot, from the same trusted
+    /// source. The node can continue syncing from that header. Nothing is written to the
+    /// database when the snapshot is invalid.
+    pub fn import<K: KvStore>(
+        self,
+        mut database: K,
+        config: BlockchainConfig,
+        trusted_hash: <Hasher as Hash>::Output,
+        trusted_checksum: &str,
+    ) -> Result<KvStoreChain<K>, BlockchainError> {
+        if self.header.hash() != trusted_hash {
+            return Err(BlockchainError::UntrustedSnapshot);
+        }
+        if database.pairs("".into())?.into_iter().next().is_some() {
+            return Err(BlockchainError::SnapshotDatabaseNotEmpty);
+        }
+        let (ops, checksum) = self.rebuild(&database, config.clone())?;
+        if checksum != trusted_checksum {
+            return Err(BlockchainError::InvalidSnapshotChecksum);
+        }
+        database.update(&ops)?;
+        KvStoreChain::new(database, config)
+    }
+}
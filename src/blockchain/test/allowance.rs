@@ -0,0 +1,109 @@
+use super::*;
+
+#[test]
+fn test_spend_from_allowance() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let spender = TxBuilder::new(&Vec::from("SPENDER"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let entries = |amount: u64| {
+        vec![RegularSendEntry {
+            dst: bob.get_address(),
+            amount: Money::ziesha(amount),
+        }]
+    };
+
+    // Nothing can be spent before an approval
+    let unapproved = spender.spend_from(
+        "".into(),
+        abc.get_address(),
+        entries(10),
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&unapproved.tx, false),
+        Err(BlockchainError::AllowanceInsufficient)
+    ));
+
+    let approve = abc.approve(
+        "".into(),
+        spender.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[approve], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_allowance(abc.get_address(), spender.get_address(), TokenId::Ziesha)?,
+        Amount(100)
+    );
+
+    let spend = spender.spend_from(
+        "".into(),
+        abc.get_address(),
+        entries(60),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(10, &[spend], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(abc.get_address(), TokenId::Ziesha)?,
+        Amount(9940)
+    );
+    assert_eq!(
+        chain.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(60)
+    );
+    assert_eq!(
+        chain.get_allowance(abc.get_address(), spender.get_address(), TokenId::Ziesha)?,
+        Amount(40)
+    );
+
+    let overspend = spender.spend_from(
+        "".into(),
+        abc.get_address(),
+        entries(41),
+        Money::ziesha(0),
+        2,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&overspend.tx, false),
+        Err(BlockchainError::AllowanceInsufficient)
+    ));
+
+    // Approving zero revokes the allowance
+    let revoke = abc.approve(
+        "".into(),
+        spender.get_address(),
+        Money::ziesha(0),
+        Money::ziesha(0),
+        2,
+    );
+    let draft = chain
+        .draft_block(15, &[revoke], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_allowance(abc.get_address(), spender.get_address(), TokenId::Ziesha)?,
+        Amount(0)
+    );
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use super::*;
+
+#[test]
+fn test_base_fee_is_burned() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.fork_schedule.activate(Feature::BaseFee, 0);
+    conf.min_base_fee = Amount(2);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    assert_eq!(chain.get_base_fee()?, Amount(2));
+
+    let free = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&free.tx, false),
+        Err(BlockchainError::FeeBelowBaseFee)
+    ));
+    let draft = chain
+        .draft_block(5, &[free.clone()], &validator, &Default::default(), true)?
+        .unwrap();
+    assert!(draft.block.body.is_empty());
+
+    let base_fee = Amount(2 * free.tx.size() as u64);
+    let paying = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(u64::from(base_fee) + 50),
+        1,
+    );
+    let circulation = chain.currency_in_circulation()?;
+    let validator_balance = chain.get_balance(validator.get_address(), TokenId::Ziesha)?;
+    let reward = chain.next_reward()?;
+    let draft = chain
+        .draft_block(5, &[paying], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+
+    // Only the tip is paid to the validator (And its delegators)
+    assert_eq!(chain.currency_in_circulation()?, circulation - base_fee);
+    assert_eq!(chain.get_burned()?, base_fee);
+    let validator_reward =
+        chain.get_balance(validator.get_address(), TokenId::Ziesha)? - validator_balance;
+    let tip_and_reward = reward.0 + 50;
+    assert_eq!(
+        validator_reward,
+        Amount(tip_and_reward - tip_and_reward * 243 / 255)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_base_fee_rises_and_falls() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.fork_schedule.activate(Feature::BaseFee, 0);
+    conf.min_base_fee = Amount(16);
+
+    let send = |nonce| {
+        abc.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::ziesha(100),
+            Money::ziesha(4000),
+            nonce,
+        )
+    };
+    let txs = [send(1), send(2)];
+    let tx_size = txs[0].tx.size();
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    chain.config.max_block_size = 2 * tx_size; // Two transactions fill a block (After genesis)
+    let circulation = chain.currency_in_circulation()?;
+
+    // A full block raises the base fee by 1/8
+    let draft = chain
+        .draft_block(5, &txs, &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 2);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(chain.get_base_fee()?, Amount(18));
+    let burned = Amount(2 * 16 * tx_size as u64);
+    assert_eq!(chain.get_burned()?, burned);
+    assert_eq!(chain.currency_in_circulation()?, circulation - burned);
+
+    // An empty block lowers it by 1/8, down to the minimum
+    let draft = chain
+        .draft_block(10, &[], &validator, &Default::default(), true)?
+        .unwrap();
+    chain.apply_block(&draft.block)?;
+    assert_eq!(chain.get_base_fee()?, Amount(16));
+    let draft = chain
+        .draft_block(15, &[], &validator, &Default::default(), true)?
+        .unwrap();
+    chain.apply_block(&draft.block)?;
+    assert_eq!(chain.get_base_fee()?, Amount(16));
+    assert_eq!(chain.get_burned()?, burned);
+
+    // Base fee and the burned amount are restored on rollback
+    chain.rollback()?;
+    chain.rollback()?;
+    assert_eq!(chain.get_base_fee()?, Amount(18));
+    chain.rollback()?;
+    assert_eq!(chain.get_base_fee()?, Amount(16));
+    assert_eq!(chain.get_burned()?, Amount(0));
+    assert_eq!(chain.currency_in_circulation()?, circulation);
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
+
+#[test]
+fn test_token_fees_do_not_raise_base_fee() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.fork_schedule.activate(Feature::BaseFee, 0);
+    conf.min_base_fee = Amount(16);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let (create_token, token_id) = abc.create_token(
+        "".into(),
+        "Stable".into(),
+        "STB".into(),
+        Amount(100000),
+        0,
+        None,
+        None,
+        Money::ziesha(8000),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[create_token], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(chain.get_base_fee()?, Amount(16));
+
+    let send = |nonce| {
+        abc.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::ziesha(100),
+            Money::new(token_id, 4000),
+            nonce,
+        )
+    };
+    let txs = [send(2), send(3)];
+    chain.config.max_block_size = 2 * txs[0].tx.size(); // Two transactions fill a block
+    let fee_tokens = HashMap::from([(
+        token_id,
+        ExchangeRate {
+            ziesha: Amount(1),
+            token: Amount(1),
+        },
+    )]);
+    let burned = chain.get_burned()?;
+
+    // A block full of txs paying their fees in other tokens burns nothing, and keeps the
+    // base fee as if it was empty
+    let draft = chain
+        .draft_block(10, &txs, &validator, &fee_tokens, true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 2);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(chain.get_base_fee()?, Amount(16));
+    assert_eq!(chain.get_burned()?, burned);
+
+    // Burned base fee of the token creation is restored on rollback
+    chain.rollback()?;
+    chain.rollback()?;
+    assert_eq!(chain.get_burned()?, Amount(0));
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
@@ -37,7 +37,7 @@ fn test_contract_create_patch() {
     );
 
     let draft = chain
-        .draft_block(1, &[tx.clone()], &miner, true)
+        .draft_block(1, &[tx.clone()], &miner, &Default::default(), true)
         .unwrap()
         .unwrap();
     chain.apply_block(&draft.block).unwrap();
@@ -90,7 +90,7 @@ fn test_mpn_contract_patching() {
         1,
     );
     let draft = chain
-        .draft_block(1, &[init_tx.clone()], &miner, false)
+        .draft_block(1, &[init_tx.clone()], &miner, &Default::default(), false)
         .unwrap()
         .unwrap();
     chain.apply_block(&draft.block).unwrap();
@@ -136,7 +136,7 @@ fn test_mpn_contract_patching() {
         2,
     );
     let draft = chain
-        .draft_block(2, &[tx.clone()], &miner, false)
+        .draft_block(2, &[tx.clone()], &miner, &Default::default(), false)
         .unwrap()
         .unwrap();
     chain.apply_block(&draft.block).unwrap();
@@ -0,0 +1,156 @@
+use super::*;
+
+#[test]
+fn test_redelegate() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let too_much = delegator.redelegate(
+        "".into(),
+        validator.get_address(),
+        validator2.get_address(),
+        Amount(26),
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&too_much.tx, false),
+        Err(BlockchainError::BalanceInsufficient)
+    ));
+    let to_self = delegator.redelegate(
+        "".into(),
+        validator.get_address(),
+        validator.get_address(),
+        Amount(10),
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&to_self.tx, false),
+        Err(BlockchainError::SelfRedelegation)
+    ));
+
+    let tx = delegator.redelegate(
+        "".into(),
+        validator.get_address(),
+        validator2.get_address(),
+        Amount(10),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(1, &[tx], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block).unwrap();
+
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        Amount(15)
+    );
+    assert_eq!(
+        chain.get_stake(validator2.get_address()).unwrap(),
+        Amount(35)
+    );
+    assert_eq!(
+        chain
+            .get_delegate(delegator.get_address(), validator.get_address())
+            .unwrap()
+            .amount,
+        Amount(15)
+    );
+    assert_eq!(
+        chain
+            .get_delegate(delegator.get_address(), validator2.get_address())
+            .unwrap()
+            .amount,
+        Amount(35)
+    );
+    assert_eq!(
+        chain
+            .get_delegators(validator2.get_address(), None)
+            .unwrap(),
+        vec![(delegator.get_address(), Amount(35))]
+    );
+    assert_eq!(
+        chain
+            .get_delegatees(delegator.get_address(), None)
+            .unwrap()
+            .first(),
+        Some(&(validator2.get_address(), Amount(35)))
+    );
+    assert_eq!(
+        chain.get_stakers().unwrap().first(),
+        Some(&(validator2.get_address(), Amount(35)))
+    );
+    // Redelegation does not create an undelegation
+    assert!(chain
+        .get_undelegations(delegator.get_address(), None)
+        .unwrap()
+        .is_empty());
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_unbonding_period() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.unbonding_period = 3;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let tx = delegator.undelegate(
+        "".into(),
+        validator.get_address(),
+        Amount(5),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(1, &[tx], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    let undelegations = chain
+        .get_undelegations(delegator.get_address(), None)
+        .unwrap();
+    assert_eq!(undelegations.len(), 1);
+    assert_eq!(undelegations[0].1.amount, Amount(5));
+    assert_eq!(undelegations[0].1.unlocks_on, 4);
+
+    // Funds are released once the chain reaches the unlock height
+    let draft = chain
+        .draft_block(2, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(
+        chain
+            .get_undelegations(delegator.get_address(), None)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let draft = chain
+        .draft_block(3, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(chain.get_height().unwrap(), 4);
+    assert!(chain
+        .get_undelegations(delegator.get_address(), None)
+        .unwrap()
+        .is_empty());
+
+    rollback_till_empty(&mut chain).unwrap();
+}
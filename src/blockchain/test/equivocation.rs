@@ -0,0 +1,246 @@
+use super::*;
+
+#[test]
+fn test_equivocation_is_slashed() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let reporter = TxBuilder::new(&Vec::from("ABC"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    // Two different headers, signed by the same validator, for the same slot
+    let first = chain
+        .draft_block(0, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    let second = chain
+        .draft_block(1, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    assert_eq!(
+        chain.epoch_slot(first.proof_of_stake.timestamp),
+        chain.epoch_slot(second.proof_of_stake.timestamp)
+    );
+
+    let stake_before = chain.get_stake(validator.get_address()).unwrap();
+    let delegate_before = chain
+        .get_delegate(delegator.get_address(), validator.get_address())
+        .unwrap()
+        .amount;
+    assert_eq!(stake_before, Amount(25));
+    assert_eq!(delegate_before, Amount(25));
+
+    let mut forged = second.clone();
+    forged.number += 1;
+    let invalid_evidences = [
+        (first.clone(), first.clone()),
+        (first.clone(), forged),
+        (
+            first.clone(),
+            chain
+                .draft_block(5, &[], &validator, &Default::default(), true)
+                .unwrap()
+                .unwrap()
+                .block
+                .header, // Different slot
+        ),
+    ];
+    for (a, b) in invalid_evidences {
+        let tx = reporter.report_equivocation("".into(), a, b, Money::ziesha(0), 1);
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&tx.tx, false),
+            Err(BlockchainError::InvalidEquivocationEvidence)
+        ));
+    }
+
+    let report = reporter.report_equivocation(
+        "".into(),
+        first.clone(),
+        second.clone(),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(2, &[report], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block).unwrap();
+
+    // 25 * 26 / 255 ~= 2
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        Amount(23)
+    );
+    assert_eq!(
+        chain
+            .get_delegate(delegator.get_address(), validator.get_address())
+            .unwrap()
+            .amount,
+        Amount(23)
+    );
+    // Slashed validator now has the least stake
+    assert_eq!(
+        chain.get_stakers().unwrap().last(),
+        Some(&(validator.get_address(), Amount(23)))
+    );
+    assert_eq!(
+        chain.get_delegators(validator.get_address(), None).unwrap(),
+        vec![(delegator.get_address(), Amount(23))]
+    );
+
+    let duplicate = reporter.report_equivocation("".into(), second, first, Money::ziesha(0), 2);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&duplicate.tx, false),
+        Err(BlockchainError::EquivocationAlreadyReported)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_unsigned_header_is_rejected() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let mut blk = chain
+        .draft_block(0, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap()
+        .block;
+    blk.header.sig = Signature::Unsigned;
+    assert!(matches!(
+        chain.fork_on_ram().apply_block(&blk),
+        Err(BlockchainError::InvalidHeaderSignature)
+    ));
+
+    let other = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    other.sign_header(&mut blk.header);
+    assert!(matches!(
+        chain.fork_on_ram().apply_block(&blk),
+        Err(BlockchainError::InvalidHeaderSignature)
+    ));
+
+    validator.sign_header(&mut blk.header);
+    chain.apply_block(&blk).unwrap();
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_unbonding_stake_is_slashed() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let reporter = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.equivocation_slash_ratio = Ratio(51); // 20%
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    // Stake moved away before the infraction is not slashed
+    let before = delegator.redelegate(
+        "".into(),
+        validator.get_address(),
+        validator2.get_address(),
+        Amount(5),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(2, &[before], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    let first = chain
+        .draft_block(5, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    let second = chain
+        .draft_block(6, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    assert_eq!(first.number, 2);
+
+    // Delegator tries to dodge the slash, after the infraction
+    let undelegate = delegator.undelegate(
+        "".into(),
+        validator.get_address(),
+        Amount(10),
+        Money::ziesha(0),
+        2,
+    );
+    let undelegation_id = UndelegationId::new(&undelegate.tx);
+    let after = delegator.redelegate(
+        "".into(),
+        validator.get_address(),
+        validator2.get_address(),
+        Amount(5),
+        Money::ziesha(0),
+        3,
+    );
+    let draft = chain
+        .draft_block(
+            7,
+            &[undelegate, after],
+            &validator,
+            &Default::default(),
+            true,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 2);
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(chain.get_stake(validator.get_address()).unwrap(), Amount(5));
+    assert_eq!(
+        chain.get_stake(validator2.get_address()).unwrap(),
+        Amount(35)
+    );
+
+    let report = reporter.report_equivocation("".into(), first, second, Money::ziesha(0), 1);
+    let draft = chain
+        .draft_block(8, &[report], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block).unwrap();
+
+    // 20% of the remaining delegation, the undelegation and the second redelegation
+    assert_eq!(chain.get_stake(validator.get_address()).unwrap(), Amount(4));
+    assert_eq!(
+        chain
+            .get_undelegation(delegator.get_address(), undelegation_id)
+            .unwrap()
+            .unwrap()
+            .amount,
+        Amount(8)
+    );
+    assert_eq!(
+        chain
+            .get_delegate(delegator.get_address(), validator2.get_address())
+            .unwrap()
+            .amount,
+        Amount(34)
+    );
+    assert_eq!(
+        chain.get_stake(validator2.get_address()).unwrap(),
+        Amount(34)
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+}
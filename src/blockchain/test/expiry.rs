@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn test_expired_transactions_are_rejected() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC")).with_valid_until(Some(2));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let mut mempool = Mempool::new(Amount(1));
+
+    let tx = abc.create_transaction(
+        "".into(),
+        validator.get_address(),
+        Money::ziesha(1),
+        Money::ziesha(0),
+        1,
+    );
+    assert_eq!(tx.tx.valid_until, Some(2));
+    mempool.add_tx(&chain, tx.clone().into(), false, 0).unwrap();
+    assert_eq!(mempool.all().count(), 1);
+
+    for ts in [5, 10] {
+        chain.fork_on_ram().apply_tx(&tx.tx, false).unwrap();
+        let draft = chain
+            .draft_block(ts, &[], &validator, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+        mempool.refresh(&chain, ts, None, None).unwrap();
+    }
+
+    // Height 3 is beyond the expiry of the transaction
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&tx.tx, false),
+        Err(BlockchainError::TransactionExpired)
+    ));
+    let draft = chain
+        .draft_block(15, &[tx.clone()], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert!(draft.block.body.is_empty());
+    assert_eq!(mempool.all().count(), 0);
+    mempool.add_tx(&chain, tx.clone().into(), true, 15).unwrap();
+    assert_eq!(mempool.all().count(), 0);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+#[test]
+fn test_fees_paid_in_custom_tokens() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let (create_token, token_id) = abc.create_token(
+        "".into(),
+        "Stable".into(),
+        "STB".into(),
+        Amount(10000),
+        0,
+        None,
+        None,
+        Money::ziesha(0),
+        1,
+    );
+
+    // Fee tokens must exist
+    let unknown_fee = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::new(token_id, 255),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&unknown_fee.tx, false),
+        Err(BlockchainError::TokenNotFound)
+    ));
+
+    let draft = chain
+        .draft_block(5, &[create_token], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+
+    let send = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::new(token_id, 255),
+        2,
+    );
+
+    // Validators only pick txs paying their fees in the tokens they accept
+    let draft = chain
+        .draft_block(10, &[send.clone()], &validator, &Default::default(), true)?
+        .unwrap();
+    assert!(draft.block.body.is_empty());
+
+    let fee_tokens = HashMap::from([(
+        token_id,
+        ExchangeRate {
+            ziesha: Amount(1),
+            token: Amount(1),
+        },
+    )]);
+    let draft = chain
+        .draft_block(10, &[send], &validator, &fee_tokens, true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(abc.get_address(), token_id)?,
+        Amount(9745)
+    );
+    assert_eq!(
+        chain.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(100)
+    );
+    assert_eq!(chain.get_balance(Default::default(), token_id)?, Amount(0));
+
+    // Validator receives its commission (12/255), the rest waits for the delegators
+    assert_eq!(
+        chain.get_balance(validator.get_address(), token_id)?,
+        Amount(12)
+    );
+    let pools = chain.get_token_reward_pools(validator.get_address())?;
+    assert_eq!(pools.len(), 1);
+    assert_eq!(pools[0].0, token_id);
+    assert_eq!(pools[0].1.balance, Amount(243));
+
+    let stake = chain.get_delegate(delegator.get_address(), validator.get_address())?;
+    let total_stake = chain.get_stake(validator.get_address())?;
+    let expected = Amount(243 * stake.amount.0 / total_stake.0);
+    let claim = delegator.claim_reward(
+        "".into(),
+        validator.get_address(),
+        Money::ziesha(0),
+        chain.get_nonce(delegator.get_address())? + 1,
+    );
+    let draft = chain
+        .draft_block(15, &[claim], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    let claimed = chain.get_balance(delegator.get_address(), token_id)?;
+    assert!(claimed <= expected && expected - claimed <= Amount(1));
+    let pools = chain.get_token_reward_pools(validator.get_address())?;
+    assert_eq!(pools[0].1.balance, Amount(243) - claimed);
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use super::*;
+
+#[test]
+fn test_fork_schedule() {
+    let mut schedule = ForkSchedule::new(&[(Feature::Governance, 3)]);
+    assert!(!schedule.is_active(Feature::Governance, 2));
+    assert!(schedule.is_active(Feature::Governance, 3));
+    assert!(schedule.is_active(Feature::Governance, 4));
+    assert!(!schedule.is_active(Feature::Jailing, 1000));
+    schedule.activate(Feature::Jailing, 0);
+    assert!(schedule.is_active(Feature::Jailing, 0));
+}
+
+#[test]
+fn test_feature_activates_in_the_middle_of_chain() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.fork_schedule.activate(Feature::Governance, 3);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let proposal = abc.propose(
+        "".into(),
+        vec![ConfigParam::MaxMemoLength(100)],
+        1000,
+        Money::ziesha(0),
+        1,
+    );
+    for ts in [5, 10] {
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&proposal.tx, false),
+            Err(BlockchainError::FeatureNotActive)
+        ));
+        for check in [true, false] {
+            let draft = chain
+                .draft_block(
+                    ts,
+                    &[proposal.clone()],
+                    &validator,
+                    &Default::default(),
+                    check,
+                )
+                .unwrap()
+                .unwrap();
+            assert!(draft.block.body.is_empty());
+        }
+        let draft = chain
+            .draft_block(
+                ts,
+                &[proposal.clone()],
+                &validator,
+                &Default::default(),
+                true,
+            )
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+
+    assert_eq!(chain.get_height().unwrap(), 3);
+    let draft = chain
+        .draft_block(
+            15,
+            &[proposal.clone()],
+            &validator,
+            &Default::default(),
+            true,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block).unwrap();
+    let proposal_id = ProposalId::new(&proposal.tx);
+    assert!(chain.get_proposal(proposal_id).unwrap().is_some());
+
+    // Rolling back below the activation height disables the feature again
+    chain.rollback().unwrap();
+    assert!(chain.get_proposal(proposal_id).unwrap().is_none());
+    chain.fork_on_ram().apply_tx(&proposal.tx, false).unwrap();
+    chain.rollback().unwrap();
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&proposal.tx, false),
+        Err(BlockchainError::FeatureNotActive)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_jailing_activates_in_the_middle_of_chain() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_missed_slots = 0;
+    conf.slot_per_epoch = 60;
+    conf.fork_schedule.activate(Feature::Jailing, 5);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    // First epoch is evaluated by block #4, before the activation
+    for ts in [5, 10, 15, 300] {
+        let unjail = validator2.unjail("".into(), Money::ziesha(0), 1);
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&unjail.tx, false),
+            Err(BlockchainError::FeatureNotActive)
+        ));
+        let draft = chain
+            .draft_block(ts, &[], &validator, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+    assert_eq!(
+        chain.get_liveness(validator2.get_address()).unwrap(),
+        Default::default()
+    );
+
+    // Second epoch, where the first validator produces every slot, is evaluated at slot 120
+    for ts in (61..=120).map(|slot| slot * 5) {
+        let draft = chain
+            .draft_block(ts, &[], &validator, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+    assert!(chain.get_liveness(validator2.get_address()).unwrap().jailed);
+    assert_eq!(chain.get_stakers().unwrap().len(), 1);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
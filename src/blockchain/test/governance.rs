@@ -0,0 +1,280 @@
+use super::*;
+
+fn vote(
+    chain: &KvStoreChain<db::RamKvStore>,
+    voter: &TxBuilder,
+    proposal_id: ProposalId,
+    approve: bool,
+) -> TransactionAndDelta {
+    let nonce = chain.get_nonce(voter.get_address()).unwrap() + 1;
+    voter.vote("".into(), proposal_id, approve, Money::ziesha(0), nonce)
+}
+
+#[test]
+fn test_accepted_proposal_overrides_config() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.proposal_voting_period = 2;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let invalid_proposals = [
+        abc.propose("".into(), vec![], 10, Money::ziesha(0), 1),
+        abc.propose(
+            "".into(),
+            vec![ConfigParam::MaxMemoLength(100)],
+            3, // Activates before the voting ends
+            Money::ziesha(0),
+            1,
+        ),
+        abc.propose(
+            "".into(),
+            vec![ConfigParam::RewardRatio(0)], // Division by zero
+            10,
+            Money::ziesha(0),
+            1,
+        ),
+        abc.propose(
+            "".into(),
+            vec![
+                ConfigParam::MaxMemoLength(100),
+                ConfigParam::MaxBlockSize(0),
+            ],
+            10,
+            Money::ziesha(0),
+            1,
+        ),
+        abc.propose(
+            "".into(),
+            vec![ConfigParam::MaxDeltaCount(usize::MAX)],
+            10,
+            Money::ziesha(0),
+            1,
+        ),
+    ];
+    for tx in invalid_proposals {
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&tx.tx, false),
+            Err(BlockchainError::InvalidProposal)
+        ));
+    }
+
+    let proposal = abc.propose(
+        "".into(),
+        vec![ConfigParam::MaxMemoLength(100)],
+        10,
+        Money::ziesha(0),
+        1,
+    );
+    let proposal_id = ProposalId::new(&proposal.tx);
+    let draft = chain
+        .draft_block(5, &[proposal], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(
+        chain
+            .get_proposal(proposal_id)
+            .unwrap()
+            .unwrap()
+            .voting_ends,
+        3
+    );
+
+    let no_stake = vote(&chain, &abc, proposal_id, true);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&no_stake.tx, false),
+        Err(BlockchainError::VoterHasNoStake)
+    ));
+    let not_found = vote(&chain, &validator, ProposalId::default(), true);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&not_found.tx, false),
+        Err(BlockchainError::ProposalNotFound)
+    ));
+
+    let votes = [
+        vote(&chain, &validator, proposal_id, true),
+        vote(&chain, &validator2, proposal_id, true),
+        vote(&chain, &validator3, proposal_id, true),
+    ];
+    let draft = chain
+        .draft_block(10, &votes, &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 3);
+    chain.apply_block(&draft.block).unwrap();
+
+    // Tallied on the next epoch boundary
+    assert!(chain.get_proposal(proposal_id).unwrap().is_some());
+    let draft = chain
+        .draft_block(50, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert!(chain.get_proposal(proposal_id).unwrap().is_none());
+
+    let long_memo = "x".repeat(80);
+    while chain.get_height().unwrap() < 10 {
+        assert_eq!(chain.params().unwrap().max_memo_length, 64);
+        let tx = abc.create_transaction(
+            long_memo.clone(),
+            validator.get_address(),
+            Money::ziesha(1),
+            Money::ziesha(0),
+            chain.get_nonce(abc.get_address()).unwrap() + 1,
+        );
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&tx.tx, false),
+            Err(BlockchainError::MemoTooLong)
+        ));
+        let draft = chain
+            .draft_block(
+                50 + chain.get_height().unwrap() as u32,
+                &[],
+                &validator,
+                &Default::default(),
+                true,
+            )
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+    assert_eq!(chain.params().unwrap().max_memo_length, 100);
+    let tx = abc.create_transaction(
+        long_memo,
+        validator.get_address(),
+        Money::ziesha(1),
+        Money::ziesha(0),
+        chain.get_nonce(abc.get_address()).unwrap() + 1,
+    );
+    chain.fork_on_ram().apply_tx(&tx.tx, false).unwrap();
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_rejected_proposal_is_dropped() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.proposal_voting_period = 2;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let proposal = abc.propose(
+        "".into(),
+        vec![ConfigParam::MaxBlockSize(2048)],
+        10,
+        Money::ziesha(0),
+        1,
+    );
+    let proposal_id = ProposalId::new(&proposal.tx);
+    let draft = chain
+        .draft_block(5, &[proposal], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    // Exactly 2/3 of the stake approves, which is not enough
+    let votes = [
+        vote(&chain, &validator, proposal_id, true),
+        vote(&chain, &validator2, proposal_id, true),
+        vote(&chain, &validator3, proposal_id, false),
+    ];
+    let draft = chain
+        .draft_block(10, &votes, &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    let draft = chain
+        .draft_block(50, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    assert!(chain.get_proposal(proposal_id).unwrap().is_none());
+    assert!(chain
+        .database
+        .pairs(db::keys::ConfigOverrideDbKey::prefix().into())
+        .unwrap()
+        .into_iter()
+        .next()
+        .is_none());
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_jailed_votes_are_not_counted() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.proposal_voting_period = 2;
+    conf.max_missed_slots = 0;
+    conf.slot_per_epoch = 60;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let proposal = abc.propose(
+        "".into(),
+        vec![ConfigParam::MaxMemoLength(100)],
+        100,
+        Money::ziesha(0),
+        1,
+    );
+    let proposal_id = ProposalId::new(&proposal.tx);
+    let votes = vec![
+        vote(&chain, &validator2, proposal_id, true),
+        vote(&chain, &validator3, proposal_id, true),
+    ];
+
+    // The voters go offline and are jailed when the epoch is evaluated, right before
+    // the tally
+    for slot in 1..=60 {
+        let txs = match slot {
+            1 => vec![proposal.clone()],
+            2 => votes.clone(),
+            _ => vec![],
+        };
+        let draft = chain
+            .draft_block(slot * 5, &txs, &validator, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(draft.block.body.len(), txs.len());
+        chain.apply_block(&draft.block).unwrap();
+    }
+    assert!(chain.get_liveness(validator2.get_address()).unwrap().jailed);
+    assert!(chain.get_liveness(validator3.get_address()).unwrap().jailed);
+
+    assert!(chain.get_proposal(proposal_id).unwrap().is_none());
+    assert!(chain
+        .database
+        .pairs(db::keys::ConfigOverrideDbKey::prefix().into())
+        .unwrap()
+        .into_iter()
+        .next()
+        .is_none());
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_config_param_from_str() {
+    assert_eq!(
+        "max_block_size=1000".parse::<ConfigParam>().unwrap(),
+        ConfigParam::MaxBlockSize(1000)
+    );
+    let commission: ConfigParam = "max_validator_commission=0.2".parse().unwrap();
+    assert_eq!(commission, ConfigParam::MaxValidatorCommission(Ratio(51)));
+    assert_eq!(
+        commission.to_string().parse::<ConfigParam>().unwrap(),
+        commission
+    );
+    assert!("max_block_size".parse::<ConfigParam>().is_err());
+    assert!("unknown=1".parse::<ConfigParam>().is_err());
+    assert!("max_validator_commission=2".parse::<ConfigParam>().is_err());
+}
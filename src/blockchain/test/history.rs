@@ -0,0 +1,73 @@
+use super::*;
+
+#[test]
+fn test_address_history() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let abc_genesis_history = chain.get_history(abc.get_address(), 0, 100)?;
+    assert!(!abc_genesis_history.is_empty());
+    assert!(chain.get_history(bob.get_address(), 0, 100)?.is_empty());
+
+    let first = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+    let second = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(200),
+        Money::ziesha(0),
+        2,
+    );
+    let draft = chain
+        .draft_block(5, &[first.clone()], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    let draft = chain
+        .draft_block(10, &[second.clone()], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+
+    // Both the sender and the receiver see the txs, newest first
+    let bob_history = chain.get_history(bob.get_address(), 0, 100)?;
+    assert_eq!(
+        bob_history,
+        vec![
+            TxLocation { block: 2, index: 0 },
+            TxLocation { block: 1, index: 0 }
+        ]
+    );
+    let abc_history = chain.get_history(abc.get_address(), 0, 100)?;
+    assert_eq!(abc_history.len(), abc_genesis_history.len() + 2);
+    assert_eq!(abc_history[..2], bob_history[..]);
+
+    // Paginated
+    assert_eq!(
+        chain.get_history(bob.get_address(), 1, 1)?,
+        vec![TxLocation { block: 1, index: 0 }]
+    );
+    assert!(chain.get_history(bob.get_address(), 2, 1)?.is_empty());
+    assert!(chain
+        .get_history(bob.get_address(), usize::MAX, 2)?
+        .is_empty());
+
+    chain.rollback()?;
+    assert_eq!(
+        chain.get_history(bob.get_address(), 0, 100)?,
+        vec![TxLocation { block: 1, index: 0 }]
+    );
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
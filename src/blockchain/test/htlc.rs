@@ -0,0 +1,109 @@
+use super::*;
+
+#[test]
+fn test_htlc_claim_and_refund() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let secret = Vec::from("SECRET");
+    let hashlock = Hasher::hash(&secret);
+    let (claimable, claimable_id) = abc.create_htlc(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        hashlock,
+        3,
+        Money::ziesha(0),
+        1,
+    );
+    let (refundable, refundable_id) = abc.create_htlc(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(200),
+        hashlock,
+        3,
+        Money::ziesha(0),
+        2,
+    );
+    let draft = chain
+        .draft_block(
+            5,
+            &[claimable, refundable],
+            &validator,
+            &Default::default(),
+            true,
+        )?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 2);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(abc.get_address(), TokenId::Ziesha)?,
+        Amount(9700)
+    );
+
+    let wrong_claim = bob.claim_htlc(
+        "".into(),
+        claimable_id,
+        Vec::from("WRONG"),
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&wrong_claim.tx, false),
+        Err(BlockchainError::InvalidHtlcPreimage)
+    ));
+    let early_refund = abc.refund_htlc("".into(), refundable_id, Money::ziesha(0), 3);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&early_refund.tx, false),
+        Err(BlockchainError::HtlcNotTimedOut)
+    ));
+
+    let claim = bob.claim_htlc("".into(), claimable_id, secret.clone(), Money::ziesha(0), 1);
+    let draft = chain
+        .draft_block(10, &[claim], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(100)
+    );
+    assert!(chain.get_htlc(claimable_id)?.is_none());
+    // The sender sees the claim in its history
+    assert_eq!(
+        chain.get_history(abc.get_address(), 0, 1)?,
+        vec![TxLocation { block: 2, index: 0 }]
+    );
+
+    // The second HTLC times out before being claimed
+    let late_claim = bob.claim_htlc("".into(), refundable_id, secret, Money::ziesha(0), 2);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&late_claim.tx, false),
+        Err(BlockchainError::HtlcTimedOut)
+    ));
+    let refund = abc.refund_htlc("".into(), refundable_id, Money::ziesha(0), 3);
+    let draft = chain
+        .draft_block(15, &[refund], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(abc.get_address(), TokenId::Ziesha)?,
+        Amount(9900)
+    );
+    assert!(chain.get_htlc(refundable_id)?.is_none());
+    // The recipient sees the refund in its history
+    assert_eq!(
+        chain.get_history(bob.get_address(), 0, 1)?,
+        vec![TxLocation { block: 3, index: 0 }]
+    );
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use super::*;
+use crate::blockchain::light::{HeaderChain, LightChain};
+
+// Syncs the headers like light nodes do, proving the validators of each epoch at its
+// first header
+fn sync_light(
+    light: &mut LightChain<db::RamKvStore>,
+    chain: &KvStoreChain<db::RamKvStore>,
+    headers: &[Header],
+) -> Result<(), BlockchainError> {
+    let mut since = light.get_height()?;
+    loop {
+        let remaining = &headers[(since - 1) as usize..];
+        match light.extend(since, remaining) {
+            Err(BlockchainError::ValidatorsUnknown(epoch)) => {
+                let first = remaining
+                    .iter()
+                    .position(|h| light.epoch(h) == epoch)
+                    .unwrap();
+                light.extend(since, &remaining[..=first])?;
+                let (header, proof) = chain.get_epoch_validators_proof(epoch)?.unwrap();
+                assert_eq!(header, remaining[first]);
+                light.update_validators(&header, &proof)?;
+                since = light.get_height()?;
+            }
+            res => {
+                return res;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_light_chain() -> Result<(), BlockchainError> {
+    let validators = ["VALIDATOR", "VALIDATOR2", "VALIDATOR3"]
+        .iter()
+        .map(|v| TxBuilder::new(&Vec::from(*v)))
+        .collect::<Vec<_>>();
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.check_validator = true;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+    let mut light = LightChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+
+    let send = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+
+    // Blocks of three epochs, produced by whoever is elected in each slot
+    let mut timestamp = 0;
+    while Blockchain::get_height(&chain)? < 25 {
+        timestamp += conf.slot_duration;
+        for validator in validators.iter() {
+            if let Some(draft) = chain.draft_block(
+                timestamp,
+                &[send.clone()],
+                validator,
+                &Default::default(),
+                true,
+            )? {
+                chain.apply_block(&draft.block)?;
+                break;
+            }
+        }
+    }
+    let headers = Blockchain::get_headers(&chain, 1, 24)?;
+    let tip = Blockchain::get_tip(&chain)?;
+
+    // Headers should be chained and signed by the elected validators
+    let mut bad_parent = headers.clone();
+    bad_parent[3].parent_hash = Default::default();
+    validators[0].sign_header(&mut bad_parent[3]);
+    assert!(matches!(
+        light.extend(1, &bad_parent),
+        Err(BlockchainError::InvalidParentHash)
+    ));
+    let mut bad_signature = headers.clone();
+    bad_signature[0].proof_of_stake.timestamp += 1;
+    assert!(matches!(
+        light.extend(1, &bad_signature),
+        Err(BlockchainError::InvalidHeaderSignature)
+    ));
+    let bob_header = |header: &Header| {
+        let mut header = header.clone();
+        header.proof_of_stake.validator = bob.get_address();
+        bob.sign_header(&mut header);
+        header
+    };
+    let mut unelected = headers.clone();
+    unelected[0] = bob_header(&unelected[0]);
+    assert!(matches!(
+        light.extend(1, &unelected),
+        Err(BlockchainError::UnelectedValidator)
+    ));
+
+    // Headers after the first one of an epoch are not accepted before its validators
+    // are proven
+    let second_epoch = headers.iter().position(|h| light.epoch(h) > 0).unwrap();
+    assert!(matches!(
+        light.extend(1, &headers),
+        Err(BlockchainError::ValidatorsUnknown(epoch)) if epoch == light.epoch(&headers[second_epoch])
+    ));
+
+    // Headers in later epochs are checked against the validators of those epochs
+    let last_epoch = light.epoch(&headers[headers.len() - 1]);
+    let last_epoch_second = headers
+        .iter()
+        .position(|h| light.epoch(h) == last_epoch)
+        .unwrap()
+        + 1;
+    let mut late_unelected = headers[..=last_epoch_second].to_vec();
+    late_unelected[last_epoch_second] = bob_header(&late_unelected[last_epoch_second]);
+    assert!(last_epoch > 1);
+    let mut other_light = LightChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+    assert!(matches!(
+        sync_light(&mut other_light, &chain, &late_unelected),
+        Err(BlockchainError::UnelectedValidator)
+    ));
+    // Stops right before the unelected header
+    assert_eq!(other_light.get_tip()?, headers[last_epoch_second - 1]);
+
+    sync_light(&mut light, &chain, &headers)?;
+    assert_eq!(light.get_tip()?, tip);
+    assert!(matches!(
+        light.extend(1, &headers[..10]),
+        Err(BlockchainError::WeakChain)
+    ));
+
+    // Validators of the current epoch are learned through state proofs
+    let mut forged = chain.prove_validators()?;
+    forged.validator_set[0].1 += Amount(1);
+    assert!(matches!(
+        light.update_validators(&tip, &forged),
+        Err(BlockchainError::InvalidStateProof)
+    ));
+    light.update_validators(&tip, &chain.prove_validators()?)?;
+
+    // Wallet queries are answered the same way by both kinds of chains
+    let balance = chain.get_balance(bob.get_address(), TokenId::Ziesha)?;
+    assert_eq!(balance, Amount(100));
+    let balance_proof = chain.prove_balance(bob.get_address(), TokenId::Ziesha)?;
+    let location = chain.get_tx_location(send.tx.hash())?.unwrap();
+    let block = chain.get_block(location.block)?;
+    let tx_proof = chain
+        .get_merkle_tree(location.block)?
+        .prove(location.index as usize);
+    for header_chain in [&chain as &dyn HeaderChain, &light as &dyn HeaderChain] {
+        assert!(header_chain.verify_balance(
+            &bob.get_address(),
+            TokenId::Ziesha,
+            balance,
+            &tip,
+            &balance_proof
+        )?);
+        assert!(!header_chain.verify_balance(
+            &bob.get_address(),
+            TokenId::Ziesha,
+            balance + Amount(1),
+            &tip,
+            &balance_proof
+        )?);
+        assert!(header_chain.verify_transaction(&send.tx, &block.header, &tx_proof)?);
+        assert!(!header_chain.verify_transaction(&send.tx, &tip, &tx_proof)?);
+    }
+
+    // Headers unknown to the light chain are not trusted
+    let mut unknown = tip.clone();
+    unknown.number += 1;
+    assert!(!light.verify_balance(
+        &bob.get_address(),
+        TokenId::Ziesha,
+        balance,
+        &unknown,
+        &balance_proof
+    )?);
+    Ok(())
+}
+
+#[test]
+fn test_light_chain_with_vrf_key_rotation() -> Result<(), BlockchainError> {
+    let validators = ["VALIDATOR", "VALIDATOR2", "VALIDATOR3"]
+        .iter()
+        .map(|v| TxBuilder::new(&Vec::from(*v)))
+        .collect::<Vec<_>>();
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.check_validator = true;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+    let mut light = LightChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+
+    // First validator moves to a VRF key it can't prove with, in the middle of an epoch
+    let mut rotate = validators[0].register_validator(
+        "".into(),
+        Ratio(12),
+        Money::ziesha(0),
+        chain.get_nonce(validators[0].get_address())? + 1,
+    );
+    if let TransactionData::UpdateStaker { vrf_pub_key, .. } = &mut rotate.tx.data {
+        *vrf_pub_key = TxBuilder::new(&Vec::from("ROTATED")).get_vrf_public_key();
+    }
+    validators[0].sign_tx(&mut rotate.tx);
+
+    let mut timestamp = 0;
+    while Blockchain::get_height(&chain)? < 25 {
+        timestamp += conf.slot_duration;
+        let mempool = if Blockchain::get_height(&chain)? >= 2 {
+            vec![rotate.clone()]
+        } else {
+            vec![]
+        };
+        for validator in validators.iter() {
+            match chain.draft_block(timestamp, &mempool, validator, &Default::default(), true) {
+                Ok(Some(draft)) => {
+                    chain.apply_block(&draft.block)?;
+                    break;
+                }
+                Ok(None) | Err(BlockchainError::UnelectedValidator) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    let headers = Blockchain::get_headers(&chain, 1, 24)?;
+    let rotated_at = chain.get_tx_location(rotate.tx.hash())?.unwrap().block;
+    let rotation_epoch = light.epoch(&headers[rotated_at as usize - 1]);
+
+    // The old key is still used until the end of the epoch
+    assert!(headers.iter().any(|h| h.number > rotated_at
+        && light.epoch(h) == rotation_epoch
+        && h.proof_of_stake.validator == validators[0].get_address()));
+    let next_epoch_start = headers
+        .iter()
+        .find(|h| light.epoch(h) > rotation_epoch)
+        .unwrap()
+        .number;
+    assert!(headers.iter().all(|h| h.number <= next_epoch_start
+        || h.proof_of_stake.validator != validators[0].get_address()));
+
+    sync_light(&mut light, &chain, &headers)?;
+    assert_eq!(light.get_tip()?, Blockchain::get_tip(&chain)?);
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+#[test]
+fn test_missing_slots_jails_staker() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_missed_slots = 2;
+    conf.slot_per_epoch = 60;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    // Only the first validator produces blocks in the first epoch
+    for i in 1..60 {
+        let draft = chain
+            .draft_block(i * 5, &[], &validator, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+    assert_eq!(chain.get_stakers().unwrap().len(), 3);
+    assert_eq!(
+        chain.get_liveness(validator2.get_address()).unwrap(),
+        Default::default()
+    );
+
+    // Epoch is evaluated by the first block of the next epoch
+    let draft = chain
+        .draft_block(300, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    // 60 slots, each validator was expected to win 20 +- 8 of them
+    let liveness = chain.get_liveness(validator.get_address()).unwrap();
+    assert_eq!(liveness.total_expected, 20);
+    assert_eq!(liveness.total_missed, 0);
+    assert!(!liveness.jailed);
+    assert_eq!(liveness.uptime(), 1.0);
+    for v in [&validator2, &validator3] {
+        let liveness = chain.get_liveness(v.get_address()).unwrap();
+        assert_eq!(liveness.missed, 12);
+        assert_eq!(liveness.total_expected, 20);
+        assert_eq!(liveness.total_missed, 12);
+        assert!(liveness.jailed);
+        assert!((liveness.uptime() - 0.4).abs() < 1e-6);
+    }
+    assert_eq!(
+        chain.get_stakers().unwrap(),
+        vec![(validator.get_address(), Amount(25))]
+    );
+
+    let not_jailed = validator.unjail("".into(), Money::ziesha(0), 1);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&not_jailed.tx, false),
+        Err(BlockchainError::StakerNotJailed)
+    ));
+
+    let unjail = validator2.unjail("".into(), Money::ziesha(0), 1);
+    let draft = chain
+        .draft_block(305, &[unjail], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block).unwrap();
+
+    let liveness = chain.get_liveness(validator2.get_address()).unwrap();
+    assert!(!liveness.jailed);
+    assert_eq!(liveness.missed, 0);
+    assert_eq!(liveness.total_missed, 12); // History is kept for the uptime
+    assert_eq!(chain.get_stakers().unwrap().len(), 2);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_missed_slots_reset_on_clean_epoch() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_missed_slots = 20;
+    conf.slot_per_epoch = 60;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    // Epoch 0: only the first validator produces, others miss 12 slots each.
+    // Epoch 1: validators take turns.
+    let validators = [&validator, &validator2, &validator3];
+    for slot in 1..=120 {
+        let miner = if slot < 60 {
+            &validator
+        } else {
+            validators[slot as usize % 3]
+        };
+        let draft = chain
+            .draft_block(slot * 5, &[], miner, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+
+    for v in [&validator2, &validator3] {
+        let liveness = chain.get_liveness(v.get_address()).unwrap();
+        assert_eq!(liveness.missed, 0);
+        assert_eq!(liveness.total_expected, 40);
+        assert_eq!(liveness.total_missed, 12);
+        assert!(!liveness.jailed);
+        assert_eq!(liveness.uptime(), 0.7);
+    }
+    assert_eq!(chain.get_stakers().unwrap().len(), 3);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_online_low_stake_validator_is_not_jailed() {
+    let validators =
+        ["VALIDATOR", "VALIDATOR2", "VALIDATOR3"].map(|v| TxBuilder::new(&Vec::from(v)));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.check_validator = true;
+    conf.max_missed_slots = 0;
+    conf.slot_per_epoch = 30;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+
+    // The third validator ends up with ~2% of the stake
+    let delegate = abc.delegate(
+        "".into(),
+        validators[0].get_address(),
+        Amount(1000),
+        Money::ziesha(0),
+        1,
+    );
+    let mut pending = vec![delegate];
+
+    // Every validator is online and produces a block whenever elected, the low-stake one
+    // losing the slots it shares with the others
+    for slot in 1..=90 {
+        for validator in validators.iter() {
+            if let Some(draft) = chain
+                .draft_block(
+                    slot * conf.slot_duration,
+                    &pending,
+                    validator,
+                    &Default::default(),
+                    true,
+                )
+                .unwrap()
+            {
+                chain.apply_block(&draft.block).unwrap();
+                pending.clear();
+                break;
+            }
+        }
+    }
+    assert!(pending.is_empty());
+    assert_eq!(
+        chain.get_stake(validators[0].get_address()).unwrap(),
+        Amount(1025)
+    );
+    for validator in validators.iter() {
+        assert!(!chain.get_liveness(validator.get_address()).unwrap().jailed);
+    }
+    assert_eq!(chain.get_stakers().unwrap().len(), 3);
+}
+
+#[test]
+fn test_sole_validator_is_not_jailed() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_missed_slots = 2;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+
+    // Other validators lose their stake, leaving a single validator in the next epoch
+    let txs = [&validator2, &validator3]
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            delegator.undelegate(
+                "".into(),
+                v.get_address(),
+                Amount(25),
+                Money::ziesha(0),
+                i as u32 + 1,
+            )
+        })
+        .collect::<Vec<_>>();
+    let draft = chain
+        .draft_block(5, &txs, &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 2);
+    chain.apply_block(&draft.block).unwrap();
+    let epoch_duration = conf.slot_duration * conf.slot_per_epoch;
+    let draft = chain
+        .draft_block(epoch_duration, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(
+        chain.get_validator_set().unwrap(),
+        vec![(validator.get_address(), Amount(25))]
+    );
+
+    // The validator goes offline for the rest of the epoch
+    let draft = chain
+        .draft_block(
+            2 * epoch_duration,
+            &[],
+            &validator,
+            &Default::default(),
+            true,
+        )
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    // Jailing it would leave nobody to produce the blocks
+    let liveness = chain.get_liveness(validator.get_address()).unwrap();
+    assert_eq!(liveness.missed, 7);
+    assert!(!liveness.jailed);
+    assert_eq!(
+        chain.get_validator_set().unwrap(),
+        vec![(validator.get_address(), Amount(25))]
+    );
+    let draft = chain
+        .draft_block(
+            3 * epoch_duration,
+            &[],
+            &validator,
+            &Default::default(),
+            true,
+        )
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert!(!chain.get_liveness(validator.get_address()).unwrap().jailed);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
@@ -1,12 +1,35 @@
 use super::*;
 use crate::config::blockchain;
-use crate::core::{Hasher, Signature, Signer, TransactionData};
+use crate::core::{
+    ConfigParam, Hasher, Multisig, ProposalId, RegularSendEntry, Signature, Signer, TokenUpdate,
+    TransactionData,
+};
 use crate::crypto::SignatureScheme;
 use crate::db;
 
+mod allowance;
+mod base_fee;
 mod contract;
+mod delegation;
+mod equivocation;
+mod expiry;
+mod fee_tokens;
+mod fork_schedule;
+mod governance;
+mod history;
+mod htlc;
+mod light;
+mod liveness;
+mod multisig;
+mod prune;
+mod replay;
+mod replay_protection;
 mod rewards;
+mod snapshot;
+mod state_root;
 mod tokens;
+mod validator_set;
+mod vesting;
 mod vrf_randomness;
 
 fn rollback_till_empty<K: KvStore>(b: &mut KvStoreChain<K>) -> Result<(), BlockchainError> {
@@ -38,7 +61,7 @@ fn test_get_header_and_get_block() {
     .unwrap();
 
     let new_block = chain
-        .draft_block(60, &[], &miner, true)
+        .draft_block(60, &[], &miner, &Default::default(), true)
         .unwrap()
         .unwrap()
         .block;
@@ -92,7 +115,7 @@ fn test_timestamp_increasing() {
     fork1
         .apply_block(
             &fork1
-                .draft_block(10, &[], &miner, true)
+                .draft_block(10, &[], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -103,6 +126,7 @@ fn test_timestamp_increasing() {
             5, // 5 < 10
             &[],
             &miner,
+            &Default::default(),
             true,
         ),
         Err(BlockchainError::InvalidTimestamp)
@@ -114,6 +138,7 @@ fn test_timestamp_increasing() {
                     10, // 10, again, should be fine
                     &[],
                     &miner,
+                    &Default::default(),
                     true,
                 )
                 .unwrap()
@@ -126,7 +151,7 @@ fn test_timestamp_increasing() {
         fork1
             .apply_block(
                 &fork1
-                    .draft_block(i, &[], &miner, true)
+                    .draft_block(i, &[], &miner, &Default::default(), true)
                     .unwrap()
                     .unwrap()
                     .block,
@@ -135,13 +160,13 @@ fn test_timestamp_increasing() {
     }
 
     assert!(matches!(
-        fork1.draft_block(28, &[], &miner, true,),
+        fork1.draft_block(28, &[], &miner, &Default::default(), true,),
         Err(BlockchainError::InvalidTimestamp)
     ));
     fork1
         .apply_block(
             &fork1
-                .draft_block(29, &[], &miner, true)
+                .draft_block(29, &[], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -162,9 +187,15 @@ fn test_block_number_correctness_check() {
     )
     .unwrap();
     let mut fork1 = chain.fork_on_ram();
-    let blk1 = fork1.draft_block(0, &[], &miner, true).unwrap().unwrap();
+    let blk1 = fork1
+        .draft_block(0, &[], &miner, &Default::default(), true)
+        .unwrap()
+        .unwrap();
     fork1.extend(1, &[blk1.block.clone()]).unwrap();
-    let blk2 = fork1.draft_block(1, &[], &miner, true).unwrap().unwrap();
+    let blk2 = fork1
+        .draft_block(1, &[], &miner, &Default::default(), true)
+        .unwrap()
+        .unwrap();
     fork1.extend(2, &[blk2.block.clone()]).unwrap();
     assert_eq!(fork1.get_height().unwrap(), 3);
 
@@ -207,9 +238,15 @@ fn test_parent_hash_correctness_check() {
     )
     .unwrap();
     let mut fork1 = chain.fork_on_ram();
-    let blk1 = fork1.draft_block(0, &[], &miner, true).unwrap().unwrap();
+    let blk1 = fork1
+        .draft_block(0, &[], &miner, &Default::default(), true)
+        .unwrap()
+        .unwrap();
     fork1.extend(1, &[blk1.block.clone()]).unwrap();
-    let blk2 = fork1.draft_block(1, &[], &miner, true).unwrap().unwrap();
+    let blk2 = fork1
+        .draft_block(1, &[], &miner, &Default::default(), true)
+        .unwrap()
+        .unwrap();
     fork1.extend(2, &[blk2.block.clone()]).unwrap();
     assert_eq!(fork1.get_height().unwrap(), 3);
 
@@ -272,6 +309,7 @@ fn test_merkle_root_check() {
                 ),
             ],
             &miner,
+            &Default::default(),
             true,
         )
         .unwrap()
@@ -297,6 +335,7 @@ fn test_merkle_root_check() {
                 ),
             ],
             &miner,
+            &Default::default(),
             true,
         )
         .unwrap()
@@ -373,7 +412,7 @@ fn test_txs_cant_be_duplicated() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[tx.clone()], &miner, true)
+                .draft_block(1, &[tx.clone()], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -396,7 +435,7 @@ fn test_txs_cant_be_duplicated() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[tx.clone()], &miner, true)
+                .draft_block(1, &[tx.clone()], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -427,7 +466,7 @@ fn test_txs_cant_be_duplicated() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[tx2], &miner, true)
+                .draft_block(1, &[tx2], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -496,7 +535,7 @@ fn test_insufficient_balance_is_handled() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[tx], &miner, true)
+                .draft_block(1, &[tx], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -536,6 +575,7 @@ fn test_cant_apply_unsigned_tx() {
         },
         nonce: 1,
         fee: Money::ziesha(300),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
     let unsigned_tx = TransactionAndDelta {
@@ -544,7 +584,13 @@ fn test_cant_apply_unsigned_tx() {
     };
 
     // Ensure apply_tx will raise
-    match chain.draft_block(1, &[unsigned_tx.clone()], &miner, false) {
+    match chain.draft_block(
+        1,
+        &[unsigned_tx.clone()],
+        &miner,
+        &Default::default(),
+        false,
+    ) {
         Ok(_) => assert!(false, "Unsigned transaction shall not be applied"),
         Err(e) => assert!(matches!(e, BlockchainError::SignatureError)),
     }
@@ -553,7 +599,7 @@ fn test_cant_apply_unsigned_tx() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[unsigned_tx], &miner, true)
+                .draft_block(1, &[unsigned_tx], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -594,6 +640,7 @@ fn test_cant_apply_invalid_signed_tx() {
         },
         nonce: 1,
         fee: Money::ziesha(300),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
 
@@ -607,7 +654,7 @@ fn test_cant_apply_invalid_signed_tx() {
     };
 
     // Ensure apply_tx will raise
-    match chain.draft_block(1, &[tx.clone()], &miner, false) {
+    match chain.draft_block(1, &[tx.clone()], &miner, &Default::default(), false) {
         Ok(_) => assert!(false, "Invalid signed transaction shall not be applied"),
         Err(e) => assert!(matches!(e, BlockchainError::SignatureError)),
     }
@@ -616,7 +663,7 @@ fn test_cant_apply_invalid_signed_tx() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[tx], &miner, true)
+                .draft_block(1, &[tx], &miner, &Default::default(), true)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -672,6 +719,7 @@ fn test_balances_are_correct_after_tx() {
                         1,
                     )],
                     &miner,
+                    &Default::default(),
                     true,
                 )
                 .unwrap()
@@ -706,6 +754,7 @@ fn test_balances_are_correct_after_tx() {
                         1,
                     )],
                     &miner,
+                    &Default::default(),
                     true,
                 )
                 .unwrap()
@@ -740,6 +789,7 @@ fn test_balances_are_correct_after_tx() {
                         1,
                     )],
                     &miner,
+                    &Default::default(),
                     true,
                 )
                 .unwrap()
@@ -774,6 +824,7 @@ fn test_balances_are_correct_after_tx() {
                         2,
                     )],
                     &miner,
+                    &Default::default(),
                     true,
                 )
                 .unwrap()
@@ -808,6 +859,7 @@ fn test_balances_are_correct_after_tx() {
                         3,
                     )],
                     &miner,
+                    &Default::default(),
                     true,
                 )
                 .unwrap()
@@ -869,6 +921,7 @@ fn test_chain_should_not_draft_invalid_transactions() {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     });
 
@@ -893,6 +946,7 @@ fn test_chain_should_not_draft_invalid_transactions() {
             },
             nonce: 1,
             fee: Money::ziesha(0),
+            valid_until: None,
             sig: Signature::Unsigned, // invalid transaction
         },
         state_delta: None,
@@ -909,13 +963,20 @@ fn test_chain_should_not_draft_invalid_transactions() {
             },
             nonce: 1,
             fee: Money::ziesha(0),
+            valid_until: None,
             sig: Signature::Unsigned, // invalid transaction
         },
         state_delta: None,
     };
     let mempool = vec![t_valid, t_invalid_unsigned, t_invalid_from_treasury];
     let draft = chain
-        .draft_block(1650000000, &mempool, &wallet_miner, true)
+        .draft_block(
+            1650000000,
+            &mempool,
+            &wallet_miner,
+            &Default::default(),
+            true,
+        )
         .unwrap()
         .unwrap();
 
@@ -943,6 +1004,7 @@ fn test_chain_should_draft_all_valid_transactions() {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     });
 
@@ -965,7 +1027,13 @@ fn test_chain_should_draft_all_valid_transactions() {
 
     let mempool = vec![t1, t2];
     let draft = chain
-        .draft_block(1650000000, &mempool, &wallet_miner, true)
+        .draft_block(
+            1650000000,
+            &mempool,
+            &wallet_miner,
+            &Default::default(),
+            true,
+        )
         .unwrap()
         .unwrap();
 
@@ -1003,6 +1071,7 @@ fn test_chain_should_rollback_applied_block() {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     });
 
@@ -1017,7 +1086,13 @@ fn test_chain_should_rollback_applied_block() {
     );
     let mut mempool = vec![t1];
     let draft = chain
-        .draft_block(1650000000, &mempool, &wallet_miner, true)
+        .draft_block(
+            1650000000,
+            &mempool,
+            &wallet_miner,
+            &Default::default(),
+            true,
+        )
         .unwrap()
         .unwrap();
 
@@ -1033,7 +1108,13 @@ fn test_chain_should_rollback_applied_block() {
     mempool.push(t2);
 
     let draft = chain
-        .draft_block(1650000001, &mempool, &wallet_miner, true)
+        .draft_block(
+            1650000001,
+            &mempool,
+            &wallet_miner,
+            &Default::default(),
+            true,
+        )
         .unwrap()
         .unwrap();
 
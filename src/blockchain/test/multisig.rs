@@ -0,0 +1,123 @@
+use super::*;
+
+#[test]
+fn test_multisig_threshold() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let signers = ["S1", "S2", "S3"].map(|s| TxBuilder::new(&Vec::from(s)));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    // Zero threshold, duplicate signers and threshold above the signer count
+    for (signers, threshold) in [
+        (vec![signers[0].get_address()], 0),
+        (vec![signers[0].get_address(); 2], 2),
+        (vec![signers[0].get_address()], 2),
+    ] {
+        let tx = abc.create_multisig(
+            "".into(),
+            Multisig { signers, threshold },
+            Money::ziesha(0),
+            1,
+        );
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&tx.tx, false),
+            Err(BlockchainError::InvalidMultisig)
+        ));
+    }
+
+    let multisig = Multisig {
+        signers: signers.iter().map(|s| s.get_address()).collect(),
+        threshold: 2,
+    };
+    let address = multisig.address();
+    let create = abc.create_multisig("".into(), multisig.clone(), Money::ziesha(0), 1);
+    let fund = abc.create_transaction(
+        "".into(),
+        address.clone(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        2,
+    );
+    let draft = chain
+        .draft_block(5, &[create, fund], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 2);
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(
+        chain.get_multisig(address.clone()).unwrap(),
+        Some(multisig.clone())
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(
+            &abc.create_multisig("".into(), multisig.clone(), Money::ziesha(0), 3)
+                .tx,
+            false
+        ),
+        Err(BlockchainError::MultisigAlreadyExists)
+    ));
+
+    let mut tx = signers[0]
+        .create_multisig_transaction(
+            "".into(),
+            &multisig,
+            vec![RegularSendEntry {
+                dst: abc.get_address(),
+                amount: Money::ziesha(30),
+            }],
+            Money::ziesha(0),
+            1,
+        )
+        .unwrap();
+    assert!(tx.verify_signature(None));
+
+    // A single signature, or the same signer twice, is not enough
+    for sigs in [1, 2] {
+        let mut tx = tx.clone();
+        if let Signature::Multi(s) = &mut tx.sig {
+            s.extend(s.clone().into_iter().take(sigs - 1));
+        }
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&tx, false),
+            Err(BlockchainError::SignatureError)
+        ));
+    }
+
+    assert!(!abc.cosign_tx(&mut tx, &multisig));
+    assert!(signers[2].cosign_tx(&mut tx, &multisig));
+    let draft = chain
+        .draft_block(
+            10,
+            &[TransactionAndDelta {
+                tx,
+                state_delta: None,
+            }],
+            &validator,
+            &Default::default(),
+            true,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(
+        chain.get_balance(address.clone(), TokenId::Ziesha).unwrap(),
+        Amount(70)
+    );
+
+    // Multi-signatures are only accepted from multisig accounts
+    let mut tx = abc
+        .create_transaction("".into(), address, Money::ziesha(1), Money::ziesha(0), 3)
+        .tx;
+    tx.sig = Signature::Multi(vec![]);
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&tx, false),
+        Err(BlockchainError::MultisigNotFound)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+fn test_pruned_blocks() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    for i in 1..=5 {
+        let send = abc.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::ziesha(100),
+            Money::ziesha(0),
+            i,
+        );
+        let draft = chain
+            .draft_block(i as u32 * 5, &[send], &validator, &Default::default(), true)?
+            .unwrap();
+        chain.apply_block(&draft.block)?;
+    }
+    assert_eq!(chain.get_pruned_height()?, 0);
+
+    // Only the bodies of the last 2 blocks (And the genesis block) are kept
+    chain.prune(2)?;
+    assert_eq!(chain.get_height()?, 6);
+    assert_eq!(chain.get_pruned_height()?, 4);
+    for i in 1..4 {
+        assert!(matches!(
+            chain.get_block(i),
+            Err(BlockchainError::BlockPruned)
+        ));
+        assert!(matches!(
+            chain.get_merkle_tree(i),
+            Err(BlockchainError::BlockPruned)
+        ));
+        assert!(chain.database.get(keys::rollback(i))?.is_none());
+    }
+    assert_eq!(chain.get_block(0)?, chain.config.genesis.block);
+    assert_eq!(chain.get_block(4)?.body.len(), 1);
+    assert_eq!(chain.get_headers(0, 6)?.len(), 6);
+    assert_eq!(
+        chain.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(500)
+    );
+
+    // Horizon never moves back
+    chain.prune(4)?;
+    assert_eq!(chain.get_pruned_height()?, 4);
+
+    // Recent blocks can still be rolled back, but not the pruned ones
+    chain.rollback()?;
+    chain.rollback()?;
+    assert_eq!(chain.get_height()?, 4);
+    assert!(matches!(
+        chain.rollback(),
+        Err(BlockchainError::RollbackBeyondPruned)
+    ));
+    assert_eq!(
+        chain.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(300)
+    );
+    Ok(())
+}
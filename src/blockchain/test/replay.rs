@@ -0,0 +1,113 @@
+use super::*;
+use crate::blockchain::replay::{replay, replay_in_windows, ReplayedBlock};
+use crate::db::StringKey;
+
+fn summary(block: &ReplayedBlock) -> (u64, <Hasher as Hash>::Output, Vec<StringKey>) {
+    (block.height, block.checksum, block.diverged_keys.clone())
+}
+
+fn replay_log<K: KvStore>(
+    chain: &KvStoreChain<K>,
+) -> Result<(Vec<ReplayedBlock>, Option<ReplayedBlock>), BlockchainError> {
+    let mut log = Vec::new();
+    let diverged = replay(chain, |b| log.push(b.clone()))?;
+
+    // Replaying in smaller windows gives the same results
+    for window in [1, 2, 4] {
+        let mut windowed_log = Vec::new();
+        let windowed_diverged = replay_in_windows(chain, window, |b| windowed_log.push(b.clone()))?;
+        assert_eq!(
+            log.iter().map(summary).collect::<Vec<_>>(),
+            windowed_log.iter().map(summary).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            diverged.as_ref().map(summary),
+            windowed_diverged.as_ref().map(summary)
+        );
+    }
+    Ok((log, diverged))
+}
+
+#[test]
+fn test_replay() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    for i in 1..=5 {
+        let send = abc.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::ziesha(100),
+            Money::ziesha(0),
+            i,
+        );
+        let draft = chain
+            .draft_block(i as u32 * 5, &[send], &validator, &Default::default(), true)?
+            .unwrap();
+        chain.apply_block(&draft.block)?;
+    }
+
+    // Consistent chains are replayed till the end, with deterministic checksums
+    let (log, diverged) = replay_log(&chain)?;
+    assert!(diverged.is_none());
+    assert_eq!(log.len(), 6);
+    for (i, block) in log.iter().enumerate() {
+        assert_eq!(block.height, i as u64);
+        assert_eq!(block.header_hash, chain.get_header(i as u64)?.hash());
+        assert!(block.diverged_keys.is_empty());
+    }
+    let (again, _) = replay_log(&chain)?;
+    assert_eq!(
+        log.iter().map(|b| b.checksum).collect::<Vec<_>>(),
+        again.iter().map(|b| b.checksum).collect::<Vec<_>>()
+    );
+
+    // State after block 2, as recorded before block 3, is corrupted
+    let bob_balance = keys::account_balance(&bob.get_address(), TokenId::Ziesha);
+    let mut corrupted = chain.fork_on_ram();
+    let rollback: Vec<WriteOp> = corrupted
+        .database
+        .get(keys::rollback(3))?
+        .unwrap()
+        .try_into()?;
+    let rollback = rollback
+        .into_iter()
+        .map(|op| match op {
+            WriteOp::Put(k, _) if k == bob_balance => WriteOp::Put(k, Amount(1).into()),
+            op => op,
+        })
+        .collect::<Vec<_>>();
+    corrupted
+        .database
+        .update(&[WriteOp::Put(keys::rollback(3), rollback.into())])?;
+    let (log, diverged) = replay_log(&corrupted)?;
+    assert_eq!(log.len(), 3);
+    let diverged = diverged.unwrap();
+    assert_eq!(diverged.height, 2);
+    assert_eq!(diverged.diverged_keys, vec![bob_balance.clone()]);
+
+    // Current state is corrupted
+    let mut corrupted = chain.fork_on_ram();
+    corrupted
+        .database
+        .update(&[WriteOp::Put(bob_balance.clone(), Amount(1).into())])?;
+    let (_, diverged) = replay_log(&corrupted)?;
+    let diverged = diverged.unwrap();
+    assert_eq!(diverged.height, 5);
+    assert_eq!(diverged.diverged_keys, vec![bob_balance]);
+
+    // Pruned chains can not be replayed
+    let mut pruned = chain.fork_on_ram();
+    pruned.prune(2)?;
+    assert!(matches!(
+        replay_log(&pruned),
+        Err(BlockchainError::BlockPruned)
+    ));
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+fn apply<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    validator: &TxBuilder,
+    tx: &TransactionAndDelta,
+) -> Result<(), BlockchainError> {
+    let draft = chain
+        .draft_block(5, &[tx.clone()], validator, &Default::default(), false)?
+        .unwrap();
+    chain.apply_block(&draft.block)
+}
+
+#[test]
+fn test_signatures_are_bound_to_chain_id() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.fork_schedule.activate(Feature::ReplayProtection, 2);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    let bound = abc.clone().with_chain_id(chain.chain_id());
+    let foreign = abc.clone().with_chain_id([1u8; 32]);
+
+    let send = |builder: &TxBuilder, nonce: u32| {
+        builder.create_transaction(
+            "".into(),
+            validator.get_address(),
+            Money::ziesha(1),
+            Money::ziesha(0),
+            nonce,
+        )
+    };
+
+    // Before activation, both legacy and chain-bound signatures are accepted
+    assert!(matches!(
+        apply(&mut chain.fork_on_ram(), &validator, &send(&foreign, 1)),
+        Err(BlockchainError::SignatureError)
+    ));
+    apply(&mut chain.fork_on_ram(), &validator, &send(&bound, 1)).unwrap();
+    apply(&mut chain, &validator, &send(&abc, 1)).unwrap();
+
+    // After activation, only signatures bound to this chain are accepted
+    for builder in [&abc, &foreign] {
+        assert!(matches!(
+            apply(&mut chain.fork_on_ram(), &validator, &send(builder, 2)),
+            Err(BlockchainError::SignatureError)
+        ));
+        let draft = chain
+            .draft_block(
+                10,
+                &[send(builder, 2)],
+                &validator,
+                &Default::default(),
+                true,
+            )
+            .unwrap()
+            .unwrap();
+        assert!(draft.block.body.is_empty());
+    }
+    apply(&mut chain, &validator, &send(&bound, 2)).unwrap();
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_mpn_transactions_are_bound_to_chain_id() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let chain_bound_vk =
+        zk::ZkVerifierKey::Groth16(Box::new(crate::config::blockchain::MPN_UPDATE_VK.clone()));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.mpn_config.chain_bound_update_vk = Some(chain_bound_vk.clone());
+    conf.fork_schedule.activate(Feature::MpnReplayProtection, 2);
+    let mpn_contract_id = conf.mpn_config.mpn_contract_id;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    let chain_id = chain.chain_id();
+
+    let send = |builder: &TxBuilder| {
+        builder.create_mpn_transaction(
+            validator.get_mpn_address(),
+            Money::ziesha(1),
+            Money::ziesha(0),
+            1,
+        )
+    };
+    let legacy = send(&abc);
+    let bound = send(&abc.clone().with_mpn_chain_id(Some(chain_id)));
+    assert!(bound.verify_signature(Some(&chain_id)));
+    assert!(!bound.verify_signature(None));
+    assert!(!bound.verify_signature(Some(&[1u8; 32])));
+
+    let update_vk = |chain: &KvStoreChain<db::RamKvStore>| {
+        chain.get_contract(mpn_contract_id).unwrap().functions[0]
+            .verifier_key
+            .clone()
+    };
+    let accepted = |chain: &KvStoreChain<db::RamKvStore>| {
+        let mut mempool = Mempool::new(Amount(1));
+        for tx in [&bound, &legacy] {
+            mempool.add_tx(chain, tx.clone().into(), false, 0).unwrap();
+        }
+        mempool
+            .mpn_txs()
+            .map(|(tx, _)| tx.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // Before activation, only legacy signatures can be proven by the update circuit
+    assert_eq!(accepted(&chain), vec![legacy.clone()]);
+    assert_eq!(update_vk(&chain), zk::ZkVerifierKey::Dummy);
+    let draft = chain
+        .draft_block(5, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    // The block at the activation height is proven by the chain-bound circuit
+    assert_eq!(accepted(&chain), vec![bound.clone()]);
+    let draft = chain
+        .draft_block(10, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(update_vk(&chain), chain_bound_vk);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_mpn_replay_protection_needs_chain_bound_key() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.mpn_config.chain_bound_update_vk = None;
+    conf.fork_schedule.activate(Feature::MpnReplayProtection, 1);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    assert!(matches!(
+        chain.draft_block(5, &[], &validator, &Default::default(), true),
+        Err(BlockchainError::ChainBoundMpnKeyMissing)
+    ));
+    rollback_till_empty(&mut chain).unwrap();
+}
@@ -14,6 +14,7 @@ fn close_enough(a: Amount, b: Amount) -> bool {
 fn test_correct_rewards() {
     let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
     let delegator = TxBuilder::new(&Vec::from("ABC"));
+    let genesis_delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
     let mut chain = KvStoreChain::new(
         db::RamKvStore::new(),
         blockchain::get_test_blockchain_config(),
@@ -24,7 +25,7 @@ fn test_correct_rewards() {
     let expected_validator_reward_1 = Amount(expected_reward_1.0 * 12 / 255);
     assert_eq!(expected_reward_1, Amount(19999999999999));
     let draft = chain
-        .draft_block(0, &[], &validator, true)
+        .draft_block(0, &[], &validator, &Default::default(), true)
         .unwrap()
         .unwrap();
     chain.apply_block(&draft.block).unwrap();
@@ -34,6 +35,20 @@ fn test_correct_rewards() {
             .unwrap(),
         expected_validator_reward_1
     ));
+    // Vec("DELEGATOR") is the only delegator, its rewards wait in the pool
+    let expected_pending_1 = expected_reward_1 - expected_validator_reward_1;
+    assert!(close_enough(
+        chain
+            .get_pending_reward(genesis_delegator.get_address(), validator.get_address())
+            .unwrap(),
+        expected_pending_1
+    ));
+    assert_eq!(
+        chain
+            .get_balance(genesis_delegator.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(25)
+    );
 
     // Vec("DELEGATOR") Has already delegated 25Z too in genesis block
     let validator_stake = Amount(25);
@@ -68,6 +83,7 @@ fn test_correct_rewards() {
                 ),
             ],
             &validator,
+            &Default::default(),
             true,
         )
         .unwrap()
@@ -83,18 +99,32 @@ fn test_correct_rewards() {
             .unwrap(),
         expected_validator_balance_2
     ));
-    assert!(close_enough(
+    assert_eq!(
         chain
             .get_balance(delegator.get_address(), TokenId::Ziesha)
             .unwrap(),
         expected_delegator_balance_2
+    );
+    // Rewards of the block were paid before the new delegations
+    let expected_pending_2 = expected_pending_1 + expected_reward_2 - expected_validator_reward_2;
+    assert!(close_enough(
+        chain
+            .get_pending_reward(genesis_delegator.get_address(), validator.get_address())
+            .unwrap(),
+        expected_pending_2
     ));
+    assert_eq!(
+        chain
+            .get_pending_reward(delegator.get_address(), validator.get_address())
+            .unwrap(),
+        Amount(0)
+    );
 
     let expected_reward_3 = chain.next_reward().unwrap();
     assert_eq!(expected_reward_3, Amount(19999600001999));
     let expected_validator_reward_3 = Amount(expected_reward_3.0 * 12 / 255);
     let draft = chain
-        .draft_block(0, &[], &validator, true)
+        .draft_block(0, &[], &validator, &Default::default(), true)
         .unwrap()
         .unwrap();
     chain.apply_block(&draft.block).unwrap();
@@ -103,17 +133,57 @@ fn test_correct_rewards() {
             .get_balance(validator.get_address(), TokenId::Ziesha)
             .unwrap()
             - expected_validator_balance_2,
-        Amount(
-            (expected_reward_3 - expected_validator_reward_3).0 / 5 + expected_validator_reward_3.0
-        )
+        expected_validator_reward_3
     ));
     assert!(close_enough(
         chain
-            .get_balance(delegator.get_address(), TokenId::Ziesha)
-            .unwrap()
-            - expected_delegator_balance_2,
+            .get_pending_reward(validator.get_address(), validator.get_address())
+            .unwrap(),
+        Amount((expected_reward_3 - expected_validator_reward_3).0 / 5)
+    ));
+    assert!(close_enough(
+        chain
+            .get_pending_reward(delegator.get_address(), validator.get_address())
+            .unwrap(),
         Amount((expected_reward_3 - expected_validator_reward_3).0 * 3 / 5)
     ));
+    assert!(close_enough(
+        chain
+            .get_pending_reward(genesis_delegator.get_address(), validator.get_address())
+            .unwrap(),
+        expected_pending_2 + Amount((expected_reward_3 - expected_validator_reward_3).0 / 5)
+    ));
+
+    // Claiming moves the pending rewards to the balance of the delegator
+    let pending = chain
+        .get_pending_reward(delegator.get_address(), validator.get_address())
+        .unwrap();
+    let claim = delegator.claim_reward(
+        "".into(),
+        validator.get_address(),
+        Money::ziesha(0),
+        chain.get_nonce(delegator.get_address()).unwrap() + 1,
+    );
+    chain.apply_tx(&claim.tx, false).unwrap();
+    assert_eq!(
+        chain
+            .get_balance(delegator.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        expected_delegator_balance_2 + pending
+    );
+    assert_eq!(
+        chain
+            .get_pending_reward(delegator.get_address(), validator.get_address())
+            .unwrap(),
+        Amount(0)
+    );
+    assert_eq!(
+        chain
+            .get_delegate(delegator.get_address(), validator.get_address())
+            .unwrap()
+            .amount,
+        delegator_stake
+    );
 }
 
 #[test]
@@ -137,6 +207,7 @@ fn test_auto_delegate() {
                 },
                 nonce: 0,
                 fee: Money::ziesha(0),
+                valid_until: None,
                 sig: Signature::Unsigned,
             },
             true,
@@ -148,10 +219,10 @@ fn test_auto_delegate() {
     let expected_delegator_reward_before_auto_delegate =
         expected_reward - expected_validator_reward;
     let expected_delegator_reward_after_auto_delegate =
-        Amount((expected_delegator_reward_before_auto_delegate.0 as f64 * 200.0 / 255.0) as u64);
+        Amount(expected_delegator_reward_before_auto_delegate.0 * 200 / 255);
     let expected_delegator_balance = Amount(25) + expected_delegator_reward_after_auto_delegate;
-    let expected_delegation = Amount(25)
-        + Amount((expected_delegator_reward_before_auto_delegate.0 as f64 * 55.0 / 255.0) as u64);
+    let expected_delegation =
+        Amount(25) + Amount(expected_delegator_reward_before_auto_delegate.0 * 55 / 255);
     assert_eq!(
         chain
             .get_balance(delegator.get_address(), TokenId::Ziesha)
@@ -160,22 +231,39 @@ fn test_auto_delegate() {
     );
     assert_eq!(expected_reward, Amount(19999999999999));
     let draft = chain
-        .draft_block(0, &[], &validator, true)
+        .draft_block(0, &[], &validator, &Default::default(), true)
         .unwrap()
         .unwrap();
     chain.apply_block(&draft.block).unwrap();
     assert!(close_enough(
+        chain
+            .get_balance(validator.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        expected_validator_reward
+    ));
+
+    // Rewards are compounded lazily, when the delegator claims them
+    assert_eq!(
         chain
             .get_delegate(delegator.get_address(), validator.get_address())
             .unwrap()
             .amount,
-        expected_delegation
-    ));
+        Amount(25)
+    );
+    let claim = delegator.claim_reward(
+        "".into(),
+        validator.get_address(),
+        Money::ziesha(0),
+        chain.get_nonce(delegator.get_address()).unwrap() + 1,
+    );
+    chain.apply_tx(&claim.tx, false).unwrap();
+
     assert!(close_enough(
         chain
-            .get_balance(validator.get_address(), TokenId::Ziesha)
-            .unwrap(),
-        expected_validator_reward
+            .get_delegate(delegator.get_address(), validator.get_address())
+            .unwrap()
+            .amount,
+        expected_delegation
     ));
     assert!(close_enough(
         chain
@@ -183,4 +271,123 @@ fn test_auto_delegate() {
             .unwrap(),
         expected_delegator_balance
     ));
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        chain
+            .get_delegate(delegator.get_address(), validator.get_address())
+            .unwrap()
+            .amount
+    );
+}
+
+#[test]
+fn test_rewards_sum_exactly() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    for (i, fee) in [0, 7, 1234].into_iter().enumerate() {
+        let validator_before = chain
+            .get_balance(validator.get_address(), TokenId::Ziesha)
+            .unwrap();
+        let pool_before = chain
+            .get_reward_pool(validator.get_address())
+            .unwrap()
+            .balance;
+        let treasury_before = chain
+            .get_balance(Default::default(), TokenId::Ziesha)
+            .unwrap();
+        let next_reward = chain.next_reward().unwrap();
+
+        let tx = abc.create_transaction(
+            "".into(),
+            abc.get_address(),
+            Money::ziesha(1),
+            Money::ziesha(fee),
+            i as u32 + 1,
+        );
+        let draft = chain
+            .draft_block(i as u32, &[tx], &validator, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(draft.block.body.len(), 1);
+        chain.apply_block(&draft.block).unwrap();
+
+        let validator_gained = chain
+            .get_balance(validator.get_address(), TokenId::Ziesha)
+            .unwrap()
+            - validator_before;
+        let pool_gained = chain
+            .get_reward_pool(validator.get_address())
+            .unwrap()
+            .balance
+            - pool_before;
+        assert_eq!(validator_gained + pool_gained, next_reward + Amount(fee));
+        assert_eq!(
+            chain
+                .get_balance(Default::default(), TokenId::Ziesha)
+                .unwrap(),
+            treasury_before - next_reward
+        );
+    }
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_reward_pool_stays_solvent() {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    let mut rng = ChaChaRng::seed_from_u64(321);
+    let validators = ["VALIDATOR", "VALIDATOR2", "VALIDATOR3"]
+        .map(|v| TxBuilder::new(&Vec::from(v)).get_address());
+    let delegators = ["ABC", "DELEGATOR"].map(|d| TxBuilder::new(&Vec::from(d)));
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    for timestamp in 0..30 {
+        let mut txs = Vec::new();
+        for delegator in delegators.iter() {
+            let nonce = chain.get_nonce(delegator.get_address()).unwrap() + 1;
+            let from = validators[rng.gen_range(0..validators.len())].clone();
+            let to = validators[rng.gen_range(0..validators.len())].clone();
+            let amount = Amount(rng.gen_range(1..10));
+            let fee = Money::ziesha(0);
+            txs.push(match rng.gen_range(0..5) {
+                0 => delegator.delegate("".into(), to, amount, fee, nonce),
+                1 => delegator.undelegate("".into(), from, amount, fee, nonce),
+                2 => delegator.redelegate("".into(), from, to, amount, fee, nonce),
+                3 => delegator.auto_delegate("".into(), to, Ratio(rng.gen()), fee, nonce),
+                _ => delegator.claim_reward("".into(), from, fee, nonce),
+            });
+        }
+        let draft = chain
+            .draft_block(timestamp, &txs, &miner, &Default::default(), true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+
+        for validator in validators.iter() {
+            let pending = delegators
+                .iter()
+                .map(|d| {
+                    chain
+                        .get_pending_reward(d.get_address(), validator.clone())
+                        .unwrap()
+                })
+                .fold(Amount(0), |a, b| a + b);
+            assert!(pending <= chain.get_reward_pool(validator.clone()).unwrap().balance);
+        }
+    }
+
+    rollback_till_empty(&mut chain).unwrap();
 }
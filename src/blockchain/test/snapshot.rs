@@ -0,0 +1,170 @@
+use super::*;
+use crate::blockchain::snapshot::Snapshot;
+
+#[test]
+fn test_snapshot_export_import() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let conf = blockchain::get_test_blockchain_config();
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+
+    let mut blocks = Vec::new();
+    let mut sends = Vec::new();
+    for i in 1..=4 {
+        let send = abc.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::ziesha(100),
+            Money::ziesha(0),
+            i,
+        );
+        let draft = chain
+            .draft_block(
+                i as u32 * 5,
+                &[send.clone()],
+                &validator,
+                &Default::default(),
+                true,
+            )?
+            .unwrap();
+        chain.apply_block(&draft.block)?;
+        blocks.push(draft.block);
+        sends.push(send);
+    }
+
+    // Snapshot of the tip results in the same state, without the history
+    let snapshot = Snapshot::export(&chain, 5, 0)?;
+    let checksum = snapshot.checksum(conf.clone())?;
+    assert_eq!(snapshot.header, chain.get_tip()?);
+    let mut imported = snapshot.clone().import(
+        db::RamKvStore::new(),
+        conf.clone(),
+        chain.get_tip()?.hash(),
+        &checksum,
+    )?;
+    assert_eq!(imported.db_checksum()?, checksum);
+    assert_eq!(imported.get_state_root()?, chain.get_state_root()?);
+    assert_eq!(
+        imported.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(400)
+    );
+    assert_eq!(imported.get_pruned_height()?, 5);
+    assert_eq!(imported.get_header(1)?, chain.get_header(1)?);
+    assert!(matches!(
+        imported.get_block(4),
+        Err(BlockchainError::BlockPruned)
+    ));
+    assert!(imported.get_tx_location(sends[3].tx.hash())?.is_none());
+    assert!(imported.get_history(bob.get_address(), 0, 10)?.is_empty());
+    assert!(matches!(
+        imported.rollback(),
+        Err(BlockchainError::RollbackBeyondPruned)
+    ));
+
+    // Only trusted snapshots are imported
+    assert!(matches!(
+        snapshot.clone().import(
+            db::RamKvStore::new(),
+            conf.clone(),
+            chain.get_header(3)?.hash(),
+            &checksum,
+        ),
+        Err(BlockchainError::UntrustedSnapshot)
+    ));
+    assert!(matches!(
+        snapshot.clone().import(
+            db::RamKvStore::new(),
+            conf.clone(),
+            chain.get_tip()?.hash(),
+            &chain.db_checksum()?,
+        ),
+        Err(BlockchainError::InvalidSnapshotChecksum)
+    ));
+    let mut tampered = snapshot.clone();
+    let (_, balance) = tampered
+        .pairs
+        .iter_mut()
+        .find(|(k, _)| *k == keys::account_balance(&bob.get_address(), TokenId::Ziesha))
+        .unwrap();
+    *balance = Amount(1000000).into();
+    assert!(matches!(
+        tampered.import(
+            db::RamKvStore::new(),
+            conf.clone(),
+            chain.get_tip()?.hash(),
+            &checksum
+        ),
+        Err(BlockchainError::UntrustedSnapshot)
+    ));
+    // Data outside of the account state is only authenticated by the checksum
+    let mut tampered = snapshot.clone();
+    tampered
+        .pairs
+        .push((keys::burned(), Amount(1000000).into()));
+    assert!(matches!(
+        tampered.import(
+            db::RamKvStore::new(),
+            conf.clone(),
+            chain.get_tip()?.hash(),
+            &checksum
+        ),
+        Err(BlockchainError::InvalidSnapshotChecksum)
+    ));
+    assert!(matches!(
+        snapshot.import(
+            db::RamKvStore::new(),
+            conf.clone(),
+            chain.get_tip()?.hash(),
+            &checksum
+        ),
+        Ok(_)
+    ));
+    let mut non_empty = db::RamKvStore::new();
+    non_empty.update(&[WriteOp::Put("KEY".into(), Amount(1).into())])?;
+    assert!(matches!(
+        Snapshot::export(&chain, 5, 0)?.import(
+            non_empty,
+            conf.clone(),
+            chain.get_tip()?.hash(),
+            &checksum
+        ),
+        Err(BlockchainError::SnapshotDatabaseNotEmpty)
+    ));
+
+    // Blocks in the retention window are kept and can be rolled back. Nodes continue
+    // syncing from the snapshot of an older height.
+    let snapshot = Snapshot::export(&chain, 3, 1)?;
+    let checksum = snapshot.checksum(conf.clone())?;
+    assert_eq!(snapshot.header, chain.get_header(2)?);
+    let mut imported = snapshot.import(
+        db::RamKvStore::new(),
+        conf.clone(),
+        chain.get_header(2)?.hash(),
+        &checksum,
+    )?;
+    assert_eq!(imported.get_height()?, 3);
+    assert_eq!(imported.get_pruned_height()?, 2);
+    assert_eq!(imported.get_block(2)?, blocks[1]);
+    assert!(imported.get_tx_location(sends[0].tx.hash())?.is_none());
+    assert!(imported.get_tx_location(sends[1].tx.hash())?.is_some());
+    assert_eq!(imported.get_history(bob.get_address(), 0, 10)?.len(), 1);
+    imported.rollback()?;
+    assert!(matches!(
+        imported.rollback(),
+        Err(BlockchainError::RollbackBeyondPruned)
+    ));
+    imported.extend(2, &blocks[1..])?;
+    assert_eq!(imported.get_tip()?, chain.get_tip()?);
+    assert_eq!(imported.get_state_root()?, chain.get_state_root()?);
+    assert_eq!(
+        imported.get_balance(bob.get_address(), TokenId::Ziesha)?,
+        Amount(400)
+    );
+
+    assert!(matches!(
+        Snapshot::export(&chain, 6, 0),
+        Err(BlockchainError::BlockNotFound)
+    ));
+    Ok(())
+}
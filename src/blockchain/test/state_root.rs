@@ -0,0 +1,166 @@
+use super::*;
+
+#[test]
+fn test_state_root() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let genesis_state_root = chain.get_state_root()?;
+    assert_ne!(genesis_state_root, <Hasher as Hash>::Output::default());
+
+    let send = abc.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[send], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+
+    // Headers claiming a wrong state root are rejected
+    let mut invalid = draft.block.clone();
+    invalid.header.state_root = genesis_state_root;
+    validator.sign_header(&mut invalid.header);
+    assert!(matches!(
+        chain.apply_block(&invalid),
+        Err(BlockchainError::InvalidStateRoot)
+    ));
+
+    chain.apply_block(&draft.block)?;
+    let header = chain.get_tip()?;
+    assert_eq!(chain.get_state_root()?, header.state_root);
+    assert_ne!(header.state_root, genesis_state_root);
+
+    let proof = chain.prove_balance(bob.get_address(), TokenId::Ziesha)?;
+    assert!(verify_balance_proof(
+        &header,
+        &bob.get_address(),
+        TokenId::Ziesha,
+        Amount(100),
+        &proof
+    ));
+    assert!(!verify_balance_proof(
+        &header,
+        &bob.get_address(),
+        TokenId::Ziesha,
+        Amount(101),
+        &proof
+    ));
+    assert!(!verify_balance_proof(
+        &header,
+        &abc.get_address(),
+        TokenId::Ziesha,
+        Amount(100),
+        &proof
+    ));
+    let proof = chain.prove_balance(abc.get_address(), TokenId::Ziesha)?;
+    assert!(verify_balance_proof(
+        &header,
+        &abc.get_address(),
+        TokenId::Ziesha,
+        chain.get_balance(abc.get_address(), TokenId::Ziesha)?,
+        &proof
+    ));
+
+    // Non-existing balances are proven to be zero
+    let unknown = TxBuilder::new(&Vec::from("UNKNOWN"));
+    let proof = chain.prove_balance(unknown.get_address(), TokenId::Ziesha)?;
+    assert!(verify_balance_proof(
+        &header,
+        &unknown.get_address(),
+        TokenId::Ziesha,
+        Amount(0),
+        &proof
+    ));
+    assert!(!verify_balance_proof(
+        &header,
+        &unknown.get_address(),
+        TokenId::Ziesha,
+        Amount(1),
+        &proof
+    ));
+
+    chain.rollback()?;
+    assert_eq!(chain.get_state_root()?, genesis_state_root);
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
+
+#[test]
+fn test_state_tree_is_canonical() -> Result<(), BlockchainError> {
+    let new_chain = || {
+        KvStoreChain::new(
+            db::RamKvStore::new(),
+            blockchain::get_test_blockchain_config(),
+        )
+        .unwrap()
+    };
+    let state_nodes = |chain: &KvStoreChain<db::RamKvStore>| -> Result<_, BlockchainError> {
+        Ok(chain.database.pairs("STN-".into())?.into_iter().count())
+    };
+    let puts = (0..64u64)
+        .map(|i| {
+            let addr = TxBuilder::new(&i.to_le_bytes()).get_address();
+            WriteOp::Put(
+                keys::account_balance(&addr, TokenId::Ziesha),
+                Amount(i + 1).into(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let removes = puts
+        .iter()
+        .map(|op| match op {
+            WriteOp::Put(k, _) => WriteOp::Remove(k.clone()),
+            WriteOp::Remove(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut chain = new_chain();
+    let genesis_root = chain.get_state_root()?;
+    let genesis_nodes = state_nodes(&chain)?;
+    chain.update_state_root(&puts)?;
+    let root = chain.get_state_root()?;
+
+    // The tree only depends on the leaves, not on the order they are written in
+    let mut reversed = new_chain();
+    reversed.update_state_root(&puts.iter().rev().cloned().collect::<Vec<_>>())?;
+    assert_eq!(reversed.get_state_root()?, root);
+    let mut half = new_chain();
+    half.update_state_root(&puts[32..])?;
+    reversed.update_state_root(&removes[..32])?;
+    assert_eq!(reversed.get_state_root()?, half.get_state_root()?);
+    assert_eq!(state_nodes(&reversed)?, state_nodes(&half)?);
+
+    // Leaves are proven, and so are the keys missing from the tree
+    let mut header = chain.get_tip()?;
+    header.state_root = root;
+    for (i, op) in puts.iter().enumerate() {
+        if let WriteOp::Put(k, v) = op {
+            let proof = ops::prove_state(&chain, k)?;
+            assert!(ops::verify_state_proof(&header, k, Some(v), &proof));
+            assert!(!ops::verify_state_proof(&header, k, None, &proof));
+            let proof = ops::prove_state(&half, k)?;
+            let mut half_header = header.clone();
+            half_header.state_root = half.get_state_root()?;
+            assert_eq!(
+                ops::verify_state_proof(&half_header, k, None, &proof),
+                i < 32
+            );
+        }
+    }
+
+    // Nodes of the removed leaves are cleaned up
+    chain.update_state_root(&removes)?;
+    assert_eq!(chain.get_state_root()?, genesis_root);
+    assert_eq!(state_nodes(&chain)?, genesis_nodes);
+    Ok(())
+}
@@ -19,6 +19,7 @@ fn test_token_balances() {
         Amount(12345),
         0,
         Some(alice.get_address()),
+        None,
         Money::ziesha(0),
         1,
     );
@@ -39,6 +40,7 @@ fn test_token_balances() {
                 1
             )],
             &miner,
+            &Default::default(),
             false,
         ),
         Err(BlockchainError::BalanceInsufficient)
@@ -47,7 +49,7 @@ fn test_token_balances() {
     chain
         .apply_block(
             &chain
-                .draft_block(1, &[token_create_tx], &miner, false)
+                .draft_block(1, &[token_create_tx], &miner, &Default::default(), false)
                 .unwrap()
                 .unwrap()
                 .block,
@@ -72,6 +74,7 @@ fn test_token_balances() {
                         2,
                     )],
                     &miner,
+                    &Default::default(),
                     false,
                 )
                 .unwrap()
@@ -101,6 +104,7 @@ fn test_token_balances() {
                 3
             )],
             &miner,
+            &Default::default(),
             false,
         ),
         Err(BlockchainError::BalanceInsufficient)
@@ -119,6 +123,7 @@ fn test_token_balances() {
                         3,
                     )],
                     &miner,
+                    &Default::default(),
                     false,
                 )
                 .unwrap()
@@ -134,3 +139,114 @@ fn test_token_balances() {
 
     rollback_till_empty(&mut chain).unwrap();
 }
+
+#[test]
+fn test_frozen_accounts_cannot_move_tokens() -> Result<(), BlockchainError> {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABCD"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let (token_create_tx, token_id) = alice.create_token(
+        "".into(),
+        "My Token".into(),
+        "MYT".into(),
+        Amount(12345),
+        0,
+        Some(alice.get_address()),
+        None,
+        Money::ziesha(0),
+        1,
+    );
+    let send = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::new(token_id, 100),
+        Money::ziesha(0),
+        2,
+    );
+    let freeze = alice.update_token(
+        "".into(),
+        token_id,
+        TokenUpdate::Freeze {
+            account: bob.get_address(),
+        },
+        Money::ziesha(0),
+        3,
+    );
+    let draft = chain
+        .draft_block(
+            1,
+            &[token_create_tx, send, freeze],
+            &miner,
+            &Default::default(),
+            true,
+        )?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 3);
+    chain.apply_block(&draft.block)?;
+    assert!(chain.is_frozen(bob.get_address(), token_id)?);
+
+    let bob_send = bob.create_transaction(
+        "".into(),
+        alice.get_address(),
+        Money::new(token_id, 10),
+        Money::ziesha(0),
+        1,
+    );
+    let bob_burn = bob.update_token(
+        "".into(),
+        token_id,
+        TokenUpdate::Burn { amount: Amount(10) },
+        Money::ziesha(0),
+        1,
+    );
+    for tx in [&bob_send, &bob_burn] {
+        assert!(matches!(
+            chain.fork_on_ram().apply_tx(&tx.tx, false),
+            Err(BlockchainError::TokenAccountFrozen)
+        ));
+    }
+    // Only the minter can freeze/unfreeze accounts
+    let bob_unfreeze = bob.update_token(
+        "".into(),
+        token_id,
+        TokenUpdate::Unfreeze {
+            account: bob.get_address(),
+        },
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&bob_unfreeze.tx, false),
+        Err(BlockchainError::TokenUpdatePermissionDenied)
+    ));
+
+    let unfreeze = alice.update_token(
+        "".into(),
+        token_id,
+        TokenUpdate::Unfreeze {
+            account: bob.get_address(),
+        },
+        Money::ziesha(0),
+        4,
+    );
+    for tx in [unfreeze, bob_burn] {
+        let draft = chain
+            .draft_block(2, &[tx], &miner, &Default::default(), true)?
+            .unwrap();
+        assert_eq!(draft.block.body.len(), 1);
+        chain.apply_block(&draft.block)?;
+    }
+    assert!(!chain.is_frozen(bob.get_address(), token_id)?);
+    assert_eq!(chain.get_balance(bob.get_address(), token_id)?, Amount(90));
+    assert_eq!(chain.get_token(token_id)?.unwrap().supply, Amount(12335));
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
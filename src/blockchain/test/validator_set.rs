@@ -0,0 +1,102 @@
+use super::*;
+
+#[test]
+fn test_validator_set_is_frozen_per_epoch() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    assert_eq!(
+        chain.get_validator_set().unwrap(),
+        chain.get_stakers().unwrap()
+    );
+
+    let tx = delegator.delegate(
+        "".into(),
+        validator3.get_address(),
+        Amount(20),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[tx], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    // Live stake has changed, but not the validators of the current epoch
+    assert_eq!(
+        chain.get_stakers().unwrap().first(),
+        Some(&(validator3.get_address(), Amount(45)))
+    );
+    assert!(chain
+        .get_validator_set()
+        .unwrap()
+        .iter()
+        .all(|(_, stake)| *stake == Amount(25)));
+
+    let draft = chain
+        .draft_block(50, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    assert_eq!(
+        chain.get_validator_set().unwrap(),
+        chain.get_stakers().unwrap()
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_validator_set_is_capped() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator3 = TxBuilder::new(&Vec::from("VALIDATOR3"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_validators = 1;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    assert_eq!(chain.get_validator_set().unwrap().len(), 1);
+    assert_eq!(chain.get_stakers().unwrap().len(), 3);
+
+    let tx = delegator.delegate(
+        "".into(),
+        validator3.get_address(),
+        Amount(20),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[tx], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+    let draft = chain
+        .draft_block(50, &[], &validator, &Default::default(), true)
+        .unwrap()
+        .unwrap();
+    chain.apply_block(&draft.block).unwrap();
+
+    // Only the top staker is allowed to validate
+    assert_eq!(
+        chain.get_validator_set().unwrap(),
+        vec![(validator3.get_address(), Amount(45))]
+    );
+    for ts in 50..100 {
+        assert!(matches!(
+            chain.validator_status(ts, &validator).unwrap(),
+            ValidatorProof::Unproven
+        ));
+    }
+    // Having all of the stake, the top staker wins every slot
+    assert!(matches!(
+        chain.validator_status(50, &validator3).unwrap(),
+        ValidatorProof::Proof { .. }
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
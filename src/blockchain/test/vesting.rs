@@ -0,0 +1,140 @@
+use super::*;
+
+#[test]
+fn test_vesting_is_released_linearly() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let team = TxBuilder::new(&Vec::from("TEAM"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    // Vestings should end after the current height
+    let expired = abc.vested_send(
+        "".into(),
+        team.get_address(),
+        Money::ziesha(100),
+        0,
+        1,
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&expired.tx, false),
+        Err(BlockchainError::InvalidVesting)
+    ));
+    let too_much = abc.vested_send(
+        "".into(),
+        team.get_address(),
+        Money::ziesha(10001),
+        2,
+        4,
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        chain.fork_on_ram().apply_tx(&too_much.tx, false),
+        Err(BlockchainError::BalanceInsufficient)
+    ));
+
+    let tx = abc.vested_send(
+        "".into(),
+        team.get_address(),
+        Money::ziesha(100),
+        2,
+        4,
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[tx], &validator, &Default::default(), true)?
+        .unwrap();
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(abc.get_address(), TokenId::Ziesha)?,
+        Amount(9900)
+    );
+
+    // Nothing is unlocked before the start height, then half of it in the middle
+    for (ts, unlocked) in [(10, 0), (15, 50), (20, 100)] {
+        let draft = chain
+            .draft_block(ts, &[], &validator, &Default::default(), true)?
+            .unwrap();
+        chain.apply_block(&draft.block)?;
+        assert_eq!(
+            chain.get_balance(team.get_address(), TokenId::Ziesha)?,
+            Amount(unlocked)
+        );
+        let vestings = chain.get_vestings(team.get_address())?;
+        if unlocked < 100 {
+            assert_eq!(vestings.len(), 1);
+            assert_eq!(vestings[0].1.unlocked, Amount(unlocked));
+            assert_eq!(vestings[0].1.locked(), Amount(100 - unlocked));
+        } else {
+            assert!(vestings.is_empty());
+        }
+    }
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
+
+#[test]
+fn test_time_locked_send() -> Result<(), BlockchainError> {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let team = TxBuilder::new(&Vec::from("TEAM"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let tx = abc.vested_send(
+        "".into(),
+        team.get_address(),
+        Money::ziesha(100),
+        3,
+        3,
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(5, &[tx], &validator, &Default::default(), true)?
+        .unwrap();
+    chain.apply_block(&draft.block)?;
+
+    for (ts, unlocked) in [(10, 0), (15, 100)] {
+        let draft = chain
+            .draft_block(ts, &[], &validator, &Default::default(), true)?
+            .unwrap();
+        chain.apply_block(&draft.block)?;
+        assert_eq!(
+            chain.get_balance(team.get_address(), TokenId::Ziesha)?,
+            Amount(unlocked)
+        );
+    }
+
+    // Unlocked tokens are spendable
+    let spend = team.create_transaction(
+        "".into(),
+        abc.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+    let draft = chain
+        .draft_block(20, &[spend], &validator, &Default::default(), true)?
+        .unwrap();
+    assert_eq!(draft.block.body.len(), 1);
+    chain.apply_block(&draft.block)?;
+    assert_eq!(
+        chain.get_balance(abc.get_address(), TokenId::Ziesha)?,
+        Amount(10000)
+    );
+
+    rollback_till_empty(&mut chain)?;
+    Ok(())
+}
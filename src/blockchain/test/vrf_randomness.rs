@@ -23,7 +23,13 @@ fn test_vrf_randomness_changes() {
     ];
     for i in 0..100 {
         let draft = chain
-            .draft_block(1700000000 + i * 5, &[], &validator, true)
+            .draft_block(
+                1700000000 + i * 5,
+                &[],
+                &validator,
+                &Default::default(),
+                true,
+            )
             .unwrap()
             .unwrap();
         chain.apply_block(&draft.block).unwrap();
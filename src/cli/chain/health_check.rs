@@ -15,17 +15,27 @@ pub fn health_check(conf: &BazukaConfig) {
     let rdb = ReadOnlyLevelDbKvStore::read_only(&conf.db, 64).unwrap();
     let db = rdb.snapshot();
     let chain = KvStoreChain::new(db, bazuka::config::blockchain::get_blockchain_config()).unwrap();
+    // Blocks before the pruned height have no rollback data, so the database can only be
+    // checked to be empty after rolling back everything when it is not pruned
+    let pruned_height = chain.get_pruned_height().unwrap();
     let mut fork = chain.fork_on_ram();
-    while fork.get_height().unwrap() != 0 {
-        fork.rollback().unwrap();
+    let mut rollback_validity_check = true;
+    while fork.get_height().unwrap() > pruned_height {
+        if let Err(e) = fork.rollback() {
+            println!("Error while rolling back: {}", e);
+            rollback_validity_check = false;
+            break;
+        }
+    }
+    if rollback_validity_check && pruned_height == 0 {
+        rollback_validity_check = fork
+            .db()
+            .pairs("".into())
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>()
+            .is_empty();
     }
-    let rollback_validity_check = fork
-        .db()
-        .pairs("".into())
-        .unwrap()
-        .into_iter()
-        .collect::<Vec<_>>()
-        .is_empty();
     let mut sum_mpn: Amount = 0.into();
     for mpn_acc in chain.get_mpn_accounts(0, 10000).unwrap() {
         for money in mpn_acc.1.tokens.values() {
@@ -38,14 +48,21 @@ pub fn health_check(conf: &BazukaConfig) {
         == chain
             .get_contract_balance(mpn_contract_id, TokenId::Ziesha)
             .unwrap();
-    let currency_in_circulation_check =
-        chain.currency_in_circulation().unwrap() == Amount::from(2000000000000000000);
+    // Burned base fees are no longer in circulation
+    let currency_in_circulation_check = chain.currency_in_circulation().unwrap()
+        + chain.get_burned().unwrap()
+        == Amount::from(2000000000000000000);
     println!(
-        "Rollback validity check: {}",
+        "Rollback validity check: {}{}",
         if rollback_validity_check {
             "PASS".bright_green()
         } else {
             "FAIL".bright_red()
+        },
+        if pruned_height > 0 {
+            format!(" (Rolled back to the pruned height {})", pruned_height)
+        } else {
+            "".into()
         }
     );
     println!(
@@ -0,0 +1,59 @@
+use crate::cli::BazukaConfig;
+use bazuka::blockchain::Blockchain;
+use bazuka::{
+    blockchain::{replay::replay, BlockchainError, KvStoreChain},
+    config::blockchain::get_blockchain_config,
+    db::ReadOnlyLevelDbKvStore,
+};
+use colored::Colorize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+pub fn replay_chain(log: Option<PathBuf>, conf: &BazukaConfig) {
+    let rdb = ReadOnlyLevelDbKvStore::read_only(&conf.db, 64).unwrap();
+    let db = rdb.snapshot();
+    let chain = KvStoreChain::new(db, get_blockchain_config()).unwrap();
+    let height = chain.get_height().unwrap();
+    let mut log = log.map(|path| BufWriter::new(File::create(path).unwrap()));
+    let diverged = replay(&chain, |block| {
+        if let Some(log) = &mut log {
+            writeln!(
+                log,
+                "{} {} {}",
+                block.height,
+                hex::encode(block.header_hash),
+                hex::encode(block.checksum)
+            )
+            .unwrap();
+        }
+        if block.height % 1000 == 0 {
+            println!("Replayed {}/{}...", block.height, height);
+        }
+    });
+    match diverged {
+        Err(BlockchainError::BlockPruned) => {
+            println!(
+                "Replay check: {} (Blocks before height {} are pruned)",
+                "UNAVAILABLE".bright_yellow(),
+                chain.get_pruned_height().unwrap()
+            );
+        }
+        Err(e) => {
+            println!("Replay check: {} ({})", "FAIL".bright_red(), e);
+        }
+        Ok(Some(block)) => {
+            println!(
+                "Replay check: {} (Diverged at height {})",
+                "FAIL".bright_red(),
+                block.height
+            );
+            for key in block.diverged_keys {
+                println!("{}", key);
+            }
+        }
+        Ok(None) => {
+            println!("Replay check: {}", "PASS".bright_green());
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::cli::BazukaConfig;
+use bazuka::blockchain::Blockchain;
+use bazuka::{
+    blockchain::{snapshot::Snapshot, KvStoreChain},
+    config::blockchain::{get_blockchain_config, MAX_REORG_DEPTH},
+    core::{hash::Hash, Hasher},
+    db::{LevelDbKvStore, ReadOnlyLevelDbKvStore},
+};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+pub fn snapshot_export(
+    height: Option<u64>,
+    retention: Option<u64>,
+    output: PathBuf,
+    conf: &BazukaConfig,
+) {
+    let rdb = ReadOnlyLevelDbKvStore::read_only(&conf.db, 64).unwrap();
+    let db = rdb.snapshot();
+    let chain = KvStoreChain::new(db, get_blockchain_config()).unwrap();
+    let height = height.unwrap_or(chain.get_height().unwrap());
+    let snapshot = Snapshot::export(&chain, height, retention.unwrap_or(MAX_REORG_DEPTH)).unwrap();
+    bincode::serialize_into(BufWriter::new(File::create(&output).unwrap()), &snapshot).unwrap();
+    println!("Height: {}", height);
+    println!("Header hash: {}", hex::encode(snapshot.header.hash()));
+    println!(
+        "Checksum: {}",
+        snapshot.checksum(get_blockchain_config()).unwrap()
+    );
+}
+
+pub fn snapshot_import(input: PathBuf, hash: String, checksum: String, conf: &BazukaConfig) {
+    let trusted_hash =
+        <Hasher as Hash>::Output::try_from(hex::decode(hash).expect("Invalid header hash!"))
+            .expect("Invalid header hash!");
+    let snapshot: Snapshot =
+        bincode::deserialize_from(BufReader::new(File::open(&input).unwrap())).unwrap();
+    let chain = snapshot
+        .import(
+            LevelDbKvStore::new(&conf.db, 64).unwrap(),
+            get_blockchain_config(),
+            trusted_hash,
+            &checksum,
+        )
+        .unwrap();
+    println!("Imported! Height: {}", chain.get_height().unwrap());
+}
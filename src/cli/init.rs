@@ -45,6 +45,7 @@ pub async fn init(
                     .unwrap_or_else(|| PeerAddress(SocketAddr::from((public_ip, DEFAULT_PORT)))),
                 db: db.unwrap_or_else(|| home::home_dir().unwrap().join(Path::new(".bazuka"))),
                 mpn_workers: vec![],
+                accepted_fee_tokens: vec![],
             })
             .unwrap(),
         )
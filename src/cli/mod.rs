@@ -16,11 +16,15 @@ use {
 use {
     bazuka::client::{NodeError, PeerAddress},
     bazuka::config,
-    bazuka::core::{Address, Decimal, GeneralAddress, MpnAddress, TokenId},
+    bazuka::core::{
+        Address, Amount, ConfigParam, Decimal, ExchangeRate, GeneralAddress, HtlcId, MpnAddress,
+        ProposalId, TokenId,
+    },
     bazuka::mpn::MpnWorker,
     bazuka::wallet::WalletCollection,
     colored::Colorize,
     serde::{Deserialize, Serialize},
+    std::collections::HashMap,
     std::net::SocketAddr,
     std::path::{Path, PathBuf},
     structopt::StructOpt,
@@ -60,6 +64,41 @@ impl TryInto<MpnWorker> for BazukaConfigMpnWorker {
     }
 }
 
+// `token` units of the token are accepted as fee in place of `ziesha` units of Ziesha
+#[cfg(feature = "client")]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BazukaConfigFeeToken {
+    token_id: String,
+    ziesha: u64,
+    token: u64,
+}
+
+#[cfg(feature = "client")]
+#[derive(Debug)]
+pub struct InvalidFeeToken(String);
+
+#[cfg(feature = "client")]
+impl std::fmt::Display for InvalidFeeToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid token-id in accepted_fee_tokens: {}", self.0)
+    }
+}
+
+impl TryInto<(TokenId, ExchangeRate)> for BazukaConfigFeeToken {
+    type Error = InvalidFeeToken;
+    fn try_into(self) -> Result<(TokenId, ExchangeRate), InvalidFeeToken> {
+        Ok((
+            self.token_id
+                .parse()
+                .map_err(|_| InvalidFeeToken(self.token_id.clone()))?,
+            ExchangeRate {
+                ziesha: Amount(self.ziesha),
+                token: Amount(self.token),
+            },
+        ))
+    }
+}
+
 #[cfg(feature = "client")]
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BazukaConfig {
@@ -68,10 +107,18 @@ pub struct BazukaConfig {
     bootstrap: Vec<PeerAddress>,
     db: PathBuf,
     mpn_workers: Vec<BazukaConfigMpnWorker>,
+    #[serde(default)]
+    accepted_fee_tokens: Vec<BazukaConfigFeeToken>,
 }
 
 #[cfg(feature = "client")]
 impl BazukaConfig {
+    fn accepted_fee_tokens(&self) -> Result<HashMap<TokenId, ExchangeRate>, InvalidFeeToken> {
+        self.accepted_fee_tokens
+            .iter()
+            .map(|t| t.clone().try_into())
+            .collect()
+    }
     fn random_node(&self) -> PeerAddress {
         PeerAddress(SocketAddr::from(([0, 0, 0, 0], DEFAULT_PORT)))
         /*self.bootstrap
@@ -104,6 +151,8 @@ enum WalletOptions {
         decimals: u8,
         #[structopt(long)]
         mintable: bool,
+        #[structopt(long)]
+        metadata: Option<String>,
         #[structopt(long, default_value = "0")]
         fee: Decimal,
     },
@@ -121,6 +170,93 @@ enum WalletOptions {
         amount: Decimal,
         #[structopt(long, default_value = "0")]
         fee: Decimal,
+        /// Token to pay the fee with (Validators only accept some tokens)
+        #[structopt(long)]
+        fee_token_id: Option<TokenId>,
+        #[structopt(long)]
+        ttl: Option<u64>,
+    },
+    /// Send money that unlocks linearly between two heights (At once if they are equal)
+    VestedSend {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long)]
+        start: u64,
+        #[structopt(long)]
+        end: u64,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Lock funds that the recipient can claim by revealing the preimage of a hashlock
+    CreateHtlc {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long)]
+        hashlock: String,
+        #[structopt(long)]
+        timeout_height: u64,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Claim the funds of a HTLC by revealing the preimage of its hashlock
+    ClaimHtlc {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        htlc_id: HtlcId,
+        #[structopt(long)]
+        preimage: String,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Return the funds of a timed out HTLC to its sender
+    RefundHtlc {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        htlc_id: HtlcId,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Allow another account to spend tokens from your account
+    Approve {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        spender: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Send tokens from an account that has approved you as a spender
+    SpendFrom {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        owner: Address,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
     },
     /// Register your validator
     RegisterValidator {
@@ -141,6 +277,8 @@ enum WalletOptions {
         amount: Decimal,
         #[structopt(long, default_value = "0")]
         fee: Decimal,
+        #[structopt(long)]
+        ttl: Option<u64>,
     },
     /// Automatically re-delegate a ratio of staking rewards
     AutoDelegate {
@@ -164,6 +302,88 @@ enum WalletOptions {
         #[structopt(long, default_value = "0")]
         fee: Decimal,
     },
+    /// Claim the rewards earned by a delegation
+    ClaimReward {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        from: Address,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Let your validator, jailed for missing its slots, validate again
+    Unjail {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Propose changing parameters of the blockchain (E.g --change max_block_size=1000000)
+    Propose {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long = "change")]
+        changes: Vec<ConfigParam>,
+        #[structopt(long)]
+        activation_height: u64,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Vote on a proposal with the stake of your validator
+    Vote {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        proposal_id: ProposalId,
+        #[structopt(long)]
+        reject: bool,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Create an account spendable by a threshold of signers
+    CreateMultisig {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long = "signer")]
+        signers: Vec<Address>,
+        #[structopt(long)]
+        threshold: u8,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Create a transaction from a multisig account, signed only by you
+    MultisigSend {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        multisig: Address,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Co-sign a multisig transaction, and broadcast it if it has enough signatures
+    MultisigSign {
+        #[structopt(long)]
+        tx: String,
+        #[structopt(long)]
+        broadcast: bool,
+    },
+    /// Move funds of a delegation to another validator, without unbonding
+    Redelegate {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        from: Address,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
     /// Resets wallet nonces
     Reset {},
     /// Get info and balances of the wallet
@@ -171,6 +391,13 @@ enum WalletOptions {
         #[structopt(long)]
         validator: bool,
     },
+    /// Show the on-chain transactions touching the wallet, newest first
+    History {
+        #[structopt(long)]
+        validator: bool,
+        #[structopt(long, default_value = "0")]
+        page: usize,
+    },
     /// Resend pending transactions
     ResendPending {},
 }
@@ -183,6 +410,12 @@ enum NodeCliOptions {
     Start {
         #[structopt(long)]
         client_only: bool,
+        /// Only sync and verify the headers
+        #[structopt(long)]
+        light: bool,
+        /// Only keep the bodies of this many recent blocks
+        #[structopt(long)]
+        prune: Option<u64>,
         #[structopt(long)]
         discord_handle: Option<String>,
         #[structopt(long)]
@@ -208,6 +441,41 @@ enum ChainCliOptions {
     DbQuery { prefix: String },
     /// Check health of the blockchain
     HealthCheck {},
+    /// Execute the blocks again and compare with the database
+    Replay {
+        /// Write the checksums of the heights into a file
+        #[structopt(long)]
+        log: Option<PathBuf>,
+    },
+    /// Export/import the state of the blockchain
+    Snapshot(SnapshotCliOptions),
+}
+
+#[derive(StructOpt)]
+#[cfg(feature = "client")]
+enum SnapshotCliOptions {
+    /// Write the state at a height into a file
+    Export {
+        #[structopt(long)]
+        height: Option<u64>,
+        /// Keep the bodies and rollback data of this many recent blocks (Defaults to the
+        /// expected max reorg depth, imported nodes can't roll back any deeper)
+        #[structopt(long)]
+        retention: Option<u64>,
+        #[structopt(long, default_value = "bazuka.snapshot")]
+        output: PathBuf,
+    },
+    /// Initialize the database from a snapshot of a trusted header
+    Import {
+        #[structopt(long, default_value = "bazuka.snapshot")]
+        input: PathBuf,
+        /// Hash of the header, from a trusted source
+        #[structopt(long)]
+        hash: String,
+        /// Checksum printed on export, from the same trusted source
+        #[structopt(long)]
+        checksum: String,
+    },
 }
 
 #[derive(StructOpt)]
@@ -244,12 +512,15 @@ enum CliOptions {
 }
 
 #[cfg(feature = "node")]
+#[allow(clippy::too_many_arguments)]
 async fn run_node<K: KvStore, B: Blockchain<K>>(
     blockchain: B,
     bazuka_config: BazukaConfig,
     wallet: WalletCollection,
     social_profiles: SocialProfiles,
     client_only: bool,
+    accepted_fee_tokens: HashMap<TokenId, ExchangeRate>,
+    prune_retention: Option<u64>,
     network: String,
 ) -> Result<(), NodeError> {
     let address = if client_only {
@@ -281,7 +552,11 @@ async fn run_node<K: KvStore, B: Blockchain<K>>(
     // Async loop that is responsible for answering external requests and gathering
     // data from external world through a heartbeat loop.
     let node = node_create(
-        config::node::get_node_options(),
+        bazuka::node::NodeOptions {
+            accepted_fee_tokens,
+            prune_retention,
+            ..config::node::get_node_options()
+        },
         &network,
         address,
         bootstrap_nodes,
@@ -391,12 +666,43 @@ pub async fn initialize_cli() {
             ChainCliOptions::HealthCheck {} => {
                 crate::cli::chain::health_check(&conf.expect(BAZUKA_NOT_INITILIZED));
             }
+            ChainCliOptions::Replay { log } => {
+                crate::cli::chain::replay_chain(log, &conf.expect(BAZUKA_NOT_INITILIZED));
+            }
+            ChainCliOptions::Snapshot(snapshot_opts) => match snapshot_opts {
+                SnapshotCliOptions::Export {
+                    height,
+                    retention,
+                    output,
+                } => {
+                    crate::cli::chain::snapshot_export(
+                        height,
+                        retention,
+                        output,
+                        &conf.expect(BAZUKA_NOT_INITILIZED),
+                    );
+                }
+                SnapshotCliOptions::Import {
+                    input,
+                    hash,
+                    checksum,
+                } => {
+                    crate::cli::chain::snapshot_import(
+                        input,
+                        hash,
+                        checksum,
+                        &conf.expect(BAZUKA_NOT_INITILIZED),
+                    );
+                }
+            },
         },
         #[cfg(feature = "node")]
         CliOptions::Node(node_opts) => match node_opts {
             NodeCliOptions::Start {
                 discord_handle,
                 client_only,
+                light,
+                prune,
                 dev,
                 ram,
                 small_mpn,
@@ -404,6 +710,8 @@ pub async fn initialize_cli() {
                 crate::cli::node::start(
                     discord_handle,
                     client_only,
+                    light,
+                    prune,
                     conf.expect(BAZUKA_NOT_INITILIZED),
                     wallet.expect(BAZUKA_NOT_INITILIZED),
                     ram,
@@ -468,6 +776,7 @@ pub async fn initialize_cli() {
                 supply,
                 decimals,
                 mintable,
+                metadata,
                 fee,
             } => {
                 crate::cli::wallet::new_token(
@@ -477,6 +786,7 @@ pub async fn initialize_cli() {
                     supply,
                     decimals,
                     mintable,
+                    metadata,
                     fee,
                     conf.expect(BAZUKA_NOT_INITILIZED),
                     wallet.expect(BAZUKA_NOT_INITILIZED),
@@ -490,7 +800,9 @@ pub async fn initialize_cli() {
                 to,
                 amount,
                 fee,
+                fee_token_id,
                 token_id,
+                ttl,
             } => {
                 crate::cli::wallet::send(
                     memo,
@@ -498,10 +810,126 @@ pub async fn initialize_cli() {
                     to,
                     amount,
                     fee,
+                    fee_token_id,
+                    token_id,
+                    ttl,
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                )
+                .await;
+            }
+            WalletOptions::VestedSend {
+                memo,
+                to,
+                token_id,
+                amount,
+                start,
+                end,
+                fee,
+            } => {
+                crate::cli::wallet::vested_send(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    to,
+                    token_id,
+                    amount,
+                    start,
+                    end,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::CreateHtlc {
+                memo,
+                to,
+                token_id,
+                amount,
+                hashlock,
+                timeout_height,
+                fee,
+            } => {
+                crate::cli::wallet::create_htlc(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    to,
+                    token_id,
+                    amount,
+                    hashlock,
+                    timeout_height,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::ClaimHtlc {
+                memo,
+                htlc_id,
+                preimage,
+                fee,
+            } => {
+                crate::cli::wallet::claim_htlc(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    htlc_id,
+                    preimage,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::RefundHtlc { memo, htlc_id, fee } => {
+                crate::cli::wallet::refund_htlc(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    htlc_id,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::Approve {
+                memo,
+                spender,
+                token_id,
+                amount,
+                fee,
+            } => {
+                crate::cli::wallet::approve(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    spender,
                     token_id,
+                    amount,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::SpendFrom {
+                memo,
+                owner,
+                to,
+                token_id,
+                amount,
+                fee,
+            } => {
+                crate::cli::wallet::spend_from(
                     conf.expect(BAZUKA_NOT_INITILIZED),
                     wallet.expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
+                    memo,
+                    owner,
+                    to,
+                    token_id,
+                    amount,
+                    fee,
                 )
                 .await;
             }
@@ -540,6 +968,124 @@ pub async fn initialize_cli() {
                 )
                 .await;
             }
+            WalletOptions::ClaimReward { memo, from, fee } => {
+                crate::cli::wallet::claim_reward(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    from,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::Unjail { memo, fee } => {
+                crate::cli::wallet::unjail(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::Propose {
+                memo,
+                changes,
+                activation_height,
+                fee,
+            } => {
+                crate::cli::wallet::propose(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    changes,
+                    activation_height,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::Vote {
+                memo,
+                proposal_id,
+                reject,
+                fee,
+            } => {
+                crate::cli::wallet::vote(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    proposal_id,
+                    !reject,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::CreateMultisig {
+                memo,
+                signers,
+                threshold,
+                fee,
+            } => {
+                crate::cli::wallet::create_multisig(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    signers,
+                    threshold,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::MultisigSend {
+                memo,
+                multisig,
+                to,
+                amount,
+                fee,
+            } => {
+                crate::cli::wallet::multisig_send(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    memo,
+                    multisig,
+                    to,
+                    amount,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::MultisigSign { tx, broadcast } => {
+                crate::cli::wallet::multisig_sign(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    tx,
+                    broadcast,
+                )
+                .await;
+            }
+            WalletOptions::Redelegate {
+                memo,
+                amount,
+                from,
+                to,
+                fee,
+            } => {
+                crate::cli::wallet::redelegate(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    amount,
+                    from,
+                    to,
+                    fee,
+                )
+                .await;
+            }
             WalletOptions::AutoDelegate {
                 memo,
                 to,
@@ -562,6 +1108,7 @@ pub async fn initialize_cli() {
                 amount,
                 to,
                 fee,
+                ttl,
             } => {
                 crate::cli::wallet::delegate(
                     conf.expect(BAZUKA_NOT_INITILIZED),
@@ -571,6 +1118,7 @@ pub async fn initialize_cli() {
                     amount,
                     to,
                     fee,
+                    ttl,
                 )
                 .await;
             }
@@ -582,6 +1130,15 @@ pub async fn initialize_cli() {
                 )
                 .await;
             }
+            WalletOptions::History { validator, page } => {
+                crate::cli::wallet::history(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    validator,
+                    page,
+                )
+                .await;
+            }
             WalletOptions::Info { validator } => {
                 crate::cli::wallet::info(
                     conf.expect(BAZUKA_NOT_INITILIZED),
@@ -1,19 +1,39 @@
 use crate::cli::{run_node, BazukaConfig, CURRENT_NETWORK};
 
 use bazuka::{
-    blockchain::KvStoreChain, client::messages::SocialProfiles, config, db::LevelDbKvStore,
-    db::RamKvStore, wallet::WalletCollection,
+    blockchain::{
+        light::{HeaderChain, LightChain},
+        BlockchainError, KvStoreChain,
+    },
+    client::{messages::SocialProfiles, BazukaClient, Limit, NodeError},
+    common::*,
+    config,
+    core::GeneralTransaction,
+    db::{KvStore, LevelDbKvStore, RamKvStore},
+    wallet::{Wallet, WalletCollection},
 };
+use colored::Colorize;
+use tokio::try_join;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn start(
     discord_handle: Option<String>,
     client_only: bool,
+    light: bool,
+    prune: Option<u64>,
     conf: BazukaConfig,
     mut wallet: WalletCollection,
     ram: bool,
     dev: bool,
     small_mpn: bool,
 ) {
+    let accepted_fee_tokens = match conf.accepted_fee_tokens() {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            println!("{} {}", "Error:".bright_red(), e);
+            return;
+        }
+    };
     let blockchain_conf = if dev {
         let validator_wallet = wallet.validator().tx_builder();
         let user_wallet = wallet.user(0).tx_builder();
@@ -22,7 +42,27 @@ pub async fn start(
         config::blockchain::get_blockchain_config()
     };
 
-    if ram {
+    if light {
+        if ram {
+            start_light(
+                LightChain::new(RamKvStore::new(), blockchain_conf).unwrap(),
+                conf,
+                wallet,
+            )
+            .await;
+        } else {
+            start_light(
+                LightChain::new(
+                    LevelDbKvStore::new(&conf.db.join("light"), 64).unwrap(),
+                    blockchain_conf,
+                )
+                .unwrap(),
+                conf,
+                wallet,
+            )
+            .await;
+        }
+    } else if ram {
         run_node(
             KvStoreChain::new(RamKvStore::new(), blockchain_conf).unwrap(),
             conf.clone(),
@@ -31,6 +71,8 @@ pub async fn start(
                 discord: discord_handle,
             },
             client_only,
+            accepted_fee_tokens.clone(),
+            prune,
             "dev".into(),
         )
         .await
@@ -44,9 +86,111 @@ pub async fn start(
                 discord: discord_handle,
             },
             client_only,
+            accepted_fee_tokens,
+            prune,
             CURRENT_NETWORK.into(),
         )
         .await
         .unwrap();
     }
 }
+
+const LIGHT_SYNC_BATCH: u64 = 100;
+
+// Syncs the headers from a full node, going back a batch whenever they don't connect.
+// Validators of each epoch are proven at its first header, before the headers after it
+// are accepted.
+async fn start_light<K: KvStore>(
+    mut chain: LightChain<K>,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+) {
+    let wallet = wallet.user(0).clone();
+    let (req_loop, client) = BazukaClient::connect(
+        wallet.tx_builder().get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(5 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let mut since = chain.get_height().unwrap();
+            loop {
+                let headers = client.get_headers(since, LIGHT_SYNC_BATCH).await?.headers;
+                match chain.extend(since, &headers) {
+                    Ok(()) => {}
+                    Err(BlockchainError::WeakChain) => {}
+                    Err(BlockchainError::InvalidParentHash) => {
+                        since = std::cmp::max(since.saturating_sub(LIGHT_SYNC_BATCH), 1);
+                        continue;
+                    }
+                    Err(BlockchainError::ValidatorsUnknown(epoch)) => {
+                        // Headers up to the first one of the epoch can still be checked
+                        if let Some(first) = headers.iter().position(|h| chain.epoch(h) == epoch) {
+                            if let Err(e) = chain.extend(since, &headers[..=first]) {
+                                println!("Invalid headers: {}", e);
+                            }
+                        }
+                        let resp = client.get_validators_proof(Some(epoch)).await?;
+                        if let Err(e) = chain.update_validators(&resp.header, &resp.proof) {
+                            println!("Invalid validators: {}", e);
+                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                        }
+                        since = chain.get_height().unwrap();
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("Invalid headers: {}", e);
+                    }
+                }
+                let height = chain.get_height().unwrap();
+                println!("Height: {}", height);
+                verify_wallet(&chain, &client, &wallet).await?;
+                if height == since {
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+                since = height;
+            }
+            #[allow(unreachable_code)]
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+// Balances and transactions of the wallet, as answered by the full node, are only shown
+// once they are proven against the synced headers
+async fn verify_wallet<K: KvStore>(
+    chain: &LightChain<K>,
+    client: &BazukaClient,
+    wallet: &Wallet,
+) -> Result<(), NodeError> {
+    let address = wallet.tx_builder().get_address();
+    for token_id in wallet.get_tokens().iter() {
+        let resp = client.get_account_proof(address.clone(), *token_id).await?;
+        if chain.verify_balance(&address, *token_id, resp.balance, &resp.header, &resp.proof)? {
+            println!(
+                "Balance of {} at #{}: {}",
+                token_id,
+                resp.header.number,
+                u64::from(resp.balance)
+            );
+        }
+    }
+    for tx in wallet.txs.values().flatten() {
+        if let GeneralTransaction::TransactionAndDelta(tx_delta) = tx {
+            let resp = client.get_transaction_proof(tx_delta.tx.hash()).await?;
+            if let Some(header) = resp.header {
+                if chain.verify_transaction(&tx_delta.tx, &header, &resp.proof)? {
+                    println!(
+                        "Transaction {} included in #{}",
+                        hex::encode(tx_delta.tx.hash()),
+                        header.number
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::core::{Address, Decimal, Money, NonceGroup, TokenId};
+use bazuka::wallet::WalletCollection;
+
+pub async fn approve(
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+    memo: Option<String>,
+    spender: Address,
+    token_id: Option<TokenId>,
+    amount: Decimal,
+    fee: Decimal,
+) -> () {
+    let tx_builder = wallet.user(0).tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    let tkn = if let Some(token_id) = token_id {
+        if !wallet.user(0).get_tokens().contains(&token_id) {
+            panic!("Token does not exist in your wallet!");
+        } else {
+            token_id
+        }
+    } else {
+        TokenId::Ziesha
+    };
+    try_join!(
+        async move {
+            let tx_builder = tx_builder
+                .with_chain_id(bazuka::config::blockchain::get_blockchain_config().chain_id());
+            let tkn_decimals = client
+                .get_token(tkn)
+                .await?
+                .token
+                .expect("Token not found!")
+                .decimals;
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+
+            let new_nonce = wallet
+                .user(0)
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let tx = tx_builder.approve(
+                memo.unwrap_or_default(),
+                spender,
+                Money {
+                    amount: amount.to_amount(tkn_decimals),
+                    token_id: tkn,
+                },
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.user(0).add_tx(tx.clone().into());
+                wallet.save(wallet_path).unwrap();
+                println!("Sent");
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
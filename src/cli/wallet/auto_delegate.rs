@@ -25,6 +25,8 @@ pub async fn auto_delegate(
     );
     try_join!(
         async move {
+            let tx_builder = tx_builder
+                .with_chain_id(bazuka::config::blockchain::get_blockchain_config().chain_id());
             let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
 
             let new_nonce = wallet
@@ -17,6 +17,7 @@ pub async fn delegate(
     amount: Decimal,
     to: Address,
     fee: Decimal,
+    ttl: Option<u64>,
 ) -> () {
     let tx_builder = wallet.user(0).tx_builder();
     if tx_builder.get_address() == to {
@@ -36,6 +37,15 @@ pub async fn delegate(
     );
     try_join!(
         async move {
+            let tx_builder = tx_builder
+                .with_chain_id(bazuka::config::blockchain::get_blockchain_config().chain_id());
+            // Transaction stays valid for `ttl` blocks after the current height
+            let valid_until = if let Some(ttl) = ttl {
+                Some(client.stats().await?.height + ttl)
+            } else {
+                None
+            };
+            let tx_builder = tx_builder.with_valid_until(valid_until);
             let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
 
             let new_nonce = wallet
@@ -0,0 +1,65 @@
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::wallet::WalletCollection;
+use colored::Colorize;
+
+pub async fn history(
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    validator: bool,
+    page: usize,
+) -> () {
+    let tx_builder = if validator {
+        wallet.validator().tx_builder()
+    } else {
+        wallet.user(0).tx_builder()
+    };
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let height = client.stats().await?.height;
+            let history = client
+                .get_explorer_history(tx_builder.get_address(), page)
+                .await?
+                .history;
+            println!(
+                "{}",
+                format!("History (Page {})\n---------", page).bright_green()
+            );
+            if history.is_empty() {
+                println!("No transactions found!");
+            }
+            for entry in history {
+                let kind = serde_json::to_value(&entry.tx.data)
+                    .ok()
+                    .and_then(|v| v["type"].as_str().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                println!(
+                    "{} {} ({} confirmations)",
+                    format!("#{}:{}", entry.block, entry.index).bright_yellow(),
+                    kind,
+                    height - entry.block
+                );
+                println!("\tHash: {}", entry.hash);
+                println!(
+                    "\tFrom: {}",
+                    entry.tx.src.unwrap_or_else(|| "Treasury".into())
+                );
+                if !entry.tx.memo.is_empty() {
+                    println!("\tMemo: {}", entry.tx.memo);
+                }
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
@@ -3,7 +3,7 @@ use tokio::try_join;
 use crate::cli::{BazukaConfig, CURRENT_NETWORK};
 use bazuka::client::{Limit, NodeError};
 use bazuka::common::*;
-use bazuka::core::{MpnAddress, NonceGroup};
+use bazuka::core::{Amount, MpnAddress, NonceGroup};
 use bazuka::wallet::WalletCollection;
 use bazuka::{client::BazukaClient, core::TokenId};
 use colored::Colorize;
@@ -139,6 +139,13 @@ pub async fn info(conf: BazukaConfig, mut wallet: WalletCollection, validator: b
                                 format!(" {} (Token-Id: {})", inf.symbol, id)
                             }
                         );
+                        if inf.locked > Amount(0) {
+                            println!(
+                                "\t(Locked in vestings: {})",
+                                inf.locked
+                                    .display_by_decimals(tokens.get(id).unwrap().decimals)
+                            );
+                        }
                     }
                 }
                 if let Some(nonce) = curr_nonce {
@@ -160,10 +167,17 @@ pub async fn info(conf: BazukaConfig, mut wallet: WalletCollection, validator: b
                     println!();
                     println!("{}", "Delegatees\n---------".bright_green());
                     for (addr, amount) in delegations.delegatees.iter() {
+                        let pending_reward = delegations
+                            .pending_rewards
+                            .get(addr)
+                            .cloned()
+                            .unwrap_or_default();
                         println!(
-                            "You -> {} ({}{})",
+                            "You -> {} ({}{}, Pending reward: {}{})",
                             addr,
                             amount.display_by_decimals(bazuka::config::UNIT_ZEROS),
+                            bazuka::config::SYMBOL,
+                            pending_reward.display_by_decimals(bazuka::config::UNIT_ZEROS),
                             bazuka::config::SYMBOL
                         );
                     }
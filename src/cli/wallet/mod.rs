@@ -1,21 +1,51 @@
 pub mod add_token;
+pub mod approve;
 pub mod auto_delegate;
+pub mod claim_htlc;
+pub mod claim_reward;
+pub mod create_htlc;
+pub mod create_multisig;
 pub mod delegate;
+pub mod history;
 pub mod info;
+pub mod multisig_send;
+pub mod multisig_sign;
 pub mod new_token;
+pub mod propose;
+pub mod redelegate;
+pub mod refund_htlc;
 pub mod register_validator;
 pub mod resend_pending;
 pub mod reset;
 pub mod send;
+pub mod spend_from;
 pub mod undelegate;
+pub mod unjail;
+pub mod vested_send;
+pub mod vote;
 
 pub use add_token::*;
+pub use approve::*;
 pub use auto_delegate::*;
+pub use claim_htlc::*;
+pub use claim_reward::*;
+pub use create_htlc::*;
+pub use create_multisig::*;
 pub use delegate::*;
+pub use history::*;
 pub use info::*;
+pub use multisig_send::*;
+pub use multisig_sign::*;
 pub use new_token::*;
+pub use propose::*;
+pub use redelegate::*;
+pub use refund_htlc::*;
 pub use register_validator::*;
 pub use resend_pending::*;
 pub use reset::*;
 pub use send::*;
+pub use spend_from::*;
 pub use undelegate::*;
+pub use unjail::*;
+pub use vested_send::*;
+pub use vote::*;
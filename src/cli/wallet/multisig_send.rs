@@ -0,0 +1,58 @@
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::core::{Address, Decimal, Money, RegularSendEntry, TokenId};
+use bazuka::wallet::WalletCollection;
+
+// Prints a transaction of a multisig account signed by this wallet, to be co-signed by
+// the other signers through `multisig-sign`
+pub async fn multisig_send(
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    memo: Option<String>,
+    multisig: Address,
+    to: Address,
+    amount: Decimal,
+    fee: Decimal,
+) -> () {
+    let tx_builder = wallet.user(0).tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let tx_builder = tx_builder
+                .with_chain_id(bazuka::config::blockchain::get_blockchain_config().chain_id());
+            let acc = client.get_account(multisig.clone()).await?;
+            let multisig = acc.multisig.expect("Multisig account not found!");
+
+            let tx = tx_builder
+                .create_multisig_transaction(
+                    memo.unwrap_or_default(),
+                    &multisig,
+                    vec![RegularSendEntry {
+                        dst: to,
+                        amount: Money {
+                            amount: amount.to_amount(bazuka::config::UNIT_ZEROS),
+                            token_id: TokenId::Ziesha,
+                        },
+                    }],
+                    Money {
+                        amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                        token_id: TokenId::Ziesha,
+                    },
+                    acc.nonce + 1,
+                )
+                .expect("You are not a signer of this multisig!");
+            println!("{}", hex::encode(bincode::serialize(&tx).unwrap()));
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
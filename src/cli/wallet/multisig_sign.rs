@@ -0,0 +1,56 @@
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::core::{Transaction, TransactionAndDelta};
+use bazuka::wallet::WalletCollection;
+
+// Adds the signature of this wallet to a partially signed multisig transaction
+pub async fn multisig_sign(
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    tx: String,
+    broadcast: bool,
+) -> () {
+    let tx_builder = wallet.user(0).tx_builder();
+    let mut tx: Transaction = bincode::deserialize(&hex::decode(tx).expect("Invalid transaction!"))
+        .expect("Invalid transaction!");
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let tx_builder = tx_builder
+                .with_chain_id(bazuka::config::blockchain::get_blockchain_config().chain_id());
+            let multisig = client
+                .get_account(tx.src.clone().unwrap_or_default())
+                .await?
+                .multisig
+                .expect("Multisig account not found!");
+            if !tx_builder.cosign_tx(&mut tx, &multisig) {
+                panic!("You are not a signer of this multisig!");
+            }
+
+            if broadcast {
+                let tx = TransactionAndDelta {
+                    tx,
+                    state_delta: None,
+                };
+                if let Some(err) = client.transact(tx.into()).await?.error {
+                    println!("Error: {}", err);
+                } else {
+                    println!("Sent");
+                }
+            } else {
+                println!("{}", hex::encode(bincode::serialize(&tx).unwrap()));
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
@@ -14,6 +14,7 @@ pub async fn new_token(
     supply: Decimal,
     decimals: u8,
     mintable: bool,
+    metadata: Option<String>,
     fee: Decimal,
     conf: BazukaConfig,
     mut wallet: WalletCollection,
@@ -28,6 +29,8 @@ pub async fn new_token(
     );
     try_join!(
         async move {
+            let tx_builder = tx_builder
+                .with_chain_id(bazuka::config::blockchain::get_blockchain_config().chain_id());
             let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
 
             let new_nonce = wallet
@@ -41,6 +44,7 @@ pub async fn new_token(
                 supply.to_amount(decimals),
                 decimals,
                 mintable.then(|| tx_builder.get_address()),
+                metadata,
                 Money {
                     amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
                     token_id: TokenId::Ziesha,
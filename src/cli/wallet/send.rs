@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use crate::cli::{BazukaConfig, CURRENT_NETWORK};
 use bazuka::wallet::WalletCollection;
 use bazuka::{
+    blockchain::Feature,
     client::{BazukaClient, Limit, NodeError},
     common::*,
     config,
@@ -16,15 +17,16 @@ pub async fn send(
     to: GeneralAddress,
     amount: Decimal,
     fee: Decimal,
+    fee_token_id: Option<TokenId>,
     token_id: Option<TokenId>,
+    ttl: Option<u64>,
     conf: BazukaConfig,
     mut wallet: WalletCollection,
     wallet_path: &PathBuf,
 ) {
     let tx_builder = wallet.user(0).tx_builder();
-    let mpn_contract_id = config::blockchain::get_blockchain_config()
-        .mpn_config
-        .mpn_contract_id;
+    let blockchain_config = config::blockchain::get_blockchain_config();
+    let mpn_contract_id = blockchain_config.mpn_config.mpn_contract_id;
 
     let (req_loop, client) = BazukaClient::connect(
         tx_builder.get_priv_key(),
@@ -44,12 +46,32 @@ pub async fn send(
 
     try_join!(
         async move {
+            let chain_id = blockchain_config.chain_id();
+            let height = client.stats().await?.height;
+            // MPN transactions are only accepted when bound exactly as the node expects
+            let mpn_chain_id = blockchain_config
+                .fork_schedule
+                .is_active(Feature::MpnReplayProtection, height)
+                .then_some(chain_id);
+            // Transaction stays valid for `ttl` blocks after the current height
+            let valid_until = ttl.map(|ttl| height + ttl);
+            let tx_builder = tx_builder
+                .with_chain_id(chain_id)
+                .with_valid_until(valid_until)
+                .with_mpn_chain_id(mpn_chain_id);
             let tkn_decimals = client
                 .get_token(tkn)
                 .await?
                 .token
                 .expect("Token not found!")
                 .decimals;
+            let fee_tkn = fee_token_id.unwrap_or(TokenId::Ziesha);
+            let fee_tkn_decimals = client
+                .get_token(fee_tkn)
+                .await?
+                .token
+                .expect("Fee token not found!")
+                .decimals;
             match from {
                 GeneralAddress::ChainAddress(from) => {
                     if tx_builder.get_address() != from {
@@ -73,8 +95,8 @@ pub async fn send(
                                     token_id: tkn,
                                 },
                                 Money {
-                                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
-                                    token_id: TokenId::Ziesha,
+                                    amount: fee.to_amount(fee_tkn_decimals),
+                                    token_id: fee_tkn,
                                 },
                                 new_nonce,
                             );
@@ -163,8 +185,8 @@ pub async fn send(
                                     token_id: tkn,
                                 },
                                 Money {
-                                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
-                                    token_id: TokenId::Ziesha,
+                                    amount: fee.to_amount(fee_tkn_decimals),
+                                    token_id: fee_tkn,
                                 },
                                 new_nonce,
                             );
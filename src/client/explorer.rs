@@ -1,7 +1,7 @@
 use crate::core::{
     Address, Amount, Block, ContractDeposit, ContractUpdate, ContractWithdraw, GeneralTransaction,
-    Header, Money, MpnDeposit, MpnWithdraw, ProofOfStake, Token, TokenUpdate, Transaction,
-    TransactionData,
+    Header, Liveness, Money, MpnDeposit, MpnWithdraw, ProofOfStake, Token, TokenUpdate,
+    Transaction, TransactionData,
 };
 use crate::crypto::jubjub::*;
 use crate::zk::{
@@ -55,6 +55,7 @@ pub struct ExplorerToken {
     pub symbol: String,
     pub supply: u64,
     pub minter: Option<String>,
+    pub metadata: Option<String>,
 }
 
 impl From<&Token> for ExplorerToken {
@@ -64,6 +65,7 @@ impl From<&Token> for ExplorerToken {
             symbol: obj.symbol.clone(),
             supply: obj.supply.into(),
             minter: obj.minter.as_ref().map(|a| a.to_string()),
+            metadata: obj.metadata.clone(),
         }
     }
 }
@@ -88,6 +90,7 @@ pub struct ExplorerHeader {
     pub parent_hash: String,
     pub number: u64,
     pub block_root: String,
+    pub state_root: String,
     pub proof_of_stake: ExplorerProofOfStake,
 }
 
@@ -97,6 +100,7 @@ impl From<&Header> for ExplorerHeader {
             parent_hash: hex::encode(&obj.parent_hash),
             number: obj.number,
             block_root: hex::encode(&obj.parent_hash),
+            state_root: hex::encode(obj.state_root),
             proof_of_stake: (&obj.proof_of_stake).into(),
         }
     }
@@ -253,6 +257,10 @@ impl From<&ZkProof> for ExplorerZkProof {
 pub enum ExplorerTokenUpdate {
     Mint { amount: u64 },
     ChangeMinter { minter: String },
+    Burn { amount: u64 },
+    Freeze { account: String },
+    Unfreeze { account: String },
+    SetMetadata { metadata: Option<String> },
 }
 
 impl From<&TokenUpdate> for ExplorerTokenUpdate {
@@ -264,6 +272,18 @@ impl From<&TokenUpdate> for ExplorerTokenUpdate {
             TokenUpdate::ChangeMinter { minter } => Self::ChangeMinter {
                 minter: minter.to_string(),
             },
+            TokenUpdate::Burn { amount } => Self::Burn {
+                amount: (*amount).into(),
+            },
+            TokenUpdate::Freeze { account } => Self::Freeze {
+                account: account.to_string(),
+            },
+            TokenUpdate::Unfreeze { account } => Self::Unfreeze {
+                account: account.to_string(),
+            },
+            TokenUpdate::SetMetadata { metadata } => Self::SetMetadata {
+                metadata: metadata.clone(),
+            },
         }
     }
 }
@@ -350,6 +370,55 @@ pub enum ExplorerTransactionData {
         to: String,
         ratio: f64,
     },
+    Redelegate {
+        from: String,
+        to: String,
+        amount: u64,
+    },
+    ClaimReward {
+        from: String,
+    },
+    Unjail,
+    Propose {
+        changes: Vec<String>,
+        activation_height: u64,
+    },
+    Vote {
+        proposal_id: String,
+        approve: bool,
+    },
+    CreateMultisig {
+        signers: Vec<String>,
+        threshold: u8,
+    },
+    VestedSend {
+        dst: String,
+        amount: ExplorerMoney,
+        start: u64,
+        end: u64,
+    },
+    CreateHtlc {
+        recipient: String,
+        amount: ExplorerMoney,
+        hashlock: String,
+        timeout_height: u64,
+    },
+    ClaimHtlc {
+        htlc_id: String,
+        preimage: String,
+    },
+    RefundHtlc {
+        htlc_id: String,
+    },
+    Approve {
+        spender: String,
+        token_id: String,
+        amount: u64,
+    },
+    SpendFrom {
+        owner: String,
+        entries: Vec<(String, ExplorerMoney)>,
+    },
     RegularSend {
         entries: Vec<(String, ExplorerMoney)>,
     },
@@ -367,6 +436,10 @@ pub enum ExplorerTransactionData {
         token_id: String,
         update: ExplorerTokenUpdate,
     },
+    ReportEquivocation {
+        first: ExplorerHeader,
+        second: ExplorerHeader,
+    },
 }
 
 impl From<&TransactionData> for ExplorerTransactionData {
@@ -391,6 +464,78 @@ impl From<&TransactionData> for ExplorerTransactionData {
                 to: to.to_string(),
                 ratio: (*ratio).into(),
             },
+            TransactionData::Redelegate { amount, from, to } => Self::Redelegate {
+                from: from.to_string(),
+                to: to.to_string(),
+                amount: (*amount).into(),
+            },
+            TransactionData::ClaimReward { from } => Self::ClaimReward {
+                from: from.to_string(),
+            },
+            TransactionData::Unjail => Self::Unjail,
+            TransactionData::Propose {
+                changes,
+                activation_height,
+            } => Self::Propose {
+                changes: changes.iter().map(|c| c.to_string()).collect(),
+                activation_height: *activation_height,
+            },
+            TransactionData::Vote {
+                proposal_id,
+                approve,
+            } => Self::Vote {
+                proposal_id: proposal_id.to_string(),
+                approve: *approve,
+            },
+            TransactionData::CreateMultisig { signers, threshold } => Self::CreateMultisig {
+                signers: signers.iter().map(|s| s.to_string()).collect(),
+                threshold: *threshold,
+            },
+            TransactionData::VestedSend {
+                dst,
+                amount,
+                start,
+                end,
+            } => Self::VestedSend {
+                dst: dst.to_string(),
+                amount: (*amount).into(),
+                start: *start,
+                end: *end,
+            },
+            TransactionData::CreateHtlc {
+                recipient,
+                amount,
+                hashlock,
+                timeout_height,
+            } => Self::CreateHtlc {
+                recipient: recipient.to_string(),
+                amount: (*amount).into(),
+                hashlock: hex::encode(hashlock),
+                timeout_height: *timeout_height,
+            },
+            TransactionData::ClaimHtlc { htlc_id, preimage } => Self::ClaimHtlc {
+                htlc_id: htlc_id.to_string(),
+                preimage: hex::encode(preimage),
+            },
+            TransactionData::RefundHtlc { htlc_id } => Self::RefundHtlc {
+                htlc_id: htlc_id.to_string(),
+            },
+            TransactionData::Approve {
+                spender,
+                token_id,
+                amount,
+            } => Self::Approve {
+                spender: spender.to_string(),
+                token_id: token_id.to_string(),
+                amount: (*amount).into(),
+            },
+            TransactionData::SpendFrom { owner, entries } => Self::SpendFrom {
+                owner: owner.to_string(),
+                entries: entries
+                    .iter()
+                    .map(|e| (e.dst.to_string(), e.amount.into()))
+                    .collect(),
+            },
             TransactionData::RegularSend { entries } => Self::RegularSend {
                 entries: entries
                     .iter()
@@ -414,6 +559,10 @@ impl From<&TransactionData> for ExplorerTransactionData {
                 token_id: token_id.to_string(),
                 update: update.into(),
             },
+            TransactionData::ReportEquivocation { first, second } => Self::ReportEquivocation {
+                first: first.as_ref().into(),
+                second: second.as_ref().into(),
+            },
         }
     }
 }
@@ -425,6 +574,7 @@ pub struct ExplorerTransaction {
     pub nonce: u32,
     pub data: ExplorerTransactionData,
     pub fee: ExplorerMoney,
+    pub valid_until: Option<u64>,
     pub sig: String,
 }
 
@@ -436,11 +586,20 @@ impl From<&Transaction> for ExplorerTransaction {
             nonce: obj.nonce,
             data: (&obj.data).into(),
             fee: obj.fee.into(),
+            valid_until: obj.valid_until,
             sig: "".into(), // TODO: Fix
         }
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ExplorerHistoryEntry {
+    pub hash: String,
+    pub block: u64,
+    pub index: u32,
+    pub tx: ExplorerTransaction,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ExplorerBlock {
     pub header: ExplorerHeader,
@@ -460,13 +619,15 @@ impl From<&Block> for ExplorerBlock {
 pub struct ExplorerStaker {
     pub_key: String,
     stake: u64,
+    uptime: f32,
 }
 
-impl From<&(Address, Amount)> for ExplorerStaker {
-    fn from(obj: &(Address, Amount)) -> Self {
+impl From<&(Address, Amount, Liveness)> for ExplorerStaker {
+    fn from(obj: &(Address, Amount, Liveness)) -> Self {
         Self {
             pub_key: obj.0.to_string(),
             stake: obj.1.into(),
+            uptime: obj.2.uptime(),
         }
     }
 }
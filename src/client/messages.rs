@@ -1,15 +1,22 @@
-use crate::blockchain::{TimestampCommit, TransactionStats, ZkBlockchainPatch};
+use crate::blockchain::{
+    light::ValidatorsProof, TimestampCommit, TransactionStats, ZkBlockchainPatch,
+};
 use crate::core::{
-    Address, Amount, Block, ContractId, GeneralAddress, GeneralTransaction, Header, Money,
-    MpnAddress, Signature, Token, TransactionAndDelta, Undelegation, ValidatorProof,
+    hash::Hash, Address, Amount, Block, ContractId, ExchangeRate, GeneralAddress,
+    GeneralTransaction, Hasher, Header, Money, MpnAddress, Multisig, Signature, Token,
+    TransactionAndDelta, TxLocation, Undelegation, ValidatorProof, Vesting,
 };
+use crate::crypto::merkle::SparseMerkleProof;
 use crate::mpn::MpnWork;
 use crate::zk;
 use std::collections::HashMap;
 use thiserror::Error;
 
 use super::{
-    explorer::{ExplorerBlock, ExplorerGeneralTransaction, ExplorerMpnAccount, ExplorerStaker},
+    explorer::{
+        ExplorerBlock, ExplorerGeneralTransaction, ExplorerHistoryEntry, ExplorerMpnAccount,
+        ExplorerStaker, ExplorerTransaction,
+    },
     Peer, PeerAddress,
 };
 use serde::{Deserialize, Serialize};
@@ -29,6 +36,9 @@ pub struct GetStatsResponse {
     pub height: u64,
     pub nodes: usize,
     pub next_reward: Amount,
+    pub base_fee: Amount, // Per byte, burned from Ziesha fees of the next block
+    #[serde(default)]
+    pub accepted_fee_tokens: HashMap<String, ExchangeRate>, // Token-id -> Rate
     pub timestamp: u32,
     pub timestamp_offset: i32,
     pub epoch: u32,
@@ -47,6 +57,8 @@ pub struct GetAccountRequest {
 pub struct GetAccountResponse {
     pub nonce: u32,
     pub mpn_deposit_nonce: u32,
+    pub multisig: Option<Multisig>,
+    pub vestings: Vec<(String, Vesting)>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -59,6 +71,7 @@ pub struct GetDelegationsRequest {
 pub struct GetDelegationsResponse {
     pub delegators: HashMap<String, Amount>,
     pub delegatees: HashMap<String, Amount>,
+    pub pending_rewards: HashMap<String, Amount>,
     pub undelegations: Vec<(String, Undelegation)>,
 }
 
@@ -83,6 +96,17 @@ pub struct GetExplorerMpnAccountsResponse {
     pub accounts: HashMap<u64, ExplorerMpnAccount>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetExplorerHistoryRequest {
+    pub address: String,
+    pub page: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetExplorerHistoryResponse {
+    pub history: Vec<ExplorerHistoryEntry>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum HandshakeRequest {
     Node(PeerAddress),
@@ -219,10 +243,74 @@ pub struct GetBalanceRequest {
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct GetBalanceResponse {
     pub balance: Amount,
+    pub locked: Amount, // Held in vestings, not spendable yet
     pub name: String,
     pub symbol: String,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetAllowanceRequest {
+    pub owner: String,
+    pub spender: String,
+    pub token_id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct GetAllowanceResponse {
+    pub allowance: Amount,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionRequest {
+    pub hash: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionResponse {
+    pub tx: Option<ExplorerTransaction>,
+    pub location: Option<TxLocation>,
+    pub confirmations: u64, // Number of blocks since (And including) the block of the tx
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetAccountProofRequest {
+    pub address: String,
+    pub token_id: String,
+}
+
+// Balance of an account, proven against the state root of the latest header
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetAccountProofResponse {
+    pub header: Header,
+    pub balance: Amount,
+    pub proof: SparseMerkleProof<Hasher>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetValidatorsProofRequest {
+    pub epoch: Option<u32>,
+}
+
+// Validator-set data, proven against the state root of the first header of the epoch
+// (The latest header when no epoch is given)
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetValidatorsProofResponse {
+    pub header: Header,
+    pub proof: ValidatorsProof,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionProofRequest {
+    pub hash: String,
+}
+
+// Header of the block including the tx (None if not included) and its Merkle branch
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionProofResponse {
+    pub header: Option<Header>,
+    pub proof: Vec<<Hasher as Hash>::Output>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct JsonMpnTransaction {
     pub nonce: u32,
@@ -356,7 +444,7 @@ impl ValidatorClaim {
     pub fn verify_signature(&self) -> bool {
         use crate::crypto::SignatureScheme;
         match &self.sig {
-            Signature::Unsigned => false,
+            Signature::Unsigned | Signature::Multi(_) => false,
             Signature::Signed(sig) => {
                 let mut unsigned = self.clone();
                 unsigned.sig = Signature::Unsigned;
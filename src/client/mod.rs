@@ -1,4 +1,7 @@
-use crate::core::{Address, GeneralTransaction, MpnAddress, Signer, TokenId};
+use crate::blockchain::BlockchainError;
+use crate::core::{
+    hash::Hash, Address, ChainId, GeneralTransaction, Hasher, MpnAddress, Signer, TokenId,
+};
 use crate::crypto::ed25519;
 use crate::crypto::SignatureScheme;
 use crate::zk::ZkProof;
@@ -54,6 +57,9 @@ pub struct Peer {
     pub pub_key: ed25519::PublicKey,
     pub height: u64,
     pub outdated_states: usize,
+    // Blocks before this height (Except the genesis block) are not served
+    #[serde(default)]
+    pub pruned_height: u64,
 }
 
 pub struct NodeRequest {
@@ -316,6 +322,17 @@ impl BazukaClient {
             .await
     }
 
+    // Chain-id of the node, which is the hash of its genesis block
+    pub async fn chain_id(&self) -> Result<ChainId, NodeError> {
+        Ok(self
+            .get_headers(0, 1)
+            .await?
+            .headers
+            .first()
+            .ok_or(BlockchainError::BlockNotFound)?
+            .hash())
+    }
+
     pub async fn get_blocks(&self, since: u64, count: u64) -> Result<GetBlocksResponse, NodeError> {
         self.sender
             .bincode_get::<GetBlocksRequest, GetBlocksResponse>(
@@ -382,6 +399,102 @@ impl BazukaClient {
             .await
     }
 
+    pub async fn get_account_proof(
+        &self,
+        address: Address,
+        token_id: TokenId,
+    ) -> Result<GetAccountProofResponse, NodeError> {
+        self.sender
+            .json_get::<GetAccountProofRequest, GetAccountProofResponse>(
+                format!("http://{}/proof/account", self.peer),
+                GetAccountProofRequest {
+                    address: address.to_string(),
+                    token_id: token_id.to_string(),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_validators_proof(
+        &self,
+        epoch: Option<u32>,
+    ) -> Result<GetValidatorsProofResponse, NodeError> {
+        self.sender
+            .json_get::<GetValidatorsProofRequest, GetValidatorsProofResponse>(
+                format!("http://{}/proof/validators", self.peer),
+                GetValidatorsProofRequest { epoch },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+        token_id: TokenId,
+    ) -> Result<GetAllowanceResponse, NodeError> {
+        self.sender
+            .json_get::<GetAllowanceRequest, GetAllowanceResponse>(
+                format!("http://{}/allowance", self.peer),
+                GetAllowanceRequest {
+                    owner: owner.to_string(),
+                    spender: spender.to_string(),
+                    token_id: token_id.to_string(),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_transaction(
+        &self,
+        hash: <Hasher as Hash>::Output,
+    ) -> Result<GetTransactionResponse, NodeError> {
+        self.sender
+            .json_get::<GetTransactionRequest, GetTransactionResponse>(
+                format!("http://{}/transaction", self.peer),
+                GetTransactionRequest {
+                    hash: hex::encode(hash),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_transaction_proof(
+        &self,
+        hash: <Hasher as Hash>::Output,
+    ) -> Result<GetTransactionProofResponse, NodeError> {
+        self.sender
+            .json_get::<GetTransactionProofRequest, GetTransactionProofResponse>(
+                format!("http://{}/proof/transaction", self.peer),
+                GetTransactionProofRequest {
+                    hash: hex::encode(hash),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_explorer_history(
+        &self,
+        address: Address,
+        page: usize,
+    ) -> Result<GetExplorerHistoryResponse, NodeError> {
+        self.sender
+            .json_get::<GetExplorerHistoryRequest, GetExplorerHistoryResponse>(
+                format!("http://{}/explorer/history", self.peer),
+                GetExplorerHistoryRequest {
+                    address: address.to_string(),
+                    page,
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
     pub async fn get_token(&self, token_id: TokenId) -> Result<GetTokenInfoResponse, NodeError> {
         self.sender
             .json_get::<GetTokenInfoRequest, GetTokenInfoResponse>(
@@ -1,6 +1,8 @@
 use super::{initials, UNIT, UNIT_ZEROS};
 
-use crate::blockchain::{BlockAndPatch, BlockchainConfig, ZkBlockchainPatch};
+use crate::blockchain::{
+    BlockAndPatch, BlockchainConfig, Feature, ForkSchedule, ZkBlockchainPatch,
+};
 use crate::common::*;
 use crate::core::{
     Amount, Block, ContractId, Header, Money, ProofOfStake, Ratio, RegularSendEntry, Signature,
@@ -25,6 +27,10 @@ const MPN_LOG4_UPDATE_BATCH_SIZE: u8 = 4;
 
 const TESTNET_HEIGHT_LIMIT: u64 = 10000;
 
+// Deepest reorg nodes are expected to handle, snapshots keep the rollback data of this
+// many blocks by default so that the imported nodes can follow it
+pub const MAX_REORG_DEPTH: u64 = 1000;
+
 lazy_static! {
     pub static ref MPN_UPDATE_VK: zk::groth16::Groth16VerifyingKey =
         bincode::deserialize(&hex::decode("b02701884fb4065e5dec5456f29cbbf7b093b5847c56b7f6c1fb103851b674f9122395c01b2ac3015bbffddd0ccce114a8c239c56aa3543ba593e69f94a411230b6138bbfade4ac527e990466b1b625617f415f58d572e2b0f559e590180ee17005001160b651af92d477bc900a6f468abe5a03d8d16667e104721d84053149b8c8e6dbaaa04f767fe3480adf9ec4e2501948c01cd4d17416f97407c9b1b69bd004dbeefb3ab8a56893eb0efd44d13f740d479eb3b43d4b11b0e23f9bed985ac0a0033316f8dbcea7ba33a2e6e3225c09f3db359b808dcd316f27ac309886060cda95c63b1f274d2f15731dd2e54027173182b5f79b1b1875c11669b2a89584308f461ce1becda321c0ede1c8e060e3dea7255d464c93ce846d65d200327888a320043ba1a5d14a41af8c158ed640c8d3ea06a21525671261fd03f8050c6e25c643a6dfb27418d1b36c14c3ce4a035b22a07a70b43b2f39e4cc54ff9bcc27f36508f0a408446d47a5e520c14a809605865a074631777ba098eb61145839216fa571000c0bd67354bcfaff0ac9be6d6e60dd27ba907b73e48cd29c9d04bfb1648047d00e6e8357101d30b79946c6072c6967909b9aae7f069033cafaea578a6b2e0e6b2bfabd528e90c2d3424af26a7d26bf95dd06296c89ddd8a662c52756656304118dce1cb5ca358fa9726344e8c37eede52e11786758be88dab87d896216dc0291c8f250322ca0aff90cea90f5ac30a250a65e187464f11b76f15fe8fd5ae1a71fd02131af2f1585807ba1729693d7481ec47d7731eefba89272466472f6482d109004188bba4fc60efa79ea39994af0bf56accd370b06fdce321aa7c0d00d4bf8cfac3ef3408822145f58963bcddd84f1711752f24db6810bcfc10b9f2d1ee7601703e2da6f8c42ce2e771e85dc81f0f71d3ec1537848e1d29220136e4193ae98a17005732f4779ecd296857e4217453314ebc5b733d289cae7d2b4109ac8df4d7cf4b368c6942006c79503155fde7a4dfbd1840f3f8f8599dcfded2050bfc1c1f41d9a0931b52bd5ea22053e7913104eba04a68e4aa9991c74949ca80871c14744f0c247b6df3c6bd961430f1aa53b855967a91432ad5645876e6b67ccf29f0cb6b2197bdc3fae24a8f5c5215aab931e62b193e64b49c48f4e916a73a2752542b78c53b7b96ab8a819fd45c37c6bc5ef76fe5b7a1d8f74df6a776b413bb7bd385280300050000000000000043a6f266a47b03bf88f2e8fb15fb3b62d20e12b405a07f68d84b69de8a9c52b77149e9856ace5913d91955911dad1e060fe5fba59c82c7629ac19db1b1760672a3d34215e22c77c7b481297d7efe062e487d8db8643909a2654c79874516261100c9b604e4c7f22c43dee2283240d0c2674fb85723a67db1d3d5c155377aa3292cee05b6c43b27a1fe04d8878e288a6e0a61949e5da8f5b7a99af76f5fa65241b915d72a16bbf0d955895a08928ef87e887edd499f717722976e8b480fa78a0604009ec0881aefe81b75e6929d68c56910dad75d478c31bbb967a6955b557035d78de677634e91302adf5ab14acab5f5830e879b2724d16efe5e3bb96dd4e142ff07307a8a0dd9bd773383ef040084214e1fce0e4392ae140897bb4ea42bcce0a10d00c3666e30e7404067260d54ecb1602bcb9430efc437b303ad37f07b23879c3034600674f6312d98b4b609ef49eecb19166dd619041ce11112f183d7e8489c384c2279bd3cd78ef06b34a47e27e7f2239460faae4add23039d5878a5e73834c809009aa92b0e69272ad538986fd0ae0888df53c71fb9eae573e0eccba522cc3f75e28a0bf56b875197857359966c032da811041ad13ca15a76d9eafe616b0e1b4581ec8bb2dd44f77f92140ea154f5dd3f0f6ad196e19b08bf67743751b7e9727b1100").unwrap()).unwrap();
@@ -83,6 +89,7 @@ fn get_mpn_contract(
         },
         nonce: 0, // MPN contract is created after Ziesha token is created
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
     TransactionAndDelta {
@@ -148,10 +155,12 @@ fn get_ziesha_token_creation_tx() -> Transaction {
                 supply: Amount(2_000_000_000_u64 * UNIT),
                 decimals: UNIT_ZEROS,
                 minter: None,
+                metadata: None,
             },
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     }
 }
@@ -183,6 +192,7 @@ pub fn get_blockchain_config() -> BlockchainConfig {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
     let delegate_to_staker = Transaction {
@@ -196,6 +206,7 @@ pub fn get_blockchain_config() -> BlockchainConfig {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
 
@@ -204,11 +215,13 @@ pub fn get_blockchain_config() -> BlockchainConfig {
             parent_hash: Default::default(),
             number: 0,
             block_root: Default::default(),
+            state_root: Default::default(),
             proof_of_stake: ProofOfStake {
                 timestamp: CHAIN_START_TIMESTAMP,
                 validator: Default::default(),
                 proof: ValidatorProof::Unproven,
             },
+            sig: Signature::Unsigned,
         },
         body: vec![
             ziesha_token_creation_tx,
@@ -233,6 +246,7 @@ pub fn get_blockchain_config() -> BlockchainConfig {
             },
             nonce: 0,
             fee: Money::ziesha(0),
+            valid_until: None,
             sig: Signature::Unsigned,
         });
     }
@@ -252,6 +266,8 @@ pub fn get_blockchain_config() -> BlockchainConfig {
             deposit_vk: zk::ZkVerifierKey::Groth16(Box::new(MPN_DEPOSIT_VK.clone())),
             withdraw_vk: zk::ZkVerifierKey::Groth16(Box::new(MPN_WITHDRAW_VK.clone())),
             update_vk: zk::ZkVerifierKey::Groth16(Box::new(MPN_UPDATE_VK.clone())),
+            // Not scheduled until the chain-bound update circuit has its parameters
+            chain_bound_update_vk: None,
         },
 
         ziesha_token_id,
@@ -271,12 +287,26 @@ pub fn get_blockchain_config() -> BlockchainConfig {
         max_delta_count: 1024, // Only allow max of 1024 ZkScalar cells to be added per block
 
         testnet_height_limit: Some(TESTNET_HEIGHT_LIMIT),
+        fork_schedule: ForkSchedule::new(&[
+            (Feature::Jailing, 0),
+            (Feature::Governance, 0),
+            (Feature::ReplayProtection, 0),
+            (Feature::BaseFee, 0),
+            (Feature::StateRoot, 0),
+        ]),
         max_memo_length: 64,
         slot_duration: 60,
         slot_per_epoch: 10,
         chain_start_timestamp: CHAIN_START_TIMESTAMP,
         check_validator: true,
         max_validator_commission: Ratio(26), // 26 / 255 ~= 10%
+        equivocation_slash_ratio: Ratio(26), // 26 / 255 ~= 10% of the stake is slashed
+        unbonding_period: 10,                // Undelegated funds are unlocked after 10 blocks
+        max_missed_slots: 20, // Stakers missing more than 20 slots in a row are jailed
+        max_validators: 100,  // Only the top 100 stakers are allowed to validate in an epoch
+        proposal_voting_period: 100, // Proposals are tallied at least 100 blocks after submission
+        proposal_approval_ratio: Ratio(170), // 170 / 255 ~= 2/3 of the stake should approve
+        min_base_fee: Amount(0), // Base fee only rises when blocks are more than half full
     }
 }
 
@@ -341,6 +371,7 @@ pub fn get_dev_blockchain_config(
             deposit_vk: zk::ZkVerifierKey::Groth16(Box::new(deposit_params.vk.clone().into())),
             withdraw_vk: zk::ZkVerifierKey::Groth16(Box::new(withdraw_params.vk.clone().into())),
             update_vk: zk::ZkVerifierKey::Groth16(Box::new(update_params.vk.clone().into())),
+            chain_bound_update_vk: None,
         };
     }
 
@@ -353,6 +384,7 @@ pub fn get_dev_blockchain_config(
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
     conf.genesis.block.body[3] = Transaction {
@@ -364,6 +396,7 @@ pub fn get_dev_blockchain_config(
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     };
     conf.genesis.block.body.push(Transaction {
@@ -377,6 +410,7 @@ pub fn get_dev_blockchain_config(
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     });
     conf
@@ -402,11 +436,18 @@ pub fn get_test_blockchain_config() -> BlockchainConfig {
         deposit_vk: zk::ZkVerifierKey::Dummy,
         withdraw_vk: zk::ZkVerifierKey::Dummy,
         update_vk: zk::ZkVerifierKey::Dummy,
+        chain_bound_update_vk: Some(zk::ZkVerifierKey::Dummy),
     };
     conf.testnet_height_limit = None;
     conf.chain_start_timestamp = 0;
     conf.check_validator = false;
     conf.slot_duration = 5;
+    // Test wallets sign without a chain-id
+    conf.fork_schedule = ForkSchedule::new(&[
+        (Feature::Jailing, 0),
+        (Feature::Governance, 0),
+        (Feature::StateRoot, 0),
+    ]);
 
     conf.genesis.block.body[1] = get_test_mpn_contract().tx;
     conf.genesis.block.body.drain(2..);
@@ -427,6 +468,7 @@ pub fn get_test_blockchain_config() -> BlockchainConfig {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     });
 
@@ -442,6 +484,7 @@ pub fn get_test_blockchain_config() -> BlockchainConfig {
         },
         nonce: 0,
         fee: Money::ziesha(0),
+        valid_until: None,
         sig: Signature::Unsigned,
     });
 
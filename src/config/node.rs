@@ -1,4 +1,5 @@
 use crate::node::{HeartbeatIntervals, NodeOptions};
+use std::collections::HashMap;
 use std::time::Duration;
 
 pub fn get_node_options() -> NodeOptions {
@@ -28,6 +29,8 @@ pub fn get_node_options() -> NodeOptions {
         mempool_max_fetch: 1000,
         max_block_time_difference: 120,
         automatic_block_generation: true,
+        accepted_fee_tokens: HashMap::new(),
+        prune_retention: None,
     }
 }
 
@@ -58,5 +61,7 @@ pub fn get_simulator_options() -> NodeOptions {
         mempool_max_fetch: 1000,
         max_block_time_difference: 120,
         automatic_block_generation: false,
+        accepted_fee_tokens: HashMap::new(),
+        prune_retention: None,
     }
 }
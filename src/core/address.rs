@@ -54,6 +54,62 @@ where
 pub enum Signature<S: SignatureScheme> {
     Unsigned,
     Signed(S::Sig),
+    Multi(Vec<(u8, S::Sig)>), // Signatures of a multisig account, by signer index
+}
+
+impl<S: SignatureScheme> std::hash::Hash for Signature<S> {
+    fn hash<Hasher>(&self, state: &mut Hasher)
+    where
+        Hasher: std::hash::Hasher,
+    {
+        state.write(&bincode::serialize(self).unwrap());
+    }
+}
+
+// Account spendable only with signatures of `threshold` members of `signers`
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Multisig<S: SignatureScheme> {
+    pub signers: Vec<S::Pub>,
+    pub threshold: u8,
+}
+
+impl<S: SignatureScheme> Multisig<S> {
+    pub fn address(&self) -> S::Pub {
+        let mut preimage = b"multisig".to_vec();
+        preimage.extend(bincode::serialize(self).unwrap());
+        S::derive_pub(&preimage)
+    }
+    pub fn is_valid(&self) -> bool {
+        let distinct = self
+            .signers
+            .iter()
+            .enumerate()
+            .all(|(i, s)| !self.signers[..i].contains(s));
+        distinct
+            && self.signers.len() <= u8::MAX as usize
+            && self.threshold > 0
+            && self.threshold as usize <= self.signers.len()
+    }
+    pub fn index_of(&self, signer: &S::Pub) -> Option<u8> {
+        self.signers
+            .iter()
+            .position(|s| s == signer)
+            .map(|i| i as u8)
+    }
+    pub fn verify(&self, msg: &[u8], sigs: &[(u8, S::Sig)]) -> bool {
+        let mut signed = Vec::new();
+        for (index, sig) in sigs {
+            match self.signers.get(*index as usize) {
+                Some(signer) if !signed.contains(index) && S::verify(signer, msg, sig) => {
+                    signed.push(*index);
+                }
+                _ => {
+                    return false;
+                }
+            }
+        }
+        signed.len() >= self.threshold as usize
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
@@ -65,6 +121,54 @@ pub struct Staker<V: VerifiableRandomFunction> {
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
 pub struct Delegate {
     pub amount: Amount,
+    // Reward-per-stake of the delegatee when the rewards were last claimed
+    pub reward_per_stake: u128,
+}
+
+impl Delegate {
+    // Rewards the delegation has earned since its last claim
+    pub fn pending_reward(&self, pool: &RewardPool) -> Amount {
+        Amount(
+            (u64::from(self.amount) as u128
+                * pool.reward_per_stake.wrapping_sub(self.reward_per_stake)
+                / RewardPool::PRECISION) as u64,
+        )
+    }
+}
+
+// Rewards of the delegators of a staker, waiting to be claimed. Each delegation is
+// entitled to `amount * (reward_per_stake - delegate.reward_per_stake) / PRECISION`,
+// rounded down (The remainders stay in the pool)
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct RewardPool {
+    pub reward_per_stake: u128, // Cumulative, only compared through wrapping subtraction
+    pub balance: Amount,
+}
+
+impl RewardPool {
+    pub const PRECISION: u128 = 1_000_000_000_000_000_000;
+}
+
+// Block production record of a staker. At the start of each epoch, the blocks the
+// staker has produced in the previous epoch are compared with the slots it was expected
+// to win by its share of stake
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct Liveness {
+    pub missed: u32, // Missed slots since the last epoch without misses
+    pub total_expected: u64,
+    pub total_missed: u64,
+    pub jailed: bool,
+}
+
+impl Liveness {
+    // Fraction of the expected blocks that the staker has actually produced
+    pub fn uptime(&self) -> f32 {
+        if self.total_expected == 0 {
+            1.0
+        } else {
+            1.0 - self.total_missed as f32 / self.total_expected as f32
+        }
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
@@ -13,6 +13,13 @@ pub struct Block<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> {
     pub body: Vec<Transaction<H, S, V>>,
 }
 
+// Where a transaction is included: the height of its block, and its index in the body
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxLocation {
+    pub block: u64,
+    pub index: u32,
+}
+
 impl<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> Block<H, S, V> {
     pub fn merkle_tree(&self) -> MerkleTree<H> {
         MerkleTree::<H>::new(self.body.iter().map(|tx| tx.hash()).collect())
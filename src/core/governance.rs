@@ -0,0 +1,129 @@
+use super::hash::Hash;
+use super::transaction::{Ratio, Transaction};
+use crate::crypto::{SignatureScheme, VerifiableRandomFunction};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    std::hash::Hash,
+    Default,
+)]
+pub struct ProposalId<H: Hash>(H::Output);
+
+#[derive(Error, Debug)]
+pub enum ParseProposalIdError {
+    #[error("proposal-id invalid")]
+    Invalid,
+}
+
+impl<H: Hash> ProposalId<H> {
+    pub fn new<S: SignatureScheme, V: VerifiableRandomFunction>(tx: &Transaction<H, S, V>) -> Self {
+        Self(tx.hash())
+    }
+}
+
+impl<H: Hash> std::fmt::Display for ProposalId<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl<H: Hash> FromStr for ProposalId<H> {
+    type Err = ParseProposalIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| ParseProposalIdError::Invalid)?;
+        let hash_output = H::Output::try_from(bytes).map_err(|_| ParseProposalIdError::Invalid)?;
+        Ok(Self(hash_output))
+    }
+}
+
+// Blockchain parameters that can be changed through governance
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ConfigParam {
+    MaxBlockSize(usize),
+    MaxDeltaCount(usize),
+    RewardRatio(u64),
+    MaxMemoLength(usize),
+    MaxValidatorCommission(Ratio),
+}
+
+impl ConfigParam {
+    // Whether the chain can keep working with the parameter changed
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::MaxBlockSize(v) => (1024..=64 * 1024 * 1024).contains(v),
+            Self::MaxDeltaCount(v) => (1..=1 << 20).contains(v),
+            Self::RewardRatio(v) => *v > 0,
+            Self::MaxMemoLength(v) => *v <= 1024,
+            Self::MaxValidatorCommission(_) => true,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ParseConfigParamError {
+    #[error("config parameter invalid, expected <name>=<value>")]
+    Invalid,
+    #[error("config parameter unknown")]
+    Unknown,
+}
+
+impl std::fmt::Display for ConfigParam {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MaxBlockSize(v) => write!(f, "max_block_size={}", v),
+            Self::MaxDeltaCount(v) => write!(f, "max_delta_count={}", v),
+            Self::RewardRatio(v) => write!(f, "reward_ratio={}", v),
+            Self::MaxMemoLength(v) => write!(f, "max_memo_length={}", v),
+            Self::MaxValidatorCommission(v) => {
+                write!(f, "max_validator_commission={}", Into::<f64>::into(*v))
+            }
+        }
+    }
+}
+
+impl FromStr for ConfigParam {
+    type Err = ParseConfigParamError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s.split_once('=').ok_or(ParseConfigParamError::Invalid)?;
+        Ok(match name {
+            "max_block_size" => {
+                Self::MaxBlockSize(value.parse().map_err(|_| ParseConfigParamError::Invalid)?)
+            }
+            "max_delta_count" => {
+                Self::MaxDeltaCount(value.parse().map_err(|_| ParseConfigParamError::Invalid)?)
+            }
+            "reward_ratio" => {
+                Self::RewardRatio(value.parse().map_err(|_| ParseConfigParamError::Invalid)?)
+            }
+            "max_memo_length" => {
+                Self::MaxMemoLength(value.parse().map_err(|_| ParseConfigParamError::Invalid)?)
+            }
+            "max_validator_commission" => Self::MaxValidatorCommission(
+                value
+                    .parse::<f32>()
+                    .map_err(|_| ParseConfigParamError::Invalid)?
+                    .try_into()
+                    .map_err(|_| ParseConfigParamError::Invalid)?,
+            ),
+            _ => {
+                return Err(ParseConfigParamError::Unknown);
+            }
+        })
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Proposal<S: SignatureScheme> {
+    pub proposer: S::Pub,
+    pub changes: Vec<ConfigParam>,
+    pub activation_height: u64,
+    pub voting_ends: u64, // Tallied on the first epoch boundary after this height
+}
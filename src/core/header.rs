@@ -1,3 +1,4 @@
+use super::address::Signature;
 use super::hash::Hash;
 use crate::crypto::{SignatureScheme, VerifiableRandomFunction};
 
@@ -38,12 +39,27 @@ pub struct Header<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> {
     pub number: u64,
     /// the merkle root of current block
     pub block_root: H::Output,
+    /// root of the account state after applying current block
+    pub state_root: H::Output,
     /// aux data for Proof-of-Stake consensus
     pub proof_of_stake: ProofOfStake<S, V>,
+    /// signature of the validator over the unsigned header
+    pub sig: Signature<S>,
 }
 
 impl<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> Header<H, S, V> {
     pub fn hash(&self) -> H::Output {
         H::hash(&bincode::serialize(&self).expect("convert header to bincode format"))
     }
+    pub fn verify_signature(&self) -> bool {
+        match &self.sig {
+            Signature::Unsigned | Signature::Multi(_) => false,
+            Signature::Signed(sig) => {
+                let mut unsigned = self.clone();
+                unsigned.sig = Signature::Unsigned;
+                let bytes = bincode::serialize(&unsigned).unwrap();
+                S::verify(&self.proof_of_stake.validator, &bytes, sig)
+            }
+        }
+    }
 }
@@ -0,0 +1,62 @@
+use super::hash::Hash;
+use super::transaction::{Money, Transaction};
+use crate::crypto::{SignatureScheme, VerifiableRandomFunction};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    std::hash::Hash,
+    Default,
+)]
+pub struct HtlcId<H: Hash>(H::Output);
+
+#[derive(Error, Debug)]
+pub enum ParseHtlcIdError {
+    #[error("htlc-id invalid")]
+    Invalid,
+}
+
+impl<H: Hash> HtlcId<H> {
+    pub fn new<S: SignatureScheme, V: VerifiableRandomFunction>(tx: &Transaction<H, S, V>) -> Self {
+        Self(tx.hash())
+    }
+}
+
+impl<H: Hash> std::fmt::Display for HtlcId<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl<H: Hash> FromStr for HtlcId<H> {
+    type Err = ParseHtlcIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| ParseHtlcIdError::Invalid)?;
+        let hash_output = H::Output::try_from(bytes).map_err(|_| ParseHtlcIdError::Invalid)?;
+        Ok(Self(hash_output))
+    }
+}
+
+// Funds escrowed until the recipient reveals the preimage of the hashlock, or
+// refunded to the sender once the timeout height is reached
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Htlc<H: Hash, S: SignatureScheme> {
+    pub sender: S::Pub,
+    pub recipient: S::Pub,
+    pub amount: Money,
+    pub hashlock: H::Output,
+    pub timeout_height: u64,
+}
+
+impl<H: Hash, S: SignatureScheme> Htlc<H, S> {
+    pub fn unlocks_with(&self, preimage: &[u8]) -> bool {
+        H::hash(preimage) == self.hashlock
+    }
+}
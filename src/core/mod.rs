@@ -1,9 +1,12 @@
 mod address;
 mod blocks;
+mod governance;
 pub mod hash;
 mod header;
+mod htlc;
 mod money;
 mod transaction;
+mod vesting;
 
 use crate::crypto;
 use crate::zk;
@@ -11,9 +14,13 @@ use crate::zk;
 use std::str::FromStr;
 use thiserror::Error;
 
+pub use blocks::TxLocation;
+pub use governance::{ConfigParam, ParseConfigParamError};
 pub use money::Amount;
 pub use money::Decimal;
+pub use money::ExchangeRate;
 pub use transaction::{Money, Ratio};
+pub use vesting::Vesting;
 
 pub type Hasher = hash::Sha3Hasher;
 pub type Signer = crypto::ed25519::Ed25519<Hasher>;
@@ -24,10 +31,16 @@ pub type ZkSigner = crypto::jubjub::JubJub<ZkHasher>;
 
 pub type ConvertRatioError = transaction::ConvertRatioError;
 
+// Hash of the genesis block, bound into signatures to prevent cross-chain replays
+pub type ChainId = <Hasher as hash::Hash>::Output;
+
 pub type Address = <Signer as crypto::SignatureScheme>::Pub;
 pub type ParseAddressError = <Signer as crypto::SignatureScheme>::PubParseError;
 pub type Staker = address::Staker<Vrf>;
 pub type Delegate = address::Delegate;
+pub type RewardPool = address::RewardPool;
+pub type Liveness = address::Liveness;
+pub type Multisig = address::Multisig<Signer>;
 pub type Undelegation = address::Undelegation;
 pub type Signature = address::Signature<Signer>;
 pub type Transaction = transaction::Transaction<Hasher, Signer, Vrf>;
@@ -41,6 +54,15 @@ pub type MpnAddress = address::MpnAddress<ZkSigner>;
 pub type ParseMpnAddressError = address::ParseMpnAddressError;
 pub type UndelegationId = transaction::UndelegationId<Hasher>;
 pub type ParseUndelegationIdError = transaction::ParseUndelegationIdError;
+pub type UnbondingStake = transaction::UnbondingStake<Hasher, Signer>;
+pub type ProposalId = governance::ProposalId<Hasher>;
+pub type ParseProposalIdError = governance::ParseProposalIdError;
+pub type Proposal = governance::Proposal<Signer>;
+pub type VestingId = vesting::VestingId<Hasher>;
+pub type ParseVestingIdError = vesting::ParseVestingIdError;
+pub type HtlcId = htlc::HtlcId<Hasher>;
+pub type ParseHtlcIdError = htlc::ParseHtlcIdError;
+pub type Htlc = htlc::Htlc<Hasher, Signer>;
 pub type MpnDeposit = transaction::MpnDeposit<Hasher, Signer, ZkSigner>;
 pub type MpnWithdraw = transaction::MpnWithdraw<Hasher, Signer, ZkSigner>;
 pub type MpnTransaction = zk::MpnTransaction;
@@ -100,6 +122,7 @@ pub enum NonceGroup {
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum GeneralTransaction {
     TransactionAndDelta(TransactionAndDelta),
     MpnDeposit(MpnDeposit),
@@ -124,13 +147,19 @@ impl GeneralTransaction {
             }),
         }
     }
-    pub fn verify_signature(&self) -> bool {
+    // MPN transactions are only bound to the chain-id once `MpnReplayProtection` is
+    // active, since their signed message is recomputed inside the MPN update circuit.
+    pub fn verify_signature(&self, chain_id: Option<&ChainId>) -> bool {
         match self {
-            GeneralTransaction::TransactionAndDelta(tx_delta) => tx_delta.tx.verify_signature(),
-            GeneralTransaction::MpnDeposit(mpn_deposit) => mpn_deposit.payment.verify_signature(),
-            GeneralTransaction::MpnTransaction(mpn_tx) => mpn_tx.verify_signature(),
+            GeneralTransaction::TransactionAndDelta(tx_delta) => {
+                tx_delta.tx.verify_signature(chain_id)
+            }
+            GeneralTransaction::MpnDeposit(mpn_deposit) => {
+                mpn_deposit.payment.verify_signature(chain_id)
+            }
+            GeneralTransaction::MpnTransaction(mpn_tx) => mpn_tx.verify_signature(chain_id),
             GeneralTransaction::MpnWithdraw(mpn_withdraw) => {
-                mpn_withdraw.verify_signature::<ZkHasher>()
+                mpn_withdraw.verify_signature::<ZkHasher>(chain_id)
             }
         }
     }
@@ -142,6 +171,18 @@ impl GeneralTransaction {
             GeneralTransaction::MpnWithdraw(mpn_withdraw) => mpn_withdraw.zk_nonce,
         }
     }
+    // Last height the transaction can be included in, only chain transactions expire
+    pub fn valid_until(&self) -> Option<u64> {
+        match self {
+            GeneralTransaction::TransactionAndDelta(tx_delta) => tx_delta.tx.valid_until,
+            _ => None,
+        }
+    }
+    pub fn is_expired(&self, height: u64) -> bool {
+        self.valid_until()
+            .map(|valid_until| height > valid_until)
+            .unwrap_or(false)
+    }
     pub fn sender(&self) -> GeneralAddress {
         match self {
             GeneralTransaction::TransactionAndDelta(tx_delta) => {
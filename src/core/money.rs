@@ -150,6 +150,26 @@ impl Div<u64> for Amount {
     }
 }
 
+// Value of a token in Ziesha, as `token` units of the token are worth `ziesha` units
+// of Ziesha. Used by validators for comparing fees that are paid in different tokens.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExchangeRate {
+    pub ziesha: Amount,
+    pub token: Amount,
+}
+
+impl ExchangeRate {
+    pub fn to_ziesha(&self, amount: Amount) -> Amount {
+        if self.token.0 == 0 {
+            return Amount(0);
+        }
+        Amount(std::cmp::min(
+            amount.0 as u128 * self.ziesha.0 as u128 / self.token.0 as u128,
+            u64::MAX as u128,
+        ) as u64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,4 +370,20 @@ mod tests {
         assert!("12 .".parse::<Decimal>().is_err());
         assert!(". 12".parse::<Decimal>().is_err());
     }
+
+    #[test]
+    fn test_exchange_rate() {
+        let rate = ExchangeRate {
+            ziesha: Amount(3),
+            token: Amount(2),
+        };
+        assert_eq!(rate.to_ziesha(Amount(10)), Amount(15));
+        assert_eq!(rate.to_ziesha(Amount(1)), Amount(1));
+        assert_eq!(rate.to_ziesha(Amount(u64::MAX)), Amount(u64::MAX));
+        let worthless = ExchangeRate {
+            ziesha: Amount(1),
+            token: Amount(0),
+        };
+        assert_eq!(worthless.to_ziesha(Amount(10)), Amount(0));
+    }
 }
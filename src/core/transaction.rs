@@ -1,5 +1,8 @@
 use super::address::Signature;
+use super::governance::{ConfigParam, ProposalId};
 use super::hash::Hash;
+use super::header::Header;
+use super::htlc::{Htlc, HtlcId};
 use super::Amount;
 use crate::crypto::DeriveMpnAccountIndex;
 use crate::crypto::VerifiableRandomFunction;
@@ -50,6 +53,21 @@ impl<H: Hash> FromStr for UndelegationId<H> {
     }
 }
 
+// Stake moved away from a staker, which is still slashable for the infractions the staker
+// has committed before the move
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug, Clone, Eq)]
+pub enum UnbondingStake<H: Hash, S: SignatureScheme> {
+    Undelegation {
+        undelegator: S::Pub,
+        undelegation_id: UndelegationId<H>,
+    },
+    Redelegation {
+        delegator: S::Pub,
+        to: S::Pub,
+        amount: Amount,
+    },
+}
+
 #[derive(
     serde::Serialize,
     serde::Deserialize,
@@ -236,32 +254,43 @@ where
         preimage.extend(&self.zk_sig.clone().into());
         self.payment.calldata == ZH::hash(&preimage)
     }
-    pub fn verify_signature<ZH: ZkHasher>(&self) -> bool {
+    pub fn verify_signature<ZH: ZkHasher>(&self, chain_id: Option<&H::Output>) -> bool {
         let msg = ZH::hash(&[
-            self.payment.fingerprint(),
+            self.payment.fingerprint(chain_id),
             ZkScalar::from(self.zk_nonce as u64),
         ]);
         ZS::verify(&self.zk_address, msg, &self.zk_sig)
     }
 }
 
+// Signed payloads are prefixed with the chain-id, so that they can't be replayed on
+// other networks. A `None` chain-id gives the legacy (unbound) preimage.
+fn with_chain_id<H: Hash>(chain_id: Option<&H::Output>, mut bytes: Vec<u8>) -> Vec<u8> {
+    if let Some(chain_id) = chain_id {
+        bytes.splice(0..0, chain_id.as_ref().iter().cloned());
+    }
+    bytes
+}
+
 impl<H: Hash, S: SignatureScheme> ContractDeposit<H, S> {
-    pub fn verify_signature(&self) -> bool {
+    pub fn signing_bytes(&self, chain_id: Option<&H::Output>) -> Vec<u8> {
         let mut unsigned = self.clone();
         unsigned.sig = None;
-        let unsigned_bin = bincode::serialize(&unsigned).unwrap();
+        with_chain_id::<H>(chain_id, bincode::serialize(&unsigned).unwrap())
+    }
+    pub fn verify_signature(&self, chain_id: Option<&H::Output>) -> bool {
         self.sig
             .as_ref()
-            .map(|sig| S::verify(&self.src, &unsigned_bin, sig))
+            .map(|sig| S::verify(&self.src, &self.signing_bytes(chain_id), sig))
             .unwrap_or(false)
     }
 }
 
 impl<H: Hash, S: SignatureScheme> ContractWithdraw<H, S> {
-    pub fn fingerprint(&self) -> ZkScalar {
+    pub fn fingerprint(&self, chain_id: Option<&H::Output>) -> ZkScalar {
         let mut unsigned = self.clone();
         unsigned.calldata = ZkScalar::default();
-        let unsigned_bin = bincode::serialize(&unsigned).unwrap();
+        let unsigned_bin = with_chain_id::<H>(chain_id, bincode::serialize(&unsigned).unwrap());
         ZkScalar::new(H::hash(&unsigned_bin).as_ref())
     }
 }
@@ -310,6 +339,7 @@ pub struct Token<S: SignatureScheme> {
     pub supply: Amount, // 1u64 in case of a NFT
     pub decimals: u8,
     pub minter: Option<S::Pub>,
+    pub metadata: Option<String>, // Off-chain URI or description of the token
 }
 
 impl<S: SignatureScheme> Token<S> {
@@ -319,6 +349,7 @@ impl<S: SignatureScheme> Token<S> {
         const MAX_NAME_LEN: usize = 32;
         const MIN_SYMBOL_LEN: usize = 3;
         const MAX_SYMBOL_LEN: usize = 6;
+        const MAX_METADATA_LEN: usize = 256;
         lazy_static! {
             static ref RE_NAME: Regex = Regex::new(r"^(?:[a-zA-Z0-9]+ )*[a-zA-Z0-9]+$").unwrap();
             static ref RE_SYMBOL: Regex = Regex::new(r"^[A-Z][A-Z0-9]*$").unwrap();
@@ -329,6 +360,11 @@ impl<S: SignatureScheme> Token<S> {
             && self.symbol.len() <= MAX_SYMBOL_LEN
             && RE_NAME.is_match(&self.name)
             && RE_SYMBOL.is_match(&self.symbol)
+            && self
+                .metadata
+                .as_ref()
+                .map(|m| m.len() <= MAX_METADATA_LEN)
+                .unwrap_or(true)
     }
 }
 
@@ -336,6 +372,10 @@ impl<S: SignatureScheme> Token<S> {
 pub enum TokenUpdate<S: SignatureScheme> {
     Mint { amount: Amount },
     ChangeMinter { minter: S::Pub },
+    Burn { amount: Amount },
+    Freeze { account: S::Pub }, // Frozen accounts cannot move their tokens
+    Unfreeze { account: S::Pub },
+    SetMetadata { metadata: Option<String> },
 }
 
 #[derive(
@@ -343,6 +383,13 @@ pub enum TokenUpdate<S: SignatureScheme> {
 )]
 pub struct Ratio(pub u8);
 
+impl Ratio {
+    // Portion of the given amount, rounded down (Computed without floating points)
+    pub fn apply(&self, amount: Amount) -> Amount {
+        Amount((u64::from(amount) as u128 * self.0 as u128 / u8::MAX as u128) as u64)
+    }
+}
+
 impl Into<f64> for Ratio {
     fn into(self) -> f64 {
         self.0 as f64 / u8::MAX as f64
@@ -406,6 +453,71 @@ pub enum TransactionData<H: Hash, S: SignatureScheme, V: VerifiableRandomFunctio
         token_id: TokenId,
         update: TokenUpdate<S>,
     },
+    // Evidence of a validator signing two different headers for the same slot
+    ReportEquivocation {
+        first: Box<Header<H, S, V>>,
+        second: Box<Header<H, S, V>>,
+    },
+    // Move a delegation from one staker to another without waiting for the unbonding period
+    Redelegate {
+        amount: Amount,
+        from: S::Pub,
+        to: S::Pub,
+    },
+    // Settle the rewards earned by a delegation
+    ClaimReward {
+        from: S::Pub,
+    },
+    // Let a staker that has been jailed for missing its slots validate again
+    Unjail,
+    // Propose changing parameters of the blockchain, starting from a given height
+    Propose {
+        changes: Vec<ConfigParam>,
+        activation_height: u64,
+    },
+    // Vote on a proposal, weighted by the stake of the voter
+    Vote {
+        proposal_id: ProposalId<H>,
+        approve: bool,
+    },
+    // Create an account owned by a threshold of signers
+    CreateMultisig {
+        signers: Vec<S::Pub>,
+        threshold: u8,
+    },
+    // Send tokens that unlock linearly between two heights (At once if they are equal)
+    VestedSend {
+        dst: S::Pub,
+        amount: Money,
+        start: u64,
+        end: u64,
+    },
+    // Escrow funds that the recipient can claim by revealing the preimage of the
+    // hashlock, or that can be refunded to the sender after the timeout height
+    CreateHtlc {
+        recipient: S::Pub,
+        amount: Money,
+        hashlock: H::Output,
+        timeout_height: u64,
+    },
+    ClaimHtlc {
+        htlc_id: HtlcId<H>,
+        preimage: Vec<u8>,
+    },
+    RefundHtlc {
+        htlc_id: HtlcId<H>,
+    },
+    // Let the spender move up to the given amount of tokens out of the account
+    Approve {
+        spender: S::Pub,
+        token_id: TokenId,
+        amount: Amount,
+    },
+    // Move tokens out of an account that has approved the sender as a spender
+    SpendFrom {
+        owner: S::Pub,
+        entries: Vec<RegularSendEntry<S>>,
+    },
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -415,6 +527,7 @@ pub struct Transaction<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction>
     pub data: TransactionData<H, S, V>,
     pub fee: Money,
     pub memo: String,
+    pub valid_until: Option<u64>, // Not includable in blocks after this height
     pub sig: Signature<S>,
 }
 
@@ -431,18 +544,83 @@ impl<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> Transaction<H, S,
     pub fn hash(&self) -> H::Output {
         H::hash(&bincode::serialize(self).unwrap())
     }
-    pub fn verify_signature(&self) -> bool {
+    pub fn signing_bytes(&self, chain_id: Option<&H::Output>) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.sig = Signature::Unsigned;
+        with_chain_id::<H>(chain_id, bincode::serialize(&unsigned).unwrap())
+    }
+    pub fn verify_signature(&self, chain_id: Option<&H::Output>) -> bool {
         match &self.src {
             None => true,
             Some(pk) => match &self.sig {
                 Signature::Unsigned => false,
-                Signature::Signed(sig) => {
-                    let mut unsigned = self.clone();
-                    unsigned.sig = Signature::Unsigned;
-                    let bytes = bincode::serialize(&unsigned).unwrap();
-                    S::verify(pk, &bytes, sig)
-                }
+                Signature::Signed(sig) => S::verify(pk, &self.signing_bytes(chain_id), sig),
+                // Checked against the signer-set of the account when applied
+                Signature::Multi(_) => true,
             },
         }
     }
+    // Accounts that appear in the transaction, as the sender or as the counterparty. The
+    // parties of an HTLC being claimed/refunded are only known by the HTLC itself.
+    pub fn involved_addresses(&self, htlc: Option<&Htlc<H, S>>) -> Vec<S::Pub> {
+        let mut addrs = Vec::new();
+        if let Some(src) = &self.src {
+            addrs.push(src.clone());
+        }
+        match &self.data {
+            TransactionData::Delegate { to, .. } | TransactionData::AutoDelegate { to, .. } => {
+                addrs.push(to.clone());
+            }
+            TransactionData::Undelegate { from, .. } | TransactionData::ClaimReward { from } => {
+                addrs.push(from.clone());
+            }
+            TransactionData::Redelegate { from, to, .. } => {
+                addrs.push(from.clone());
+                addrs.push(to.clone());
+            }
+            TransactionData::RegularSend { entries } => {
+                addrs.extend(entries.iter().map(|e| e.dst.clone()));
+            }
+            TransactionData::SpendFrom { owner, entries } => {
+                addrs.push(owner.clone());
+                addrs.extend(entries.iter().map(|e| e.dst.clone()));
+            }
+            TransactionData::VestedSend { dst, .. } => {
+                addrs.push(dst.clone());
+            }
+            TransactionData::CreateHtlc { recipient, .. } => {
+                addrs.push(recipient.clone());
+            }
+            TransactionData::ClaimHtlc { .. } | TransactionData::RefundHtlc { .. } => {
+                if let Some(htlc) = htlc {
+                    addrs.push(htlc.sender.clone());
+                    addrs.push(htlc.recipient.clone());
+                }
+            }
+            TransactionData::Approve { spender, .. } => {
+                addrs.push(spender.clone());
+            }
+            TransactionData::UpdateContract { updates, .. } => {
+                for update in updates.iter() {
+                    match update {
+                        ContractUpdate::Deposit { deposits, .. } => {
+                            addrs.extend(deposits.iter().map(|d| d.src.clone()));
+                        }
+                        ContractUpdate::Withdraw { withdraws, .. } => {
+                            addrs.extend(withdraws.iter().map(|w| w.dst.clone()));
+                        }
+                        ContractUpdate::FunctionCall { .. } => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        let mut unique = Vec::new();
+        for addr in addrs {
+            if !unique.contains(&addr) {
+                unique.push(addr);
+            }
+        }
+        unique
+    }
 }
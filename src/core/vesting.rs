@@ -0,0 +1,123 @@
+use super::hash::Hash;
+use super::money::Amount;
+use super::transaction::{Money, Transaction};
+use crate::crypto::{SignatureScheme, VerifiableRandomFunction};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    std::hash::Hash,
+    Default,
+)]
+pub struct VestingId<H: Hash>(H::Output);
+
+#[derive(Error, Debug)]
+pub enum ParseVestingIdError {
+    #[error("vesting-id invalid")]
+    Invalid,
+}
+
+impl<H: Hash> VestingId<H> {
+    pub fn new<S: SignatureScheme, V: VerifiableRandomFunction>(tx: &Transaction<H, S, V>) -> Self {
+        Self(tx.hash())
+    }
+}
+
+impl<H: Hash> std::fmt::Display for VestingId<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl<H: Hash> FromStr for VestingId<H> {
+    type Err = ParseVestingIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| ParseVestingIdError::Invalid)?;
+        let hash_output = H::Output::try_from(bytes).map_err(|_| ParseVestingIdError::Invalid)?;
+        Ok(Self(hash_output))
+    }
+}
+
+// Tokens sent to an account that unlock linearly between two heights. When `start`
+// and `end` are equal, the whole amount unlocks at once (A time-lock).
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Vesting {
+    pub amount: Money,
+    pub unlocked: Amount, // Already credited to the balance of the recipient
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Vesting {
+    pub fn locked(&self) -> Amount {
+        self.amount.amount - self.unlocked
+    }
+
+    // Total amount that is unlocked once the block with the given height is applied
+    pub fn unlocked_at(&self, height: u64) -> Amount {
+        if height >= self.end {
+            self.amount.amount
+        } else if height <= self.start {
+            Amount(0)
+        } else {
+            let amount: u64 = self.amount.amount.into();
+            Amount(
+                (amount as u128 * (height - self.start) as u128 / (self.end - self.start) as u128)
+                    as u64,
+            )
+        }
+    }
+
+    // Next height, after the given one, in which more tokens are unlocked
+    pub fn next_release(&self, after: u64) -> Option<u64> {
+        if after >= self.end {
+            None
+        } else {
+            Some(std::cmp::max(after + 1, self.start + 1).min(self.end))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_vesting() {
+        let vesting = Vesting {
+            amount: Money::ziesha(100),
+            unlocked: Amount(0),
+            start: 10,
+            end: 20,
+        };
+        assert_eq!(vesting.unlocked_at(5), Amount(0));
+        assert_eq!(vesting.unlocked_at(10), Amount(0));
+        assert_eq!(vesting.unlocked_at(13), Amount(30));
+        assert_eq!(vesting.unlocked_at(20), Amount(100));
+        assert_eq!(vesting.unlocked_at(25), Amount(100));
+        assert_eq!(vesting.next_release(3), Some(11));
+        assert_eq!(vesting.next_release(13), Some(14));
+        assert_eq!(vesting.next_release(20), None);
+    }
+
+    #[test]
+    fn test_time_lock() {
+        let vesting = Vesting {
+            amount: Money::ziesha(100),
+            unlocked: Amount(0),
+            start: 10,
+            end: 10,
+        };
+        assert_eq!(vesting.unlocked_at(9), Amount(0));
+        assert_eq!(vesting.unlocked_at(10), Amount(100));
+        assert_eq!(vesting.next_release(3), Some(10));
+        assert_eq!(vesting.next_release(10), None);
+    }
+}
@@ -76,6 +76,16 @@ impl<H: Hash> SignatureScheme for Ed25519<H> {
     fn verify(pk: &PublicKey, message: &[u8], sig: &Signature) -> bool {
         pk.0.verify(message, &sig.0).is_ok()
     }
+    fn derive_pub(seed: &[u8]) -> PublicKey {
+        // Hash until we land on a valid curve point, its discrete-log remains unknown
+        let mut x = H::hash(seed);
+        loop {
+            if let Ok(public) = ed25519_dalek::PublicKey::from_bytes(x.as_ref()) {
+                return PublicKey(public);
+            }
+            x = H::hash(x.as_ref());
+        }
+    }
 }
 
 impl std::fmt::Display for PublicKey {
@@ -133,4 +143,12 @@ mod tests {
         assert!(Ed25519::<crate::core::Hasher>::verify(&pk, msg, &sig));
         assert!(!Ed25519::<crate::core::Hasher>::verify(&pk, fake_msg, &sig));
     }
+
+    #[test]
+    fn test_ed25519_derive_pub() {
+        let pk = Ed25519::<crate::core::Hasher>::derive_pub(b"ABC");
+        assert_eq!(pk, Ed25519::<crate::core::Hasher>::derive_pub(b"ABC"));
+        assert_ne!(pk, Ed25519::<crate::core::Hasher>::derive_pub(b"ABD"));
+        assert_ne!(pk, Ed25519::<crate::core::Hasher>::generate_keys(b"ABC").0);
+    }
 }
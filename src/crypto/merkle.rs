@@ -1,4 +1,4 @@
-use crate::core::hash::Hash;
+use crate::core::{hash::Hash, Hasher, Header, Transaction};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -108,6 +108,110 @@ impl<H: Hash> MerkleTree<H> {
     }
 }
 
+/// Checks that a transaction is included in the block of the given header, using the
+/// Merkle branch returned by `MerkleTree::prove`. Since siblings are merged in a sorted
+/// order, the position of the transaction in the block is not needed.
+pub fn verify_inclusion(
+    header: &Header,
+    tx: &Transaction,
+    proof: &[<Hasher as Hash>::Output],
+) -> bool {
+    proof.iter().fold(tx.hash(), |curr, sibling| {
+        merge_hash::<Hasher>(&curr, sibling)
+    }) == header.block_root
+}
+
+/// Depth of sparse Merkle trees, leaves are placed by the bits of the hash of their keys
+pub const SPARSE_MERKLE_DEPTH: usize = 256;
+
+/// Bit of a sparse Merkle path at the given depth (0 is the child of the root)
+pub fn sparse_merkle_bit<H: Hash>(path: &H::Output, depth: usize) -> bool {
+    (path.as_ref()[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+/// Parent of two sparse Merkle nodes. Empty subtrees are represented by zero at every
+/// level, so that the nodes of the empty parts of the tree need not be stored.
+pub fn sparse_merkle_merge<H: Hash>(left: &H::Output, right: &H::Output) -> H::Output {
+    let empty = H::Output::default();
+    if *left == empty && *right == empty {
+        return empty;
+    }
+    let mut inp = Vec::new();
+    inp.extend(left.as_ref());
+    inp.extend(right.as_ref());
+    H::hash(&inp)
+}
+
+/// Node of a subtree holding a single leaf. The preimage is one byte longer than the ones
+/// of merged nodes, so a leaf can't be passed off as a subtree and vice versa.
+pub fn sparse_merkle_leaf<H: Hash>(path: &H::Output, leaf: &H::Output) -> H::Output {
+    let mut inp = vec![1u8];
+    inp.extend(path.as_ref());
+    inp.extend(leaf.as_ref());
+    H::hash(&inp)
+}
+
+/// Stored nodes of a compressed sparse Merkle tree. Subtrees holding a single leaf are
+/// shortcut by that leaf, so leaves are placed as high as the other paths let them, and
+/// only the subtrees with two or more leaves have branch nodes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SparseMerkleNode<H: Hash> {
+    Leaf { path: H::Output, leaf: H::Output },
+    Branch(H::Output),
+}
+
+impl<H: Hash> SparseMerkleNode<H> {
+    pub fn hash(&self) -> H::Output {
+        match self {
+            SparseMerkleNode::Leaf { path, leaf } => sparse_merkle_leaf::<H>(path, leaf),
+            SparseMerkleNode::Branch(hash) => *hash,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseMerkleProof<H: Hash> {
+    // Siblings along the path, from the position of the leaf up to the root
+    pub siblings: Vec<H::Output>,
+    // Leaf of another path holding the position, proving that the path has no leaf
+    pub other_leaf: Option<(H::Output, H::Output)>,
+}
+
+impl<H: Hash> SparseMerkleProof<H> {
+    /// Checks the leaf of a path against the root, zero leaves prove the path is empty
+    pub fn verify(&self, root: &H::Output, path: &H::Output, leaf: &H::Output) -> bool {
+        let depth = self.siblings.len();
+        if depth > SPARSE_MERKLE_DEPTH {
+            return false;
+        }
+        let empty = H::Output::default();
+        let mut curr = match &self.other_leaf {
+            None if *leaf == empty => empty,
+            None => sparse_merkle_leaf::<H>(path, leaf),
+            Some((other_path, other_leaf)) => {
+                if *leaf != empty
+                    || *other_leaf == empty
+                    || other_path == path
+                    || (0..depth).any(|d| {
+                        sparse_merkle_bit::<H>(other_path, d) != sparse_merkle_bit::<H>(path, d)
+                    })
+                {
+                    return false;
+                }
+                sparse_merkle_leaf::<H>(other_path, other_leaf)
+            }
+        };
+        for (i, sibling) in self.siblings.iter().enumerate() {
+            curr = if sparse_merkle_bit::<H>(path, depth - i - 1) {
+                sparse_merkle_merge::<H>(sibling, &curr)
+            } else {
+                sparse_merkle_merge::<H>(&curr, sibling)
+            };
+        }
+        curr == *root
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +231,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_inclusion() {
+        let mut block = crate::config::blockchain::get_test_blockchain_config()
+            .genesis
+            .block;
+        let tree = block.merkle_tree();
+        block.header.block_root = tree.root();
+        for (i, tx) in block.body.iter().enumerate() {
+            let proof = tree.prove(i);
+            assert!(verify_inclusion(&block.header, tx, &proof));
+            let other = &block.body[(i + 1) % block.body.len()];
+            assert!(!verify_inclusion(&block.header, other, &proof));
+            assert!(!verify_inclusion(&block.header, tx, &proof[1..]));
+        }
+        let mut header = block.header.clone();
+        header.block_root = Default::default();
+        assert!(!verify_inclusion(&header, &block.body[0], &tree.prove(0)));
+    }
+
+    #[test]
+    fn test_sparse_merkle_proof() {
+        let path = Sha3Hasher::hash(b"key");
+        let leaf = Sha3Hasher::hash(b"value");
+        let sibling = Sha3Hasher::hash(b"sibling");
+
+        // A tree with a single leaf is shortcut by that leaf
+        let root = sparse_merkle_leaf::<Sha3Hasher>(&path, &leaf);
+        let empty = SparseMerkleProof::<Sha3Hasher> {
+            siblings: vec![],
+            other_leaf: None,
+        };
+        assert!(empty.verify(&root, &path, &leaf));
+        assert!(!empty.verify(&root, &path, &sibling));
+        assert!(!empty.verify(&root, &Sha3Hasher::hash(b"other"), &leaf));
+        assert!(empty.verify(&Default::default(), &path, &Default::default()));
+        assert!(!empty.verify(&root, &path, &Default::default()));
+
+        // Paths starting with different bits are placed right below the root
+        let mut other_path = path;
+        other_path[0] ^= 0x80;
+        let other = sparse_merkle_leaf::<Sha3Hasher>(&other_path, &sibling);
+        let with_other = if sparse_merkle_bit::<Sha3Hasher>(&path, 0) {
+            sparse_merkle_merge::<Sha3Hasher>(&other, &root)
+        } else {
+            sparse_merkle_merge::<Sha3Hasher>(&root, &other)
+        };
+        let proof = SparseMerkleProof::<Sha3Hasher> {
+            siblings: vec![other],
+            other_leaf: None,
+        };
+        assert!(proof.verify(&with_other, &path, &leaf));
+        assert!(!proof.verify(&root, &path, &leaf));
+        let misplaced = SparseMerkleProof::<Sha3Hasher> {
+            siblings: vec![other, Default::default()],
+            other_leaf: None,
+        };
+        assert!(!misplaced.verify(&with_other, &path, &leaf));
+
+        // Paths are proven empty by the leaf holding their position
+        let mut absent_path = path;
+        absent_path[31] ^= 1;
+        let absence = SparseMerkleProof::<Sha3Hasher> {
+            siblings: vec![other],
+            other_leaf: Some((path, leaf)),
+        };
+        assert!(absence.verify(&with_other, &absent_path, &Default::default()));
+        assert!(!absence.verify(&with_other, &absent_path, &leaf));
+        assert!(!absence.verify(&with_other, &path, &Default::default()));
+        assert!(!absence.verify(&with_other, &other_path, &Default::default()));
+    }
+
     #[test]
     fn test_calculation() {
         assert_eq!(MerkleTree::<Sha3Hasher>::new(Vec::new()).root(), [0u8; 32]);
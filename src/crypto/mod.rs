@@ -30,6 +30,8 @@ pub trait SignatureScheme: Clone + Serialize + Default {
     fn generate_keys(seed: &[u8]) -> (Self::Pub, Self::Priv);
     fn sign(sk: &Self::Priv, msg: &[u8]) -> Self::Sig;
     fn verify(pk: &Self::Pub, msg: &[u8], sig: &Self::Sig) -> bool;
+    // Deterministic public-key which nobody knows the private-key of
+    fn derive_pub(seed: &[u8]) -> Self::Pub;
 }
 
 pub trait DeriveMpnAccountIndex {
@@ -1,5 +1,5 @@
 use super::*;
-use crate::core::{Address, ContractId, TokenId, UndelegationId};
+use crate::core::{Address, ContractId, HtlcId, ProposalId, TokenId, UndelegationId, VestingId};
 use crate::zk::ZkDataLocator;
 use thiserror::Error;
 
@@ -7,6 +7,10 @@ pub fn height() -> StringKey {
     "HGT".into()
 }
 
+pub fn pruned_height() -> StringKey {
+    "PRN".into()
+}
+
 pub fn outdated() -> StringKey {
     "OUT".into()
 }
@@ -15,6 +19,35 @@ pub fn randomness() -> StringKey {
     "RND".into()
 }
 
+pub fn base_fee() -> StringKey {
+    "BSF".into()
+}
+
+pub fn validators_proof(epoch: u32) -> StringKey {
+    format!("VPF-{:010}", epoch).into()
+}
+
+pub fn light_validators(epoch: u32) -> StringKey {
+    format!("LVL-{:010}", epoch).into()
+}
+
+pub fn burned() -> StringKey {
+    "BRN".into()
+}
+
+pub fn active_config() -> StringKey {
+    "ACF".into()
+}
+
+pub fn validator_set() -> StringKey {
+    "VLS".into()
+}
+
+// Stakers (VRF keys) of the validator-set, as they were when the set was frozen
+pub fn validator_stakers() -> StringKey {
+    "VLK".into()
+}
+
 pub fn block(index: u64) -> StringKey {
     format!("BLK-{:010}", index).into()
 }
@@ -31,6 +64,24 @@ pub fn merkle(index: u64) -> StringKey {
     format!("MRK-{:010}", index).into()
 }
 
+pub fn tx_location(tx_hash: &<Hasher as Hash>::Output) -> StringKey {
+    format!("TXL-{}", hex::encode(tx_hash)).into()
+}
+
+// Prefixes of the keys of the account state, committed to by the state root of headers.
+// Validator-set and randomness are included, so that light clients can check VRF proofs.
+// Left out are the chain history, indexes and callbacks derived from the included keys
+// (E.g. DEK-, DRK-, UDC-, VSC-), consensus and governance bookkeeping (E.g. LIV-, EQV-,
+// PRP-, VOT-) and the contracts, whose states are committed to by their compressed states.
+pub const ACCOUNT_STATE_PREFIXES: [&str; 21] = [
+    "ACB-", "NNC-", "DNC-", "SKR-", "STK-", "DEL-", "ADL-", "ALW-", "FRZ-", "VLS", "VLK", "RND",
+    "RWP-", "TRP-", "TRC-", "UDL-", "UBS-", "VST-", "TKN-", "MSG-", "HTL-",
+];
+
+pub fn state_tree_node(depth: usize, path: &<Hasher as Hash>::Output) -> StringKey {
+    format!("STN-{:03}-{}", depth, hex::encode(path)).into()
+}
+
 pub fn compressed_state_at(contract_id: &ContractId, at: u64) -> StringKey {
     format!("CSA-{:010}-{}", at, contract_id).into()
 }
@@ -51,10 +102,39 @@ pub fn stake(address: &Address) -> StringKey {
     format!("STK-{}", address).into()
 }
 
+pub fn reward_pool(address: &Address) -> StringKey {
+    format!("RWP-{}", address).into()
+}
+
+pub fn liveness(address: &Address) -> StringKey {
+    format!("LIV-{}", address).into()
+}
+
+pub fn multisig(address: &Address) -> StringKey {
+    format!("MSG-{}", address).into()
+}
+
+pub fn htlc(htlc_id: &HtlcId) -> StringKey {
+    format!("HTL-{}", htlc_id).into()
+}
+
 pub fn auto_delegate(delegator: &Address, delegatee: &Address) -> StringKey {
     format!("ADL-{}-{}", delegator, delegatee).into()
 }
 
+// Stake moved away from a staker at some height, slashable until it is unbonded
+pub fn unbonding_stake(staker: &Address, block: u64) -> StringKey {
+    format!("{}-{:010}", unbonding_stake_prefix(staker), block).into()
+}
+
+pub fn unbonding_stake_prefix(staker: &Address) -> String {
+    format!("UBS-{}", staker)
+}
+
+pub fn equivocation(validator: &Address, epoch: u32, slot: u32) -> StringKey {
+    format!("EQV-{}-{}-{}", validator, epoch, slot).into()
+}
+
 pub struct UndelegationDbKey {
     pub undelegation_id: UndelegationId,
     pub undelegator: Address,
@@ -129,6 +209,203 @@ impl UndelegationCallbackDbKey {
     }
 }
 
+pub struct VestingDbKey {
+    pub recipient: Address,
+    pub vesting_id: VestingId,
+}
+impl From<VestingDbKey> for StringKey {
+    fn from(key: VestingDbKey) -> Self {
+        format!(
+            "{}-{}",
+            VestingDbKey::prefix(&key.recipient),
+            key.vesting_id
+        )
+        .into()
+    }
+}
+impl TryFrom<StringKey> for VestingDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 3 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let recipient = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let vesting_id = splitted[2].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(VestingDbKey {
+            recipient,
+            vesting_id,
+        })
+    }
+}
+impl VestingDbKey {
+    pub fn prefix(recipient: &Address) -> String {
+        format!("VST-{}", recipient)
+    }
+}
+
+// Delegators' share of the fees a staker has received in a token other than Ziesha
+pub struct TokenRewardPoolDbKey {
+    pub staker: Address,
+    pub token_id: TokenId,
+}
+impl From<TokenRewardPoolDbKey> for StringKey {
+    fn from(key: TokenRewardPoolDbKey) -> Self {
+        format!(
+            "{}-{}",
+            TokenRewardPoolDbKey::prefix(&key.staker),
+            key.token_id
+        )
+        .into()
+    }
+}
+impl TryFrom<StringKey> for TokenRewardPoolDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 3 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let staker = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let token_id = splitted[2].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(TokenRewardPoolDbKey { staker, token_id })
+    }
+}
+impl TokenRewardPoolDbKey {
+    pub fn prefix(staker: &Address) -> String {
+        format!("TRP-{}", staker)
+    }
+}
+
+// Height in which the next portion of a vesting should be released
+pub struct VestingCallbackDbKey {
+    pub block: u64,
+    pub recipient: Address,
+    pub vesting_id: VestingId,
+}
+impl From<VestingCallbackDbKey> for StringKey {
+    fn from(key: VestingCallbackDbKey) -> Self {
+        format!(
+            "{}{}-{}",
+            VestingCallbackDbKey::prefix(key.block),
+            key.recipient,
+            key.vesting_id
+        )
+        .into()
+    }
+}
+impl TryFrom<StringKey> for VestingCallbackDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 4 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let block = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let recipient = splitted[2].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let vesting_id = splitted[3].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(VestingCallbackDbKey {
+            block,
+            recipient,
+            vesting_id,
+        })
+    }
+}
+impl VestingCallbackDbKey {
+    pub fn prefix(block: u64) -> String {
+        format!("VSC-{}-", block)
+    }
+}
+
+pub struct ProposalDbKey {
+    pub proposal_id: ProposalId,
+}
+impl From<ProposalDbKey> for StringKey {
+    fn from(key: ProposalDbKey) -> Self {
+        format!("{}-{}", ProposalDbKey::prefix(), key.proposal_id).into()
+    }
+}
+impl TryFrom<StringKey> for ProposalDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 2 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let proposal_id = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(ProposalDbKey { proposal_id })
+    }
+}
+impl ProposalDbKey {
+    pub fn prefix() -> String {
+        "PRP".into()
+    }
+}
+
+pub struct VoteDbKey {
+    pub proposal_id: ProposalId,
+    pub voter: Address,
+}
+impl From<VoteDbKey> for StringKey {
+    fn from(key: VoteDbKey) -> Self {
+        format!("{}-{}", VoteDbKey::prefix(&key.proposal_id), key.voter).into()
+    }
+}
+impl TryFrom<StringKey> for VoteDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 3 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let proposal_id = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let voter = splitted[2].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(VoteDbKey { proposal_id, voter })
+    }
+}
+impl VoteDbKey {
+    pub fn prefix(proposal_id: &ProposalId) -> String {
+        format!("VOT-{}", proposal_id)
+    }
+}
+
+// Parameter changes of accepted proposals, sorted by their activation heights
+pub struct ConfigOverrideDbKey {
+    pub activation_height: u64,
+    pub proposal_id: ProposalId,
+}
+impl From<ConfigOverrideDbKey> for StringKey {
+    fn from(key: ConfigOverrideDbKey) -> Self {
+        format!(
+            "{}-{:010}-{}",
+            ConfigOverrideDbKey::prefix(),
+            key.activation_height,
+            key.proposal_id
+        )
+        .into()
+    }
+}
+impl TryFrom<StringKey> for ConfigOverrideDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 3 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let activation_height = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let proposal_id = splitted[2].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(ConfigOverrideDbKey {
+            activation_height,
+            proposal_id,
+        })
+    }
+}
+impl ConfigOverrideDbKey {
+    pub fn prefix() -> String {
+        "CFG".into()
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ParseDbKeyError {
     #[error("invalid db-key")]
@@ -255,6 +532,48 @@ impl DelegateeRankDbKey {
     }
 }
 
+// Transactions touching an address, newest ones come first
+pub struct AddressHistoryDbKey {
+    pub address: Address,
+    pub block: u64,
+    pub index: u32,
+}
+impl From<AddressHistoryDbKey> for StringKey {
+    fn from(key: AddressHistoryDbKey) -> Self {
+        format!(
+            "{}-{:016x}-{:08x}",
+            AddressHistoryDbKey::prefix(&key.address),
+            u64::MAX - key.block,
+            u32::MAX - key.index
+        )
+        .into()
+    }
+}
+impl TryFrom<StringKey> for AddressHistoryDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split("-").collect::<Vec<_>>();
+        if splitted.len() != 4 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let address = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let block = u64::MAX
+            - u64::from_str_radix(splitted[2], 16).map_err(|_| ParseDbKeyError::Invalid)?;
+        let index = u32::MAX
+            - u32::from_str_radix(splitted[3], 16).map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(AddressHistoryDbKey {
+            address,
+            block,
+            index,
+        })
+    }
+}
+impl AddressHistoryDbKey {
+    pub fn prefix(address: &Address) -> String {
+        format!("HIS-{}", address)
+    }
+}
+
 pub fn delegate(delegator: &Address, delegatee: &Address) -> StringKey {
     format!("DEL-{}-{}", delegator, delegatee).into()
 }
@@ -275,6 +594,23 @@ pub fn contract(contract_id: &ContractId) -> StringKey {
     format!("CON-{}", contract_id).into()
 }
 
+pub fn allowance(owner: &Address, spender: &Address, token_id: TokenId) -> StringKey {
+    format!("ALW-{}-{}-{}", owner, spender, token_id).into()
+}
+
+pub fn frozen(address: &Address, token_id: TokenId) -> StringKey {
+    format!("FRZ-{}-{}", address, token_id).into()
+}
+
+// Reward-per-stake of a token reward-pool when a delegation last claimed from it
+pub fn token_reward_checkpoint(
+    delegator: &Address,
+    delegatee: &Address,
+    token_id: TokenId,
+) -> StringKey {
+    format!("TRC-{}-{}-{}", delegator, delegatee, token_id).into()
+}
+
 pub fn token(token_id: &TokenId) -> StringKey {
     format!("TKN-{}", token_id).into()
 }
@@ -1,11 +1,15 @@
 pub mod keys;
 
-use crate::blockchain::{ZkBlockchainPatch, ZkCompressedStateChange};
+use crate::blockchain::{
+    light::{EpochValidators, ValidatorsProof},
+    ZkBlockchainPatch, ZkCompressedStateChange,
+};
 use crate::core::{
-    hash::Hash, Amount, Block, ContractAccount, ContractId, Delegate, Hasher, Header, Ratio,
-    Staker, Token, Undelegation,
+    hash::Hash, Address, Amount, Block, ConfigParam, ContractAccount, ContractId, Delegate, Hasher,
+    Header, Htlc, Liveness, Multisig, Proposal, Ratio, RewardPool, Staker, Token, TxLocation,
+    UnbondingStake, Undelegation, Vesting,
 };
-use crate::crypto::merkle::MerkleTree;
+use crate::crypto::merkle::{MerkleTree, SparseMerkleNode};
 use crate::zk::{
     ZkCompressedState, ZkContract, ZkDataPairs, ZkDeltaPairs, ZkScalar, ZkState, ZkStateModel,
 };
@@ -97,16 +101,28 @@ gen_try_into!(
     <Hasher as Hash>::Output,
     Ratio,
     Delegate,
+    RewardPool,
+    Liveness,
+    Multisig,
+    Htlc,
     Undelegation,
+    Vec<UnbondingStake>,
+    Vesting,
+    TxLocation,
+    Proposal,
+    Vec<ConfigParam>,
     Staker,
     ContractAccount,
     Header,
     Block,
     Vec<WriteOp>,
     MerkleTree<Hasher>,
+    SparseMerkleNode<Hasher>,
     ZkContract,
     ZkCompressedState,
     Vec<ContractId>,
+    Vec<(Address, Amount)>,
+    Vec<(Address, Staker)>,
     HashMap<ContractId, ContractAccount>,
     HashMap<ContractId, ZkCompressedStateChange>,
     ZkState,
@@ -117,6 +133,9 @@ gen_try_into!(
     ZkDeltaPairs,
     Token,
     Amount,
+    (u64, ValidatorsProof),
+    EpochValidators,
+    bool,
     ()
 );
 gen_from!(
@@ -127,16 +146,28 @@ gen_from!(
     <Hasher as Hash>::Output,
     Ratio,
     Delegate,
+    RewardPool,
+    Liveness,
+    Multisig,
+    Htlc,
     Undelegation,
+    Vec<UnbondingStake>,
+    Vesting,
+    TxLocation,
+    Proposal,
+    Vec<ConfigParam>,
     Staker,
     ContractAccount,
     Header,
     &Block,
     Vec<WriteOp>,
     MerkleTree<Hasher>,
+    SparseMerkleNode<Hasher>,
     ZkContract,
     ZkCompressedState,
     Vec<ContractId>,
+    Vec<(Address, Amount)>,
+    Vec<(Address, Staker)>,
     HashMap<ContractId, ContractAccount>,
     HashMap<ContractId, ZkCompressedStateChange>,
     &ZkState,
@@ -147,6 +178,9 @@ gen_from!(
     &ZkDeltaPairs,
     &Token,
     Amount,
+    (u64, ValidatorsProof),
+    EpochValidators,
+    bool,
     ()
 );
 
@@ -84,7 +84,7 @@ fn withdraws_root(
                 ),
                 (
                     ZkDataLocator(vec![i as u64, 5]),
-                    Some(withdraw.fingerprint()),
+                    Some(withdraw.fingerprint(None)),
                 ),
                 (ZkDataLocator(vec![i as u64, 6]), Some(withdraw.calldata)),
             ]
@@ -126,6 +126,7 @@ fn test_empty_update_circuit() {
         aux_data: aux,
         next_state: ZkScalar::from(123),
         fee_token: TokenId::Ziesha,
+        chain_id: None,
 
         transitions: vec![UpdateTransition::null(3, 3); 4],
     };
@@ -22,6 +22,9 @@ pub struct UpdateCircuit {
     pub next_state: ZkScalar, // Public
     pub fee_token: TokenId,   // Private
 
+    // Hardcoded in the circuit, so each chain-id gets its own verifier key
+    pub chain_id: Option<ZkScalar>,
+
     pub transitions: Vec<UpdateTransition>, // Secret :)
 }
 
@@ -36,6 +39,7 @@ impl MpnCircuit for UpdateCircuit {
             aux_data: Default::default(),
             next_state: Default::default(),
             fee_token: Default::default(),
+            chain_id: None,
             transitions: vec![
                 UpdateTransition::null(log4_tree_size, log4_token_tree_size);
                 1 << (2 * log4_batch_size)
@@ -441,6 +445,16 @@ impl Circuit<BellmanFr> for UpdateCircuit {
                 ],
             )?;
 
+            // Signed message is bound to the chain-id (See `MpnTransaction::signing_hash`)
+            let tx_msg_wit = if let Some(chain_id) = self.chain_id {
+                poseidon::poseidon(
+                    &mut *cs,
+                    &[&tx_hash_wit, &Number::constant::<CS>(chain_id.into())],
+                )?
+            } else {
+                tx_hash_wit
+            };
+
             let tx_sig_r_wit = AllocatedPoint::alloc(&mut *cs, || Ok(trans.tx.sig.r))?;
             // Check if sig_r resides on curve
             tx_sig_r_wit.assert_on_curve(&mut *cs, &enabled_wit)?;
@@ -452,7 +466,7 @@ impl Circuit<BellmanFr> for UpdateCircuit {
                 &mut *cs,
                 &enabled_wit,
                 &src_addr_wit,
-                &tx_hash_wit,
+                &tx_msg_wit,
                 &tx_sig_r_wit,
                 &tx_sig_s_wit,
             )?;
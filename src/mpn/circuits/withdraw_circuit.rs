@@ -105,7 +105,7 @@ impl Circuit<BellmanFr> for WithdrawCircuit {
             // Tx amount should always have at most 64 bits
             let fingerprint = AllocatedNum::alloc(&mut *cs, || {
                 Ok(if trans.enabled {
-                    trans.tx.payment.fingerprint()
+                    trans.fingerprint
                 } else {
                     ZkScalar::ZERO
                 }
@@ -5,8 +5,8 @@ pub mod withdraw;
 
 use crate::blockchain::BlockchainError;
 use crate::core::{
-    Amount, ContractId, ContractUpdate, Money, MpnAddress, MpnDeposit, MpnWithdraw, Signature,
-    TokenId, Transaction, TransactionAndDelta, TransactionData,
+    Amount, ChainId, ContractId, ContractUpdate, Money, MpnAddress, MpnDeposit, MpnWithdraw,
+    Signature, TokenId, Transaction, TransactionAndDelta, TransactionData,
 };
 use crate::db::{KvStore, WriteOp};
 use crate::wallet::TxBuilder;
@@ -24,6 +24,8 @@ pub enum MpnError {
     BlockchainError(#[from] BlockchainError),
     #[error("insufficient workers in the pool")]
     InsufficientWorkers,
+    #[error("verifier key of the chain-bound update circuit is not configured")]
+    ChainBoundUpdateKeyMissing,
 }
 
 fn extract_delta(ops: &[WriteOp]) -> ZkDeltaPairs {
@@ -113,7 +115,12 @@ impl MpnWorkPool {
                         proof: self.solutions[&i].clone(),
                     },
                     MpnWorkData::Update(trans) => {
-                        assert!(trans.iter().all(|t| t.tx.fee.token_id == TokenId::Ziesha));
+                        // All txs of an update batch pay their fees in a single token
+                        let fee_token = trans
+                            .first()
+                            .map(|t| t.tx.fee.token_id)
+                            .unwrap_or(TokenId::Ziesha);
+                        assert!(trans.iter().all(|t| t.tx.fee.token_id == fee_token));
                         let fee_sum = trans
                             .iter()
                             .map(|t| Into::<u64>::into(t.tx.fee.amount))
@@ -123,7 +130,7 @@ impl MpnWorkPool {
                             next_state: self.works[&i].new_root.clone(),
                             proof: self.solutions[&i].clone(),
                             fee: Money {
-                                token_id: TokenId::Ziesha,
+                                token_id: fee_token,
                                 amount: fee_sum.into(),
                             },
                         }
@@ -139,6 +146,7 @@ impl MpnWorkPool {
                     contract_id: self.config.mpn_contract_id.clone(),
                     updates,
                 },
+                valid_until: None,
                 sig: Signature::Unsigned,
             };
             tx_builder.sign_tx(&mut update);
@@ -166,6 +174,10 @@ pub struct MpnConfig {
     pub deposit_vk: ZkVerifierKey,
     pub withdraw_vk: ZkVerifierKey,
     pub update_vk: ZkVerifierKey,
+    // Verifier key of the update circuit bound to the chain-id, replaces `update_vk`
+    // once `MpnReplayProtection` is active
+    #[serde(default)]
+    pub chain_bound_update_vk: Option<ZkVerifierKey>,
 }
 
 impl MpnConfig {
@@ -220,6 +232,9 @@ pub struct MpnWork {
     pub data: MpnWorkData,
     pub new_root: ZkCompressedState,
     pub worker: MpnWorker,
+    // Chain-id the update circuit should be bound to (See `UpdateCircuit::chain_id`)
+    #[serde(default)]
+    pub chain_id: Option<ZkScalar>,
 }
 
 impl MpnWork {
@@ -227,7 +242,10 @@ impl MpnWork {
         match &self.data {
             MpnWorkData::Deposit(_) => &self.config.deposit_vk,
             MpnWorkData::Withdraw(_) => &self.config.withdraw_vk,
-            MpnWorkData::Update(_) => &self.config.update_vk,
+            MpnWorkData::Update(_) => match (&self.chain_id, &self.config.chain_bound_update_vk) {
+                (Some(_), Some(vk)) => vk,
+                _ => &self.config.update_vk,
+            },
         }
         .clone()
     }
@@ -259,7 +277,14 @@ pub fn prepare_works<K: KvStore>(
     mut validator_tx_builder_mpn_nonce: u32,
     validator_tx_builder: TxBuilder,
     user_tx_builder: TxBuilder,
+    chain_id: Option<ChainId>,
+    mpn_chain_id: Option<ChainId>,
 ) -> Result<MpnWorkPool, MpnError> {
+    if mpn_chain_id.is_some() && config.chain_bound_update_vk.is_none() {
+        return Err(MpnError::ChainBoundUpdateKeyMissing);
+    }
+    let validator_tx_builder = validator_tx_builder.with_mpn_chain_id(mpn_chain_id);
+    let work_chain_id = mpn_chain_id.as_ref().map(crate::zk::chain_id_scalar);
     let mut mirror = db.mirror();
     let mut works = Vec::new();
     let workers = workers.values().collect::<Vec<_>>();
@@ -303,6 +328,7 @@ pub fn prepare_works<K: KvStore>(
             new_root,
             data: MpnWorkData::Deposit(transitions),
             worker: workers[worker_id].clone(),
+            chain_id: work_chain_id,
         });
         *rewards
             .entry(workers[worker_id].mpn_address.clone())
@@ -318,6 +344,7 @@ pub fn prepare_works<K: KvStore>(
             config.log4_withdraw_batch_size,
             &mut mirror,
             &withdraws,
+            chain_id.as_ref(),
         )?;
         log::info!("Made MPN-Withdraw block of {} txs.", transitions.len());
         for (i, tx) in transitions.iter().enumerate() {
@@ -329,6 +356,7 @@ pub fn prepare_works<K: KvStore>(
             new_root,
             data: MpnWorkData::Withdraw(transitions),
             worker: workers[worker_id].clone(),
+            chain_id: work_chain_id,
         });
         *rewards
             .entry(workers[worker_id].mpn_address.clone())
@@ -376,6 +404,7 @@ pub fn prepare_works<K: KvStore>(
             TokenId::Ziesha,
             &mut mirror,
             &updates,
+            mpn_chain_id.as_ref(),
         )?;
         log::info!("Made MPN-Update block of {} txs.", transitions.len());
         for (i, tx) in transitions.iter().enumerate() {
@@ -388,6 +417,7 @@ pub fn prepare_works<K: KvStore>(
             new_root,
             data: MpnWorkData::Update(transitions),
             worker: workers[worker_id].clone(),
+            chain_id: work_chain_id,
         });
         worker_id = (worker_id + 1) % workers.len();
     }
@@ -441,6 +471,7 @@ pub struct WithdrawTransition {
     pub before_token_hash: ZkScalar,
     pub fee_token_index: u64,
     pub fee_balance_proof: Vec<[ZkScalar; 3]>,
+    pub fingerprint: ZkScalar,
 }
 
 impl WithdrawTransition {
@@ -457,6 +488,7 @@ impl WithdrawTransition {
             before_token_hash: Default::default(),
             fee_token_index: Default::default(),
             fee_balance_proof: vec![Default::default(); log4_token_tree_size as usize],
+            fingerprint: Default::default(),
         }
     }
 }
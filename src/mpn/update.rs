@@ -1,10 +1,11 @@
 use super::*;
 use crate::blockchain::BlockchainError;
-use crate::core::{ContractId, TokenId, ZkHasher};
+use crate::core::{ChainId, ContractId, TokenId, ZkHasher};
 use crate::db::{keys, KvStore, WriteOp};
 use crate::zk::{KvStoreStateManager, ZkCompressedState, ZkDataLocator, ZkScalar};
 use rayon::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn update<K: KvStore>(
     mpn_contract_id: ContractId,
     mpn_log4_account_capacity: u8,
@@ -13,6 +14,7 @@ pub fn update<K: KvStore>(
     fee_token: TokenId,
     db: &mut K,
     txs: &[MpnTransaction],
+    chain_id: Option<&ChainId>,
 ) -> Result<(ZkCompressedState, ZkPublicInputs, Vec<UpdateTransition>), BlockchainError> {
     let mut rejected = Vec::new();
     let mut accepted = Vec::new();
@@ -32,6 +34,7 @@ pub fn update<K: KvStore>(
             tx.fee.token_id == fee_token
                 && tx.src_pub_key.is_on_curve()
                 && tx.dst_pub_key.is_on_curve()
+                && tx.verify_signature(chain_id)
         })
         .collect::<Vec<_>>();
 
@@ -1,6 +1,6 @@
 use super::*;
 use crate::blockchain::BlockchainError;
-use crate::core::{ContractId, ZkHasher};
+use crate::core::{ChainId, ContractId, ZkHasher};
 use crate::db::{keys, KvStore, WriteOp};
 use crate::zk::{
     KvStoreStateManager, ZkCompressedState, ZkDataLocator, ZkDeltaPairs, ZkScalar, ZkStateBuilder,
@@ -14,6 +14,7 @@ pub fn withdraw<K: KvStore>(
     log4_batch_size: u8,
     db: &mut K,
     txs: &[MpnWithdraw],
+    chain_id: Option<&ChainId>,
 ) -> Result<(ZkCompressedState, ZkPublicInputs, Vec<WithdrawTransition>), BlockchainError> {
     let mut mirror = db.mirror();
 
@@ -56,7 +57,7 @@ pub fn withdraw<K: KvStore>(
 
         if (acc.address != Default::default() && tx.zk_address.0.decompress() != acc.address)
             || !tx.verify_calldata::<ZkHasher>()
-            || !tx.verify_signature::<ZkHasher>()
+            || !tx.verify_signature::<ZkHasher>(chain_id)
             || tx.zk_nonce != acc.withdraw_nonce + 1
             || tx.payment.amount.token_id != acc_token.token_id
             || tx.payment.amount.amount > acc_token.amount
@@ -149,6 +150,7 @@ pub fn withdraw<K: KvStore>(
                 token_balance_proof,
                 fee_balance_proof,
                 before_token_hash,
+                fingerprint: tx.payment.fingerprint(chain_id),
             });
             accepted.push(tx);
         }
@@ -212,10 +214,7 @@ pub fn withdraw<K: KvStore>(
                         ZkDataLocator(vec![i as u64, 4]),
                         Some(trans.tx.payment.fee.amount.into()),
                     ),
-                    (
-                        ZkDataLocator(vec![i as u64, 5]),
-                        Some(trans.tx.payment.fingerprint()),
-                    ),
+                    (ZkDataLocator(vec![i as u64, 5]), Some(trans.fingerprint)),
                     (ZkDataLocator(vec![i as u64, 6]), Some(calldata)),
                 ]
                 .into(),
@@ -257,6 +256,7 @@ mod tests {
             conf.log4_withdraw_batch_size,
             &mut db,
             &[],
+            None,
         )
         .unwrap();
     }
@@ -317,6 +317,7 @@ mod tests {
                 conf.log4_withdraw_batch_size,
                 &mut db,
                 &[withdrawal],
+                None,
             )
             .unwrap()
             .2
@@ -16,6 +16,13 @@ pub async fn get_account<K: KvStore, B: Blockchain<K>>(
             req.address.parse()?,
             context.blockchain.config().mpn_config.mpn_contract_id,
         )?,
+        multisig: context.blockchain.get_multisig(req.address.parse()?)?,
+        vestings: context
+            .blockchain
+            .get_vestings(req.address.parse()?)?
+            .into_iter()
+            .map(|(id, v)| (id.to_string(), v))
+            .collect(),
     })
 }
 
@@ -53,14 +60,18 @@ mod tests {
             resp,
             GetAccountResponse {
                 nonce: 0,
-                mpn_deposit_nonce: 0
+                mpn_deposit_nonce: 0,
+                multisig: None,
+                vestings: vec![]
             }
         );
         assert_eq!(
             resp_treasury,
             GetAccountResponse {
                 nonce: 0,
-                mpn_deposit_nonce: 0
+                mpn_deposit_nonce: 0,
+                multisig: None,
+                vestings: vec![]
             }
         );
         let resp_invalid = get_account(
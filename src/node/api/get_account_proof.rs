@@ -0,0 +1,73 @@
+use super::messages::{GetAccountProofRequest, GetAccountProofResponse};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::core::{Address, TokenId};
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_account_proof<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetAccountProofRequest,
+) -> Result<GetAccountProofResponse, NodeError> {
+    let context = context.read().await;
+    let token_id: TokenId = req.token_id.parse()?;
+    let address: Address = req.address.parse()?;
+    Ok(GetAccountProofResponse {
+        header: context.blockchain.get_tip()?,
+        balance: context.blockchain.get_balance(address.clone(), token_id)?,
+        proof: context.blockchain.prove_balance(address, token_id)?,
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::verify_balance_proof;
+    use crate::core::Amount;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_account_proof() {
+        let ctx = test_context();
+        let abc = TxBuilder::new(&Vec::from("ABC")).get_address();
+        let resp = get_account_proof(
+            ctx.clone(),
+            GetAccountProofRequest {
+                address: abc.to_string(),
+                token_id: TokenId::Ziesha.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.balance, Amount(10000));
+        assert_eq!(resp.header.number, 100);
+        assert!(verify_balance_proof(
+            &resp.header,
+            &abc,
+            TokenId::Ziesha,
+            resp.balance,
+            &resp.proof
+        ));
+        assert!(!verify_balance_proof(
+            &resp.header,
+            &abc,
+            TokenId::Ziesha,
+            Amount(20000),
+            &resp.proof
+        ));
+
+        assert!(get_account_proof(
+            ctx.clone(),
+            GetAccountProofRequest {
+                address: "not-an-address".into(),
+                token_id: TokenId::Ziesha.to_string(),
+            },
+        )
+        .await
+        .is_err());
+    }
+}
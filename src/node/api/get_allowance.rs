@@ -0,0 +1,53 @@
+use super::messages::{GetAllowanceRequest, GetAllowanceResponse};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_allowance<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetAllowanceRequest,
+) -> Result<GetAllowanceResponse, NodeError> {
+    let context = context.read().await;
+    Ok(GetAllowanceResponse {
+        allowance: context.blockchain.get_allowance(
+            req.owner.parse()?,
+            req.spender.parse()?,
+            req.token_id.parse()?,
+        )?,
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Amount;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_allowance() {
+        let ctx = test_context();
+        let abc_addr = TxBuilder::new(&Vec::from("ABC")).get_address();
+        let spender_addr = TxBuilder::new(&Vec::from("SPENDER")).get_address();
+        let resp = get_allowance(
+            ctx.clone(),
+            GetAllowanceRequest {
+                owner: abc_addr.to_string(),
+                spender: spender_addr.to_string(),
+                token_id: "Ziesha".into(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            resp,
+            GetAllowanceResponse {
+                allowance: Amount(0)
+            }
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::messages::{GetBalanceRequest, GetBalanceResponse};
 use super::{NodeContext, NodeError};
 use crate::blockchain::Blockchain;
-use crate::core::TokenId;
+use crate::core::{Address, Amount, TokenId};
 use crate::db::KvStore;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -16,10 +16,16 @@ pub async fn get_balance<K: KvStore, B: Blockchain<K>>(
         .blockchain
         .get_token(token_id)?
         .ok_or(crate::blockchain::BlockchainError::TokenNotFound)?;
+    let address: Address = req.address.parse()?;
+    let locked = context
+        .blockchain
+        .get_vestings(address.clone())?
+        .into_iter()
+        .filter(|(_, v)| v.amount.token_id == token_id)
+        .fold(Amount(0), |sum, (_, v)| sum + v.locked());
     Ok(GetBalanceResponse {
-        balance: context
-            .blockchain
-            .get_balance(req.address.parse()?, token_id)?,
+        balance: context.blockchain.get_balance(address, token_id)?,
+        locked,
         name: tkn.name,
         symbol: tkn.symbol,
     })
@@ -31,7 +37,6 @@ use super::tests::*;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::Amount;
     use crate::node::TxBuilder;
 
     #[tokio::test]
@@ -51,6 +56,7 @@ mod tests {
             resp,
             GetBalanceResponse {
                 balance: Amount(10000),
+                locked: Amount(0),
                 name: "Ziesha".into(),
                 symbol: "ZSH".into(),
             }
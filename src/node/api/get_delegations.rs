@@ -1,6 +1,7 @@
 use super::messages::{GetDelegationsRequest, GetDelegationsResponse};
 use super::{NodeContext, NodeError};
 use crate::blockchain::Blockchain;
+use crate::core::Address;
 use crate::db::KvStore;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -10,10 +11,23 @@ pub async fn get_delegations<K: KvStore, B: Blockchain<K>>(
     req: GetDelegationsRequest,
 ) -> Result<GetDelegationsResponse, NodeError> {
     let context = context.read().await;
+    let address: Address = req.address.parse()?;
+    let delegatees = context
+        .blockchain
+        .get_delegatees(address.clone(), Some(req.top))?;
     Ok(GetDelegationsResponse {
-        delegatees: context
-            .blockchain
-            .get_delegatees(req.address.parse()?, Some(req.top))?
+        pending_rewards: delegatees
+            .iter()
+            .map(|(k, _)| {
+                Ok((
+                    k.to_string(),
+                    context
+                        .blockchain
+                        .get_pending_reward(address.clone(), k.clone())?,
+                ))
+            })
+            .collect::<Result<_, NodeError>>()?,
+        delegatees: delegatees
             .into_iter()
             .map(|(k, v)| (k.to_string(), v))
             .collect(),
@@ -83,6 +97,7 @@ mod tests {
         .unwrap();
         assert_eq!(resp.delegatees.len(), 3);
         assert_eq!(resp.delegators.len(), 0);
+        assert_eq!(resp.pending_rewards.len(), 3);
         for val in [validator1, validator2, validator3] {
             assert_eq!(
                 resp.delegatees.get(&val.get_address().to_string()).cloned(),
@@ -0,0 +1,100 @@
+use super::messages::{GetExplorerHistoryRequest, GetExplorerHistoryResponse, InputError};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::client::explorer::ExplorerHistoryEntry;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const HISTORY_PAGE_SIZE: usize = 50;
+
+pub async fn get_explorer_history<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetExplorerHistoryRequest,
+) -> Result<GetExplorerHistoryResponse, NodeError> {
+    let context = context.read().await;
+    let address = req.address.parse().map_err(|_| InputError::Invalid)?;
+    let mut history = Vec::new();
+    for location in context
+        .blockchain
+        .get_history(address, req.page, HISTORY_PAGE_SIZE)?
+    {
+        let block = context.blockchain.get_block(location.block)?;
+        let tx = block
+            .body
+            .get(location.index as usize)
+            .ok_or(InputError::Invalid)?;
+        history.push(ExplorerHistoryEntry {
+            hash: hex::encode(tx.hash()),
+            block: location.block,
+            index: location.index,
+            tx: tx.into(),
+        });
+    }
+    Ok(GetExplorerHistoryResponse { history })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Address;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_explorer_history() {
+        let ctx = test_context();
+        let abc = TxBuilder::new(&Vec::from("ABC")).get_address();
+        let resp = get_explorer_history(
+            ctx.clone(),
+            GetExplorerHistoryRequest {
+                address: abc.to_string(),
+                page: 0,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!resp.history.is_empty());
+        let genesis = ctx.read().await.blockchain.get_block(0).unwrap();
+        for entry in resp.history.iter() {
+            assert_eq!(entry.block, 0);
+            let tx = &genesis.body[entry.index as usize];
+            assert_eq!(entry.hash, hex::encode(tx.hash()));
+            assert!(tx.involved_addresses(None).contains(&abc));
+        }
+
+        let resp = get_explorer_history(
+            ctx.clone(),
+            GetExplorerHistoryRequest {
+                address: abc.to_string(),
+                page: 1,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(resp.history.is_empty());
+
+        let resp = get_explorer_history(
+            ctx.clone(),
+            GetExplorerHistoryRequest {
+                address: Address::default().to_string(),
+                page: 0,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(resp.history.is_empty());
+
+        assert!(get_explorer_history(
+            ctx.clone(),
+            GetExplorerHistoryRequest {
+                address: "not-an-address".into(),
+                page: 0,
+            },
+        )
+        .await
+        .is_err());
+    }
+}
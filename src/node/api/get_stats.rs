@@ -18,6 +18,13 @@ pub async fn get_stats<K: KvStore, B: Blockchain<K>>(
         height: context.blockchain.get_height()?,
         nodes: context.peer_manager.node_count(),
         next_reward: context.blockchain.next_reward()?,
+        base_fee: context.blockchain.get_base_fee()?,
+        accepted_fee_tokens: context
+            .opts
+            .accepted_fee_tokens
+            .iter()
+            .map(|(id, rate)| (id.to_string(), *rate))
+            .collect(),
         timestamp: ts,
         timestamp_offset: context.timestamp_offset,
         epoch,
@@ -27,3 +34,36 @@ pub async fn get_stats<K: KvStore, B: Blockchain<K>>(
         validator_claim: context.validator_claim.clone(),
     })
 }
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Amount, ExchangeRate, TokenId};
+
+    #[tokio::test]
+    async fn test_get_stats_fee_tokens() {
+        let ctx = test_context();
+        let token_id: TokenId =
+            "0x0001020304050607080900010203040506070809000102030405060708090001"
+                .parse()
+                .unwrap();
+        let rate = ExchangeRate {
+            ziesha: Amount(2),
+            token: Amount(3),
+        };
+        ctx.write()
+            .await
+            .opts
+            .accepted_fee_tokens
+            .insert(token_id, rate);
+        let resp = get_stats(ctx.clone(), GetStatsRequest {}).await.unwrap();
+        assert_eq!(resp.height, 101);
+        assert_eq!(
+            resp.accepted_fee_tokens,
+            [(token_id.to_string(), rate)].into_iter().collect()
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use super::messages::{GetTransactionRequest, GetTransactionResponse, InputError};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::core::{hash::Hash, Hasher};
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_transaction<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetTransactionRequest,
+) -> Result<GetTransactionResponse, NodeError> {
+    let context = context.read().await;
+    let hash =
+        <Hasher as Hash>::Output::try_from(hex::decode(req.hash).map_err(|_| InputError::Invalid)?)
+            .map_err(|_| InputError::Invalid)?;
+    Ok(match context.blockchain.get_tx_location(hash)? {
+        Some(location) => {
+            let block = context.blockchain.get_block(location.block)?;
+            let tx = block
+                .body
+                .get(location.index as usize)
+                .ok_or(InputError::Invalid)?;
+            GetTransactionResponse {
+                tx: Some(tx.into()),
+                location: Some(location),
+                confirmations: context.blockchain.get_height()? - location.block,
+            }
+        }
+        None => GetTransactionResponse {
+            tx: None,
+            location: None,
+            confirmations: 0,
+        },
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TxLocation;
+
+    #[tokio::test]
+    async fn test_get_transaction() {
+        let ctx = test_context();
+        let genesis = ctx.read().await.blockchain.get_block(0).unwrap();
+        let resp = get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                hash: hex::encode(genesis.body[2].hash()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(resp.tx.unwrap().memo, "Dummy tx");
+        assert_eq!(resp.location, Some(TxLocation { block: 0, index: 2 }));
+        assert_eq!(resp.confirmations, 101);
+
+        let resp = get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                hash: hex::encode(<Hasher as Hash>::hash(b"unknown")),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(resp.tx.is_none() && resp.location.is_none());
+        assert_eq!(resp.confirmations, 0);
+
+        assert!(get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                hash: "not-a-hash".into()
+            },
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use super::messages::{GetTransactionProofRequest, GetTransactionProofResponse, InputError};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::core::{hash::Hash, Hasher};
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_transaction_proof<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetTransactionProofRequest,
+) -> Result<GetTransactionProofResponse, NodeError> {
+    let context = context.read().await;
+    let hash =
+        <Hasher as Hash>::Output::try_from(hex::decode(req.hash).map_err(|_| InputError::Invalid)?)
+            .map_err(|_| InputError::Invalid)?;
+    Ok(match context.blockchain.get_tx_location(hash)? {
+        Some(location) => GetTransactionProofResponse {
+            header: Some(context.blockchain.get_header(location.block)?),
+            proof: context
+                .blockchain
+                .get_merkle_tree(location.block)?
+                .prove(location.index as usize),
+        },
+        None => GetTransactionProofResponse {
+            header: None,
+            proof: vec![],
+        },
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Money;
+    use crate::crypto::merkle::verify_inclusion;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_transaction_proof() {
+        let ctx = test_context();
+        let abc = TxBuilder::new(&Vec::from("ABC"));
+        let txs = (1..=3)
+            .map(|nonce| {
+                abc.create_transaction(
+                    "".into(),
+                    Default::default(),
+                    Money::ziesha(100),
+                    Money::ziesha(0),
+                    nonce,
+                )
+            })
+            .collect::<Vec<_>>();
+        let block = {
+            let mut ctx = ctx.write().await;
+            let validator = ctx.validator_wallet.clone();
+            let block = ctx
+                .blockchain
+                .draft_block(6100, &txs, &validator, &Default::default(), true)
+                .unwrap()
+                .unwrap()
+                .block;
+            ctx.blockchain.extend(101, &[block.clone()]).unwrap();
+            block
+        };
+        assert_eq!(block.body.len(), 3);
+        for tx in block.body.iter() {
+            let resp = get_transaction_proof(
+                ctx.clone(),
+                GetTransactionProofRequest {
+                    hash: hex::encode(tx.hash()),
+                },
+            )
+            .await
+            .unwrap();
+            let header = resp.header.unwrap();
+            assert_eq!(header, block.header);
+            assert!(verify_inclusion(&header, tx, &resp.proof));
+            let genesis = ctx.read().await.blockchain.get_header(0).unwrap();
+            assert!(!verify_inclusion(&genesis, tx, &resp.proof));
+        }
+
+        let resp = get_transaction_proof(
+            ctx.clone(),
+            GetTransactionProofRequest {
+                hash: hex::encode(<Hasher as Hash>::hash(b"unknown")),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(resp.header.is_none() && resp.proof.is_empty());
+
+        assert!(get_transaction_proof(
+            ctx.clone(),
+            GetTransactionProofRequest {
+                hash: "not-a-hash".into()
+            },
+        )
+        .await
+        .is_err());
+    }
+}
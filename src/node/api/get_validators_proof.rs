@@ -0,0 +1,67 @@
+use super::messages::{GetValidatorsProofRequest, GetValidatorsProofResponse};
+use super::{NodeContext, NodeError};
+use crate::blockchain::{Blockchain, BlockchainError};
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_validators_proof<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetValidatorsProofRequest,
+) -> Result<GetValidatorsProofResponse, NodeError> {
+    let context = context.read().await;
+    Ok(match req.epoch {
+        Some(epoch) => {
+            let (header, proof) = context
+                .blockchain
+                .get_epoch_validators_proof(epoch)?
+                .ok_or(BlockchainError::ValidatorsProofNotFound)?;
+            GetValidatorsProofResponse { header, proof }
+        }
+        None => GetValidatorsProofResponse {
+            header: context.blockchain.get_tip()?,
+            proof: context.blockchain.prove_validators()?,
+        },
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::light::{HeaderChain, LightChain};
+    use crate::config::blockchain::get_test_blockchain_config;
+    use crate::db::RamKvStore;
+
+    #[tokio::test]
+    async fn test_get_validators_proof() {
+        let ctx = test_context();
+        let resp = get_validators_proof(ctx.clone(), GetValidatorsProofRequest { epoch: None })
+            .await
+            .unwrap();
+        assert_eq!(resp.header.number, 100);
+        assert_eq!(resp.proof.stakers.len(), resp.proof.validator_set.len());
+
+        let headers = ctx.read().await.blockchain.get_headers(1, 100).unwrap();
+        let mut light = LightChain::new(RamKvStore::new(), get_test_blockchain_config()).unwrap();
+        light.extend(1, &headers).unwrap();
+        assert_eq!(light.get_tip().unwrap(), resp.header);
+        light.update_validators(&resp.header, &resp.proof).unwrap();
+
+        // Proofs of past epochs are taken at their first header (Blocks are 60s apart,
+        // epochs are 50s long)
+        let resp = get_validators_proof(ctx.clone(), GetValidatorsProofRequest { epoch: Some(1) })
+            .await
+            .unwrap();
+        assert_eq!(resp.header.number, 2);
+        light.update_validators(&resp.header, &resp.proof).unwrap();
+        assert!(matches!(
+            get_validators_proof(ctx.clone(), GetValidatorsProofRequest { epoch: Some(2) }).await,
+            Err(NodeError::BlockchainError(
+                BlockchainError::ValidatorsProofNotFound
+            ))
+        ));
+    }
+}
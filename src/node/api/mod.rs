@@ -30,12 +30,24 @@ mod get_mpn_account;
 pub use get_mpn_account::*;
 mod get_explorer_mpn_accounts;
 pub use get_explorer_mpn_accounts::*;
+mod get_explorer_history;
+pub use get_explorer_history::*;
 mod get_mempool;
 pub use get_mempool::*;
 mod get_debug_data;
 pub use get_debug_data::*;
 mod get_balance;
 pub use get_balance::*;
+mod get_allowance;
+pub use get_allowance::*;
+mod get_transaction;
+pub use get_transaction::*;
+mod get_transaction_proof;
+pub use get_transaction_proof::*;
+mod get_account_proof;
+pub use get_account_proof::*;
+mod get_validators_proof;
+pub use get_validators_proof::*;
 mod get_token;
 pub use get_token::*;
 mod post_validator_claim;
@@ -91,7 +103,13 @@ mod tests {
         .unwrap();
         for i in 0..NUM_BLOCKS {
             let block = blockchain
-                .draft_block((i * 60 + 30) as u32, &[], &validator_wallet, true)
+                .draft_block(
+                    (i * 60 + 30) as u32,
+                    &[],
+                    &validator_wallet,
+                    &Default::default(),
+                    true,
+                )
                 .unwrap()
                 .unwrap();
             blockchain.extend((i + 1) as u64, &[block.block]).unwrap();
@@ -58,11 +58,13 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
     pub fn get_info(&self) -> Result<Option<Peer>, NodeError> {
         let height = self.blockchain.get_height()?;
         let outdated_states = self.blockchain.get_outdated_contracts()?.len();
+        let pruned_height = self.blockchain.get_pruned_height()?;
         Ok(self.address.map(|address| Peer {
             address,
             height,
             pub_key: self.validator_wallet.get_address(),
             outdated_states,
+            pruned_height,
         }))
     }
 
@@ -104,6 +106,9 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
             self.opts.tx_max_time_alive,
             self.opts.tx_max_time_alive,
         )?;
+        if let Some(retention) = self.opts.prune_retention {
+            self.blockchain.prune(retention)?;
+        }
         Ok(())
     }
 
@@ -141,7 +146,13 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
         let ts = self.network_timestamp();
         let raw_txs: Vec<TransactionAndDelta> =
             self.mempool.tx_deltas().map(|(tx, _)| tx.clone()).collect();
-        match self.blockchain.draft_block(ts, &raw_txs, &wallet, true) {
+        match self.blockchain.draft_block(
+            ts,
+            &raw_txs,
+            &wallet,
+            &self.opts.accepted_fee_tokens,
+            true,
+        ) {
             Ok(draft) => {
                 if let Some(draft) = draft {
                     self.blockchain
@@ -75,6 +75,9 @@ pub async fn generate_block<K: KvStore, B: Blockchain<K>>(
                 mpn_nonce,
                 ctx.validator_wallet.clone(),
                 ctx.user_wallet.clone(),
+                ctx.blockchain
+                    .signing_chain_id(ctx.blockchain.get_height()?),
+                ctx.blockchain.mpn_chain_id(ctx.blockchain.get_height()?),
             )?);
         }
         if let Some(claim) = ctx.validator_claim.clone() {
@@ -7,7 +7,7 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
     let ctx = context.read().await;
     let net = ctx.outgoing.clone();
     let opts = ctx.opts.clone();
-    let max_block_size = ctx.blockchain.config().max_block_size;
+    let max_block_size = ctx.blockchain.params()?.max_block_size;
     let mut sorted_peers = ctx.peer_manager.get_peers();
     drop(ctx);
 
@@ -35,6 +35,11 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
             let start_height = std::cmp::min(local_height, peer.height);
             drop(ctx);
 
+            if start_height < peer.pruned_height {
+                log::info!("Skipped syncing with {} (Pruned)", peer.address);
+                break;
+            }
+
             // WARN: Chain might change when getting responses from users, maybe get all data needed before dropping ctx
 
             // Get all headers starting from the indices that we don't have.
@@ -190,6 +195,12 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
 
             drop(ctx);
 
+            // Peer has switched to a fork before its pruned height, its blocks are unavailable
+            if headers[0].number < peer.pruned_height {
+                log::info!("Skipped syncing with {} (Pruned)", peer.address);
+                break;
+            }
+
             if let Ok(resp) = net
                 .bincode_get::<GetBlocksRequest, GetBlocksResponse>(
                     format!("http://{}/bincode/blocks", peer.address).to_string(),
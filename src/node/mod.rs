@@ -13,7 +13,7 @@ use crate::client::{
     NETWORK_HEADER, SIGNATURE_HEADER,
 };
 use crate::common::*;
-use crate::core::Amount;
+use crate::core::{Amount, ExchangeRate, TokenId};
 use crate::crypto::ed25519;
 use crate::crypto::SignatureScheme;
 use crate::db::KvStore;
@@ -63,6 +63,10 @@ pub struct NodeOptions {
     pub mempool_max_fetch: usize,
     pub max_block_time_difference: u32,
     pub automatic_block_generation: bool,
+    // Tokens, other than Ziesha, that are accepted as tx fees by this validator
+    pub accepted_fee_tokens: HashMap<TokenId, ExchangeRate>,
+    // Number of recent blocks whose bodies and rollback data are kept (All when None)
+    pub prune_retention: Option<u64>,
 }
 
 fn fetch_signature(
@@ -255,6 +259,33 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
                     &api::get_balance(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
                 )?);
             }
+            (Method::GET, "/allowance") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_allowance(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
+                )?);
+            }
+            (Method::GET, "/transaction") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_transaction(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
+                )?);
+            }
+            (Method::GET, "/proof/account") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_account_proof(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
+                )?);
+            }
+            (Method::GET, "/proof/validators") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_validators_proof(Arc::clone(&context), serde_qs::from_str(&qs)?)
+                        .await?,
+                )?);
+            }
+            (Method::GET, "/proof/transaction") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_transaction_proof(Arc::clone(&context), serde_qs::from_str(&qs)?)
+                        .await?,
+                )?);
+            }
             (Method::GET, "/mpn/account") => {
                 *response.body_mut() = Body::from(serde_json::to_vec(
                     &api::get_mpn_account(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
@@ -337,6 +368,12 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
                         .await?,
                 )?);
             }
+            (Method::GET, "/explorer/history") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_explorer_history(Arc::clone(&context), serde_qs::from_str(&qs)?)
+                        .await?,
+                )?);
+            }
             (Method::GET, "/explorer/mempool") => {
                 *response.body_mut() = Body::from(serde_json::to_vec(
                     &api::get_explorer_mempool(Arc::clone(&context), serde_qs::from_str(&qs)?)
@@ -483,6 +520,7 @@ pub async fn node_create<K: KvStore, B: Blockchain<K>>(
     firewall: Option<Firewall>,
     mpn_workers: Vec<MpnWorker>,
 ) -> Result<(), NodeError> {
+    let chain_id = blockchain.chain_id();
     let context = Arc::new(RwLock::new(NodeContext {
         _phantom: std::marker::PhantomData,
         firewall,
@@ -503,8 +541,8 @@ pub async fn node_create<K: KvStore, B: Blockchain<K>>(
         mpn_work_pool: None,
         mempool: Mempool::new(Amount(1_000_000_000)),
         blockchain,
-        validator_wallet,
-        user_wallet,
+        validator_wallet: validator_wallet.with_chain_id(chain_id),
+        user_wallet: user_wallet.with_chain_id(chain_id),
         peer_manager: PeerManager::new(
             address,
             bootstrap,
@@ -2,10 +2,10 @@
 use crate::client::{messages::ValidatorClaim, PeerAddress};
 
 use crate::core::{
-    hash::Hash, Address, Amount, ContractDeposit, ContractId, ContractUpdate, ContractWithdraw,
-    Hasher, Money, MpnAddress, MpnDeposit, MpnWithdraw, Ratio, RegularSendEntry, Signature, Signer,
-    Token, TokenId, Transaction, TransactionAndDelta, TransactionData, ValidatorProof, Vrf,
-    ZkSigner,
+    hash::Hash, Address, Amount, ChainId, ConfigParam, ContractDeposit, ContractId, ContractUpdate,
+    ContractWithdraw, Hasher, Header, HtlcId, Money, MpnAddress, MpnDeposit, MpnWithdraw, Multisig,
+    ProposalId, Ratio, RegularSendEntry, Signature, Signer, Token, TokenId, TokenUpdate,
+    Transaction, TransactionAndDelta, TransactionData, ValidatorProof, Vrf, ZkSigner,
 };
 use crate::crypto::SignatureScheme;
 use crate::crypto::VerifiableRandomFunction;
@@ -24,6 +24,9 @@ pub struct TxBuilder {
     zk_private_key: <ZkSigner as ZkSignatureScheme>::Priv,
     address: Address,
     zk_address: <ZkSigner as ZkSignatureScheme>::Pub,
+    chain_id: Option<ChainId>, // Signatures are not chain-bound when None
+    valid_until: Option<u64>,  // Expiry height of the built transactions
+    mpn_chain_id: Option<ChainId>, // MPN transactions are bound separately (See `MpnReplayProtection`)
 }
 
 impl TxBuilder {
@@ -41,8 +44,23 @@ impl TxBuilder {
             zk_private_key: zk_sk,
             vrf_public_key,
             vrf_private_key,
+            chain_id: None,
+            valid_until: None,
+            mpn_chain_id: None,
         }
     }
+    pub fn with_chain_id(mut self, chain_id: ChainId) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+    pub fn with_mpn_chain_id(mut self, mpn_chain_id: Option<ChainId>) -> Self {
+        self.mpn_chain_id = mpn_chain_id;
+        self
+    }
+    pub fn with_valid_until(mut self, valid_until: Option<u64>) -> Self {
+        self.valid_until = valid_until;
+        self
+    }
     pub fn get_priv_key(&self) -> <Signer as SignatureScheme>::Priv {
         self.private_key.clone()
     }
@@ -64,13 +82,36 @@ impl TxBuilder {
         Signer::sign(&self.private_key, bytes)
     }
     pub fn sign_deposit(&self, tx: &mut ContractDeposit) {
-        let bytes = bincode::serialize(&tx).unwrap();
+        let bytes = tx.signing_bytes(self.chain_id.as_ref());
         tx.sig = Some(Signer::sign(&self.private_key, &bytes));
     }
     pub fn sign_tx(&self, tx: &mut Transaction) {
-        let bytes = bincode::serialize(&tx).unwrap();
+        let bytes = tx.signing_bytes(self.chain_id.as_ref());
         tx.sig = Signature::Signed(Signer::sign(&self.private_key, &bytes));
     }
+    // Adds the signature of this wallet to a transaction of a multisig account,
+    // returns false if this wallet is not one of its signers
+    pub fn cosign_tx(&self, tx: &mut Transaction, multisig: &Multisig) -> bool {
+        let index = if let Some(index) = multisig.index_of(&self.get_address()) {
+            index
+        } else {
+            return false;
+        };
+        let sig = Signer::sign(&self.private_key, &tx.signing_bytes(self.chain_id.as_ref()));
+        let mut sigs = match &tx.sig {
+            Signature::Multi(sigs) => sigs.clone(),
+            _ => Vec::new(),
+        };
+        sigs.retain(|(i, _)| *i != index);
+        sigs.push((index, sig));
+        tx.sig = Signature::Multi(sigs);
+        true
+    }
+    pub fn sign_header(&self, header: &mut Header) {
+        header.sig = Signature::Unsigned;
+        let bytes = bincode::serialize(&header).unwrap();
+        header.sig = Signature::Signed(Signer::sign(&self.private_key, &bytes));
+    }
     pub fn delegate(
         &self,
         memo: String,
@@ -88,6 +129,7 @@ impl TxBuilder {
             },
             nonce,
             fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -114,6 +156,126 @@ impl TxBuilder {
             },
             nonce,
             fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn redelegate(
+        &self,
+        memo: String,
+        from: Address,
+        to: Address,
+        amount: Amount,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::Redelegate { amount, from, to },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn claim_reward(
+        &self,
+        memo: String,
+        from: Address,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::ClaimReward { from },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn unjail(&self, memo: String, fee: Money, nonce: u32) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::Unjail,
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn propose(
+        &self,
+        memo: String,
+        changes: Vec<ConfigParam>,
+        activation_height: u64,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::Propose {
+                changes,
+                activation_height,
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn vote(
+        &self,
+        memo: String,
+        proposal_id: ProposalId,
+        approve: bool,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::Vote {
+                proposal_id,
+                approve,
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -137,6 +299,34 @@ impl TxBuilder {
             data: TransactionData::AutoDelegate { to, ratio },
             nonce,
             fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn report_equivocation(
+        &self,
+        memo: String,
+        first: Header,
+        second: Header,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::ReportEquivocation {
+                first: Box::new(first),
+                second: Box::new(second),
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -175,6 +365,7 @@ impl TxBuilder {
             },
             nonce,
             fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -220,6 +411,7 @@ impl TxBuilder {
         supply: Amount,
         decimals: u8,
         minter: Option<Address>,
+        metadata: Option<String>,
         fee: Money,
         nonce: u32,
     ) -> (TransactionAndDelta, TokenId) {
@@ -233,10 +425,12 @@ impl TxBuilder {
                     minter,
                     supply,
                     decimals,
+                    metadata,
                 },
             },
             nonce,
             fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -250,6 +444,29 @@ impl TxBuilder {
             token_id,
         )
     }
+    pub fn update_token(
+        &self,
+        memo: String,
+        token_id: TokenId,
+        update: TokenUpdate,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::UpdateToken { token_id, update },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
     pub fn create_multi_transaction(
         &self,
         memo: String,
@@ -263,6 +480,7 @@ impl TxBuilder {
             data: TransactionData::RegularSend { entries },
             nonce,
             fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -271,6 +489,213 @@ impl TxBuilder {
             state_delta: None,
         }
     }
+    #[allow(clippy::too_many_arguments)]
+    pub fn vested_send(
+        &self,
+        memo: String,
+        dst: Address,
+        amount: Money,
+        start: u64,
+        end: u64,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::VestedSend {
+                dst,
+                amount,
+                start,
+                end,
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_htlc(
+        &self,
+        memo: String,
+        recipient: Address,
+        amount: Money,
+        hashlock: <Hasher as Hash>::Output,
+        timeout_height: u64,
+        fee: Money,
+        nonce: u32,
+    ) -> (TransactionAndDelta, HtlcId) {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::CreateHtlc {
+                recipient,
+                amount,
+                hashlock,
+                timeout_height,
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        let htlc_id = HtlcId::new(&tx);
+        (
+            TransactionAndDelta {
+                tx,
+                state_delta: None,
+            },
+            htlc_id,
+        )
+    }
+    pub fn claim_htlc(
+        &self,
+        memo: String,
+        htlc_id: HtlcId,
+        preimage: Vec<u8>,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::ClaimHtlc { htlc_id, preimage },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn refund_htlc(
+        &self,
+        memo: String,
+        htlc_id: HtlcId,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::RefundHtlc { htlc_id },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn approve(
+        &self,
+        memo: String,
+        spender: Address,
+        amount: Money,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::Approve {
+                spender,
+                token_id: amount.token_id,
+                amount: amount.amount,
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn spend_from(
+        &self,
+        memo: String,
+        owner: Address,
+        entries: Vec<RegularSendEntry>,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::SpendFrom { owner, entries },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn create_multisig(
+        &self,
+        memo: String,
+        multisig: Multisig,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::CreateMultisig {
+                signers: multisig.signers,
+                threshold: multisig.threshold,
+            },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    // Transaction from a multisig account, only signed by this wallet
+    pub fn create_multisig_transaction(
+        &self,
+        memo: String,
+        multisig: &Multisig,
+        entries: Vec<RegularSendEntry>,
+        fee: Money,
+        nonce: u32,
+    ) -> Option<Transaction> {
+        let mut tx = Transaction {
+            memo,
+            src: Some(multisig.address()),
+            data: TransactionData::RegularSend { entries },
+            nonce,
+            fee,
+            valid_until: self.valid_until,
+            sig: Signature::Unsigned,
+        };
+        self.cosign_tx(&mut tx, multisig).then_some(tx)
+    }
     pub fn create_mpn_transaction(
         &self,
         to: MpnAddress,
@@ -288,7 +713,7 @@ impl TxBuilder {
             fee,
             sig: Default::default(),
         };
-        tx.sign(&self.zk_private_key);
+        tx.sign(&self.zk_private_key, self.mpn_chain_id.as_ref());
         tx
     }
     pub fn create_contract(
@@ -305,6 +730,7 @@ impl TxBuilder {
             data: TransactionData::CreateContract { contract },
             nonce,
             fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
@@ -342,6 +768,7 @@ impl TxBuilder {
             },
             nonce,
             fee: miner_fee,
+            valid_until: self.valid_until,
             sig: Signature::Unsigned,
         };
         let bytes = bincode::serialize(&tx).unwrap();
@@ -413,7 +840,10 @@ impl TxBuilder {
         };
         let sig = ZkSigner::sign(
             &self.zk_private_key,
-            crate::core::ZkHasher::hash(&[tx.fingerprint(), zk::ZkScalar::from(nonce as u64)]),
+            crate::core::ZkHasher::hash(&[
+                tx.fingerprint(self.chain_id.as_ref()),
+                zk::ZkScalar::from(nonce as u64),
+            ]),
         );
         let mut calldata_builder =
             zk::ZkStateBuilder::<crate::core::ZkHasher>::new(zk::MPN_WITHDRAW_STATE_MODEL.clone());
@@ -1,4 +1,4 @@
-use crate::core::{hash::Hash, Amount, Hasher, Money, TokenId, ZkHasher as ZkMainHasher};
+use crate::core::{hash::Hash, Amount, ChainId, Hasher, Money, TokenId, ZkHasher as ZkMainHasher};
 use crate::crypto::{jubjub, DeriveMpnAccountIndex, ZkSignatureScheme};
 
 use ff::{Field, PrimeField};
@@ -210,6 +210,10 @@ pub fn hash_to_scalar(inp: &[u8]) -> ZkScalar {
     ZkScalar::new(&Hasher::hash(inp))
 }
 
+pub fn chain_id_scalar(chain_id: &ChainId) -> ZkScalar {
+    ZkScalar::new(chain_id.as_ref())
+}
+
 impl std::fmt::Display for ZkScalar {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let rep = self
@@ -604,11 +608,23 @@ impl MpnTransaction {
     pub fn dst_index(&self, log4_account_capacity: u8) -> u64 {
         self.dst_pub_key.mpn_account_index(log4_account_capacity)
     }
-    pub fn verify_signature(&self) -> bool {
-        jubjub::JubJub::<ZkMainHasher>::verify(&self.src_pub_key, self.hash(), &self.sig)
+    pub fn verify_signature(&self, chain_id: Option<&ChainId>) -> bool {
+        jubjub::JubJub::<ZkMainHasher>::verify(
+            &self.src_pub_key,
+            self.signing_hash(chain_id),
+            &self.sig,
+        )
     }
-    pub fn sign(&mut self, sk: &jubjub::PrivateKey) {
-        self.sig = jubjub::JubJub::<ZkMainHasher>::sign(sk, self.hash());
+    pub fn sign(&mut self, sk: &jubjub::PrivateKey, chain_id: Option<&ChainId>) {
+        self.sig = jubjub::JubJub::<ZkMainHasher>::sign(sk, self.signing_hash(chain_id));
+    }
+    // The message actually signed. The update circuit recomputes it, binding it to the
+    // chain-id it was built for (See `UpdateCircuit::chain_id`)
+    pub fn signing_hash(&self, chain_id: Option<&ChainId>) -> ZkScalar {
+        match chain_id {
+            Some(chain_id) => ZkMainHasher::hash(&[self.hash(), chain_id_scalar(chain_id)]),
+            None => self.hash(),
+        }
     }
     pub fn hash(&self) -> ZkScalar {
         let dst_pub_decom = self.dst_pub_key.0.decompress();